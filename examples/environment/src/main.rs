@@ -40,7 +40,9 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
         &context,
         TextureCubeMap::<f16>::new_from_equirectangular(
             &context,
-            &loaded.hdr_image("chinese_garden_4k").unwrap(),
+            &loaded
+                .hdr_image("examples/assets/chinese_garden_4k.hdr")
+                .unwrap(),
         )
         .unwrap(),
     )
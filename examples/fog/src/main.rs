@@ -36,7 +36,7 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
             .await
             .unwrap();
 
-    let (meshes, materials) = loaded.obj("suzanne.obj").unwrap();
+    let (meshes, materials) = loaded.obj("examples/assets/suzanne.obj").unwrap();
     let mut monkey_material = PhysicalMaterial::new(&context, &materials[0]).unwrap();
     monkey_material.render_states.cull = Cull::Back;
     let monkey = Model::new_with_material(&context, &meshes[0], monkey_material);
@@ -24,7 +24,13 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     )
     .await
     .unwrap();
-    let image = Texture2D::new(&context, &loaded.hdr_image("").unwrap()).unwrap();
+    let image = Texture2D::new(
+        &context,
+        &loaded
+            .hdr_image("examples/assets/syferfontein_18d_clear_4k.hdr")
+            .unwrap(),
+    )
+    .unwrap();
 
     let mut gui = GUI::new(&context).unwrap();
 
@@ -44,12 +44,13 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     )
     .await
     .unwrap();
-    let (mut cpu_meshes, cpu_materials) = loaded.gltf("DamagedHelmet.glb").unwrap();
+    let (mut cpu_meshes, cpu_materials) = loaded
+        .gltf("examples/assets/gltf/DamagedHelmet.glb")
+        .unwrap();
     let mut material = PhysicalMaterial::new(&context, &cpu_materials[0]).unwrap();
     material.render_states.cull = Cull::Back;
     cpu_meshes[0].compute_tangents().unwrap();
     let mut model = Model::new_with_material(&context, &cpu_meshes[0], material).unwrap();
-    model.set_transformation(Mat4::from_angle_x(degrees(90.0)));
 
     let mut plane = Model::new_with_material(
         &context,
@@ -110,7 +110,7 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
         ]
     ).await.unwrap();
 
-    let (cpu_meshes, cpu_materials) = loaded.gltf("Sponza.gltf").unwrap();
+    let (cpu_meshes, cpu_materials) = loaded.gltf("https://raw.githubusercontent.com/KhronosGroup/glTF-Sample-Models/master/2.0/Sponza/glTF/Sponza.gltf").unwrap();
 
     let mut materials = Vec::new();
     for m in cpu_materials.iter() {
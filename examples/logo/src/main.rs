@@ -69,9 +69,13 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     )
     .await
     .unwrap();
-    let environment_map =
-        TextureCubeMap::<f32>::new_from_equirectangular(&context, &loaded.hdr_image("").unwrap())
-            .unwrap();
+    let environment_map = TextureCubeMap::<f32>::new_from_equirectangular(
+        &context,
+        &loaded
+            .hdr_image("examples/assets/syferfontein_18d_clear_4k.hdr")
+            .unwrap(),
+    )
+    .unwrap();
     let light = AmbientLight {
         environment: Some(Environment::new(&context, &environment_map).unwrap()),
         ..Default::default()
@@ -26,7 +26,9 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     .await
     .unwrap();
 
-    let (mut cpu_meshes, cpu_materials) = loaded.gltf("NormalTangentTest.glb").unwrap();
+    let (mut cpu_meshes, cpu_materials) = loaded
+        .gltf("examples/assets/gltf/NormalTangentTest.glb")
+        .unwrap();
     let material = PhysicalMaterial::new(&context, &cpu_materials[0]).unwrap();
     cpu_meshes[0].compute_tangents().unwrap();
 
@@ -44,7 +46,9 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     instanced_model_with_computed_tangents
         .set_transformation(Mat4::from_translation(vec3(1.4, -1.2, 0.0)));
 
-    let (cpu_meshes, cpu_materials) = loaded.gltf("NormalTangentMirrorTest.glb").unwrap();
+    let (cpu_meshes, cpu_materials) = loaded
+        .gltf("examples/assets/gltf/NormalTangentMirrorTest.glb")
+        .unwrap();
     let material = PhysicalMaterial::new(&context, &cpu_materials[0]).unwrap();
 
     let mut model_with_loaded_tangents =
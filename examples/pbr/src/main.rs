@@ -39,15 +39,17 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     .await
     .unwrap();
 
-    let environment_map = loaded.hdr_image("chinese").unwrap();
+    let environment_map = loaded
+        .hdr_image("examples/assets/chinese_garden_4k.hdr")
+        .unwrap();
     let skybox = Skybox::new_from_equirectangular(&context, &environment_map).unwrap();
 
-    let (mut cpu_meshes, cpu_materials) = loaded.gltf("DamagedHelmet.glb").unwrap();
-    let mut material = PhysicalMaterial::new(&context, &cpu_materials[0]).unwrap();
-    material.render_states.cull = Cull::Back;
+    let (mut cpu_meshes, cpu_materials) = loaded
+        .gltf("examples/assets/gltf/DamagedHelmet.glb")
+        .unwrap();
+    let material = PhysicalMaterial::new(&context, &cpu_materials[0]).unwrap();
     cpu_meshes[0].compute_tangents().unwrap();
-    let mut model = Model::new_with_material(&context, &cpu_meshes[0], material.clone()).unwrap();
-    model.set_transformation(Mat4::from_angle_x(degrees(90.0)));
+    let model = Model::new_with_material(&context, &cpu_meshes[0], material.clone()).unwrap();
 
     let light =
         AmbientLight::new_with_environment(&context, 1.0, Color::WHITE, skybox.texture()).unwrap();
@@ -99,6 +101,7 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
                         } else {
                             None
                         },
+                        albedo_texture_transform: model.material.albedo_texture_transform,
                         metallic: model.material.metallic,
                         roughness: model.material.roughness,
                         metallic_roughness_texture: if metallic_roughness_enabled {
@@ -106,18 +109,23 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
                         } else {
                             None
                         },
+                        metallic_roughness_texture_transform: model
+                            .material
+                            .metallic_roughness_texture_transform,
                         normal_scale: model.material.normal_scale,
                         normal_texture: if normal_map_enabled {
                             model.material.normal_texture.clone()
                         } else {
                             None
                         },
+                        normal_texture_transform: model.material.normal_texture_transform,
                         occlusion_strength: model.material.occlusion_strength,
                         occlusion_texture: if occlusion_map_enabled {
                             model.material.occlusion_texture.clone()
                         } else {
                             None
                         },
+                        occlusion_texture_transform: model.material.occlusion_texture_transform,
                         emissive: if emissive_map_enabled {
                             model.material.emissive
                         } else {
@@ -128,8 +136,45 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
                         } else {
                             None
                         },
+                        emissive_texture_transform: model.material.emissive_texture_transform,
+                        emissive_strength: model.material.emissive_strength,
+                        clearcoat: model.material.clearcoat,
+                        clearcoat_texture: model.material.clearcoat_texture.clone(),
+                        clearcoat_texture_transform: model.material.clearcoat_texture_transform,
+                        clearcoat_roughness: model.material.clearcoat_roughness,
+                        clearcoat_roughness_texture: model
+                            .material
+                            .clearcoat_roughness_texture
+                            .clone(),
+                        clearcoat_roughness_texture_transform: model
+                            .material
+                            .clearcoat_roughness_texture_transform,
+                        clearcoat_normal_scale: model.material.clearcoat_normal_scale,
+                        clearcoat_normal_texture: if normal_map_enabled {
+                            model.material.clearcoat_normal_texture.clone()
+                        } else {
+                            None
+                        },
+                        clearcoat_normal_texture_transform: model
+                            .material
+                            .clearcoat_normal_texture_transform,
+                        sheen_color: model.material.sheen_color,
+                        sheen_color_texture: model.material.sheen_color_texture.clone(),
+                        sheen_color_texture_transform: model.material.sheen_color_texture_transform,
+                        sheen_roughness: model.material.sheen_roughness,
+                        sheen_roughness_texture: model.material.sheen_roughness_texture.clone(),
+                        sheen_roughness_texture_transform: model
+                            .material
+                            .sheen_roughness_texture_transform,
+                        transmission: model.material.transmission,
+                        transmission_texture: model.material.transmission_texture.clone(),
+                        transmission_texture_transform: model
+                            .material
+                            .transmission_texture_transform,
+                        index_of_refraction: model.material.index_of_refraction,
                         render_states: model.material.render_states,
                         is_transparent: model.material.is_transparent,
+                        alpha_cutout: model.material.alpha_cutout,
                         lighting_model: LightingModel::Cook(
                             NormalDistributionFunction::TrowbridgeReitzGGX,
                             GeometryFunction::SmithSchlickGGX,
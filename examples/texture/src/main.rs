@@ -47,7 +47,14 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
     let skybox = Skybox::new(
         &context,
         &loaded
-            .cube_image("right", "left", "top", "top", "front", "back")
+            .cube_image(
+                "examples/assets/skybox_evening/right.jpg",
+                "examples/assets/skybox_evening/left.jpg",
+                "examples/assets/skybox_evening/top.jpg",
+                "examples/assets/skybox_evening/top.jpg",
+                "examples/assets/skybox_evening/front.jpg",
+                "examples/assets/skybox_evening/back.jpg",
+            )
             .unwrap(),
     )
     .unwrap();
@@ -56,14 +63,19 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
         &CpuMesh::cube(),
         ColorMaterial {
             texture: Some(std::rc::Rc::new(
-                Texture2D::new(&context, &loaded.image("test_texture").unwrap()).unwrap(),
+                Texture2D::new(
+                    &context,
+                    &loaded.image("examples/assets/test_texture.jpg").unwrap(),
+                )
+                .unwrap(),
             )),
             ..Default::default()
         },
     )
     .unwrap();
     box_object.material.render_states.cull = Cull::Back;
-    let (penguin_cpu_meshes, penguin_cpu_materials) = loaded.obj("PenguinBaseMesh.obj").unwrap();
+    let (penguin_cpu_meshes, penguin_cpu_materials) =
+        loaded.obj("examples/assets/PenguinBaseMesh.obj").unwrap();
     let mut penguin_object = Model::new_with_material(
         &context,
         &penguin_cpu_meshes[0],
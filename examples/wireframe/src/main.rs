@@ -37,7 +37,7 @@ pub async fn run(screenshot: Option<std::path::PathBuf>) {
             .await
             .unwrap();
 
-    let (mut meshes, materials) = loaded.obj("suzanne.obj").unwrap();
+    let (mut meshes, materials) = loaded.obj("examples/assets/suzanne.obj").unwrap();
     let mut cpu_mesh = meshes.remove(0);
     cpu_mesh.transform(&Mat4::from_translation(vec3(0.0, 2.0, 0.0)));
     let mut model = Model::new_with_material(
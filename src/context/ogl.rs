@@ -76,6 +76,27 @@ impl GLContext {
         }
     }
 
+    pub fn get_extension(&self, name: &str) -> bool {
+        unsafe {
+            let mut count = 0;
+            self.inner.GetIntegerv(consts::NUM_EXTENSIONS, &mut count);
+            (0..count.max(0) as u32).any(|i| {
+                let extension = self.inner.GetStringi(consts::EXTENSIONS, i);
+                !extension.is_null()
+                    && std::ffi::CStr::from_ptr(extension as *const std::os::raw::c_char).to_bytes()
+                        == name.as_bytes()
+            })
+        }
+    }
+
+    pub fn get_integer(&self, name: u32) -> i32 {
+        let mut value = 0;
+        unsafe {
+            self.inner.GetIntegerv(name, &mut value);
+        }
+        value
+    }
+
     pub fn create_shader(&self, type_: ShaderType) -> Option<Shader> {
         let id = unsafe { self.inner.CreateShader(type_.to_const()) };
         Some(Shader(id))
@@ -852,6 +873,32 @@ impl GLContext {
         }
     }
 
+    pub fn compressed_tex_sub_image_2d(
+        &self,
+        target: u32,
+        level: u32,
+        x_offset: u32,
+        y_offset: u32,
+        width: u32,
+        height: u32,
+        format: u32,
+        data: &[u8],
+    ) {
+        unsafe {
+            self.inner.CompressedTexSubImage2D(
+                target,
+                level as i32,
+                x_offset as i32,
+                y_offset as i32,
+                width as i32,
+                height as i32,
+                format,
+                data.len() as i32,
+                data.as_ptr() as *const consts::types::GLvoid,
+            );
+        }
+    }
+
     pub fn tex_image_2d_with_f32_data(
         &self,
         target: u32,
@@ -31,6 +31,19 @@ impl GLContext {
         self.inner.finish();
     }
 
+    pub fn get_extension(&self, name: &str) -> bool {
+        // Getting the extension also enables it
+        self.inner.get_extension(name).ok().flatten().is_some()
+    }
+
+    pub fn get_integer(&self, name: u32) -> i32 {
+        self.inner
+            .get_parameter(name)
+            .ok()
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0) as i32
+    }
+
     pub fn bind_buffer_base(&self, target: u32, index: u32, buffer: &Buffer) {
         self.inner.bind_buffer_base(target, index, Some(buffer));
     }
@@ -243,6 +256,29 @@ impl GLContext {
             .unwrap();
     }
 
+    pub fn compressed_tex_sub_image_2d(
+        &self,
+        target: u32,
+        level: u32,
+        x_offset: u32,
+        y_offset: u32,
+        width: u32,
+        height: u32,
+        format: u32,
+        data: &[u8],
+    ) {
+        self.inner.compressed_tex_sub_image_2d_with_u8_array(
+            target,
+            level as i32,
+            x_offset as i32,
+            y_offset as i32,
+            width as i32,
+            height as i32,
+            format,
+            &mut data.to_vec(),
+        );
+    }
+
     pub fn tex_image_2d_with_u8_data(
         &self,
         target: u32,
@@ -17,6 +17,20 @@ pub struct Context {
     programs: Rc<RefCell<HashMap<String, Program>>>,
     effects: Rc<RefCell<HashMap<String, ImageEffect>>>,
     camera2d: Rc<RefCell<Option<Camera>>>,
+    compressed_texture_support: CompressedTextureSupport,
+    max_texture_size: u32,
+}
+
+///
+/// The families of compressed texture formats which are supported by the graphics hardware.
+///
+#[derive(Clone, Copy, Debug)]
+struct CompressedTextureSupport {
+    s3tc: bool,
+    rgtc: bool,
+    bptc: bool,
+    etc2: bool,
+    astc: bool,
 }
 
 impl Context {
@@ -24,11 +38,63 @@ impl Context {
     /// Creates a new context from a [OpenGL/WebGL context](GLContext).
     ///
     pub fn from_gl_context(context: GLContext) -> Self {
+        // The extensions are named differently on desktop and web and some formats are part of the core desktop OpenGL specification.
+        let desktop = cfg!(not(target_arch = "wasm32"));
+        let supports = |names: &[&str]| names.iter().any(|name| context.get_extension(name));
+        let compressed_texture_support = CompressedTextureSupport {
+            s3tc: supports(&[
+                "GL_EXT_texture_compression_s3tc",
+                "WEBGL_compressed_texture_s3tc",
+            ]),
+            rgtc: desktop || supports(&["EXT_texture_compression_rgtc"]),
+            bptc: supports(&[
+                "GL_ARB_texture_compression_bptc",
+                "EXT_texture_compression_bptc",
+            ]),
+            etc2: supports(&["GL_ARB_ES3_compatibility", "WEBGL_compressed_texture_etc"]),
+            astc: supports(&[
+                "GL_KHR_texture_compression_astc_ldr",
+                "WEBGL_compressed_texture_astc",
+            ]),
+        };
+        let max_texture_size = context
+            .get_integer(crate::context::consts::MAX_TEXTURE_SIZE)
+            .max(0) as u32;
         Self {
             context,
             programs: Rc::new(RefCell::new(HashMap::new())),
             effects: Rc::new(RefCell::new(HashMap::new())),
             camera2d: Rc::new(RefCell::new(None)),
+            compressed_texture_support,
+            max_texture_size,
+        }
+    }
+
+    ///
+    /// Returns the maximum width and height of a texture supported by the graphics hardware.
+    ///
+    pub fn max_texture_size(&self) -> u32 {
+        self.max_texture_size
+    }
+
+    ///
+    /// Returns whether textures with the given compressed format can be created, ie. whether the format is supported by the graphics hardware.
+    ///
+    pub fn supports_compressed_format(&self, format: CompressedFormat) -> bool {
+        let support = self.compressed_texture_support;
+        match format {
+            CompressedFormat::Bc1Rgb
+            | CompressedFormat::Bc1Rgba
+            | CompressedFormat::Bc2
+            | CompressedFormat::Bc3 => support.s3tc,
+            CompressedFormat::Bc4 | CompressedFormat::Bc5 => support.rgtc,
+            CompressedFormat::Bc6h | CompressedFormat::Bc7 => support.bptc,
+            CompressedFormat::Etc2Rgb
+            | CompressedFormat::Etc2Rgba1
+            | CompressedFormat::Etc2Rgba
+            | CompressedFormat::EacR
+            | CompressedFormat::EacRg => support.etc2,
+            CompressedFormat::Astc { .. } => support.astc && format.internal_format().is_ok(),
         }
     }
 
@@ -134,6 +200,14 @@ mod cpu_mesh;
 #[doc(inline)]
 pub use cpu_mesh::*;
 
+mod cpu_skin;
+#[doc(inline)]
+pub use cpu_skin::*;
+
+mod animation;
+#[doc(inline)]
+pub use animation::*;
+
 pub mod render_states;
 pub use render_states::*;
 
@@ -204,6 +278,14 @@ pub enum CoreError {
     TextureCreation,
     #[error("invalid size of texture data (got {0} pixels but expected {1} pixels)")]
     InvalidTextureLength(usize, usize),
+    #[error("invalid size of compressed texture data (got {0} bytes but expected {1} bytes)")]
+    InvalidCompressedTextureLength(usize, usize),
+    #[error("the compressed texture format {0:?} is not supported by the graphics hardware or by this type of texture")]
+    UnsupportedCompressedFormat(CompressedFormat),
+    #[error("the compressed texture format {0:?} requires texture data of type u8")]
+    CompressedTextureDataType(CompressedFormat),
+    #[error("the image with size {0}x{1} is not a cube map in a horizontal (4:3) or vertical (3:4) cross layout")]
+    InvalidCubeMapCrossLayout(u32, u32),
     #[error("the render call requires the {0} vertex buffer which is missing on the given mesh")]
     MissingMeshBuffer(String),
     #[error(
@@ -224,4 +306,10 @@ pub enum CoreError {
     NegativeDistance,
     #[error("a minimum must be smaller than a maximum")]
     MinimumLargerThanMaximum,
+    #[error("the joints of the skin {0} does not form a hierarchy")]
+    CyclicJointHierarchy(String),
+    #[error("the skin {0} has no joints")]
+    EmptySkin(String),
+    #[error("{0} morph targets of a mesh with {1} vertices require a texture of height {2} which exceeds the maximum texture size {3}")]
+    TooManyMorphTargets(usize, usize, u32, u32),
 }
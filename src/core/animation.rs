@@ -0,0 +1,395 @@
+use crate::core::*;
+
+///
+/// Defines how the values between two keyframes in a [KeyframeTrack] are computed.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyframeInterpolation {
+    /// The value of the previous keyframe is used until the next keyframe is reached.
+    Step,
+    /// The values are linearly interpolated (spherically linear interpolated for rotations).
+    #[default]
+    Linear,
+    /// The values are interpolated using a cubic Hermite spline.
+    /// In this case, each keyframe consists of three values: An in-tangent, the value and an out-tangent.
+    CubicSpline,
+}
+
+///
+/// A value that can be animated by a [KeyframeTrack], for example a translation ([Vec3]), a rotation ([Quat]) or morph target weights (`Vec<f32>`).
+///
+pub trait KeyframeValue: Clone {
+    ///
+    /// Interpolates linearly between this value and the other value, where `t` is a number between 0 and 1.
+    ///
+    fn interpolate_linear(&self, other: &Self, t: f32) -> Self;
+
+    ///
+    /// Evaluates the cubic Hermite spline between `value0` and `value1` with the given tangents,
+    /// where `t` is a number between 0 and 1 and `duration` is the time between the two keyframes.
+    ///
+    fn interpolate_cubic_spline(
+        value0: &Self,
+        out_tangent0: &Self,
+        value1: &Self,
+        in_tangent1: &Self,
+        t: f32,
+        duration: f32,
+    ) -> Self;
+}
+
+fn hermite_coefficients(t: f32, duration: f32) -> [f32; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        2.0 * t3 - 3.0 * t2 + 1.0,
+        (t3 - 2.0 * t2 + t) * duration,
+        -2.0 * t3 + 3.0 * t2,
+        (t3 - t2) * duration,
+    ]
+}
+
+impl KeyframeValue for Vec3 {
+    fn interpolate_linear(&self, other: &Self, t: f32) -> Self {
+        self.lerp(*other, t)
+    }
+
+    fn interpolate_cubic_spline(
+        value0: &Self,
+        out_tangent0: &Self,
+        value1: &Self,
+        in_tangent1: &Self,
+        t: f32,
+        duration: f32,
+    ) -> Self {
+        let c = hermite_coefficients(t, duration);
+        value0 * c[0] + out_tangent0 * c[1] + value1 * c[2] + in_tangent1 * c[3]
+    }
+}
+
+impl KeyframeValue for Quat {
+    fn interpolate_linear(&self, other: &Self, t: f32) -> Self {
+        // Interpolate along the shortest path
+        let other = if self.dot(*other) < 0.0 {
+            -other
+        } else {
+            *other
+        };
+        self.slerp(other, t).normalize()
+    }
+
+    fn interpolate_cubic_spline(
+        value0: &Self,
+        out_tangent0: &Self,
+        value1: &Self,
+        in_tangent1: &Self,
+        t: f32,
+        duration: f32,
+    ) -> Self {
+        let c = hermite_coefficients(t, duration);
+        (value0 * c[0] + out_tangent0 * c[1] + value1 * c[2] + in_tangent1 * c[3]).normalize()
+    }
+}
+
+impl KeyframeValue for Vec<f32> {
+    fn interpolate_linear(&self, other: &Self, t: f32) -> Self {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| a + (b - a) * t)
+            .collect()
+    }
+
+    fn interpolate_cubic_spline(
+        value0: &Self,
+        out_tangent0: &Self,
+        value1: &Self,
+        in_tangent1: &Self,
+        t: f32,
+        duration: f32,
+    ) -> Self {
+        let c = hermite_coefficients(t, duration);
+        (0..value0.len().min(value1.len()))
+            .map(|i| {
+                value0[i] * c[0]
+                    + out_tangent0.get(i).cloned().unwrap_or(0.0) * c[1]
+                    + value1[i] * c[2]
+                    + in_tangent1.get(i).cloned().unwrap_or(0.0) * c[3]
+            })
+            .collect()
+    }
+}
+
+///
+/// A sequence of keyframes, ie. values at specific points in time, which can be sampled at any time.
+///
+#[derive(Debug, Clone, Default)]
+pub struct KeyframeTrack<T: KeyframeValue> {
+    /// The interpolation between the keyframes.
+    pub interpolation: KeyframeInterpolation,
+    /// The time of each keyframe in seconds in increasing order.
+    pub times: Vec<f32>,
+    /// The value of each keyframe.
+    /// If the interpolation is [KeyframeInterpolation::CubicSpline], each keyframe has three values (in-tangent, value, out-tangent),
+    /// otherwise one value per keyframe.
+    pub values: Vec<T>,
+}
+
+impl<T: KeyframeValue> KeyframeTrack<T> {
+    ///
+    /// Returns the time of the last keyframe.
+    ///
+    pub fn duration(&self) -> f32 {
+        self.times.last().cloned().unwrap_or(0.0)
+    }
+
+    ///
+    /// Returns the value at the given time in seconds.
+    /// Before the first keyframe, the value of the first keyframe is returned and after the last keyframe, the value of the last keyframe is returned.
+    /// Returns `None` if the track has no keyframes.
+    ///
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let stride = if self.interpolation == KeyframeInterpolation::CubicSpline {
+            3
+        } else {
+            1
+        };
+        let value_count = self.values.len() / stride;
+        let count = self.times.len().min(value_count);
+        if count == 0 {
+            return None;
+        }
+        let value = |index: usize| &self.values[index * stride + stride / 2];
+        if time <= self.times[0] {
+            return Some(value(0).clone());
+        }
+        if time >= self.times[count - 1] {
+            return Some(value(count - 1).clone());
+        }
+        let next = self.times[..count].partition_point(|t| *t <= time);
+        let previous = next - 1;
+        let duration = self.times[next] - self.times[previous];
+        let t = if duration > 0.0 {
+            (time - self.times[previous]) / duration
+        } else {
+            0.0
+        };
+        Some(match self.interpolation {
+            KeyframeInterpolation::Step => value(previous).clone(),
+            KeyframeInterpolation::Linear => value(previous).interpolate_linear(value(next), t),
+            KeyframeInterpolation::CubicSpline => T::interpolate_cubic_spline(
+                value(previous),
+                &self.values[previous * 3 + 2],
+                value(next),
+                &self.values[next * 3],
+                t,
+                duration,
+            ),
+        })
+    }
+}
+
+///
+/// A transformation decomposed into a translation, a rotation and a non-uniform scale, which is applied in the order scale, rotation and then translation.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationTransformation {
+    /// The translation.
+    pub translation: Vec3,
+    /// The rotation.
+    pub rotation: Quat,
+    /// The non-uniform scale.
+    pub scale: Vec3,
+}
+
+impl AnimationTransformation {
+    ///
+    /// Returns the transformation as a matrix.
+    ///
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_translation(self.translation)
+            * Mat4::from(self.rotation)
+            * Mat4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
+    }
+}
+
+impl Default for AnimationTransformation {
+    fn default() -> Self {
+        Self {
+            translation: vec3(0.0, 0.0, 0.0),
+            rotation: Quat::one(),
+            scale: vec3(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+///
+/// Identifies the target of an [AnimationChannel].
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnimationTarget {
+    /// The target with the given name, for example a [SceneNode](crate::SceneNode) or a [CpuJoint] with this name.
+    Name(String),
+    /// The target with the given node index, for example a [SceneNode](crate::SceneNode) or a [CpuJoint] with this node index.
+    /// Used by loaded animations, where the index is the index of the node in the file, since the names of the nodes are not necessarily unique.
+    Node(usize),
+}
+
+impl AnimationTarget {
+    ///
+    /// Returns true if this target identifies an object with the given name and node index.
+    ///
+    pub fn matches(&self, name: &str, node_index: Option<usize>) -> bool {
+        match self {
+            Self::Name(target) => target == name,
+            Self::Node(target) => node_index == Some(*target),
+        }
+    }
+}
+
+impl Default for AnimationTarget {
+    fn default() -> Self {
+        Self::Name(String::new())
+    }
+}
+
+///
+/// The animation of the transformation of a single target, for example a node in a scene graph or a joint in a [CpuSkin].
+///
+#[derive(Debug, Clone, Default)]
+pub struct AnimationChannel {
+    /// The animated target.
+    pub target: AnimationTarget,
+    /// The transformation of the target when it is not animated.
+    /// The translation, rotation or scale is used if the corresponding track is not specified.
+    pub rest: AnimationTransformation,
+    /// The translation keyframes, if the translation is animated.
+    pub translation: Option<KeyframeTrack<Vec3>>,
+    /// The rotation keyframes, if the rotation is animated.
+    pub rotation: Option<KeyframeTrack<Quat>>,
+    /// The scale keyframes, if the scale is animated.
+    pub scale: Option<KeyframeTrack<Vec3>>,
+    /// The keyframes of the morph target weights of the meshes of the target, if the weights are animated, see [CpuMesh::morph_targets].
+    pub morph_weights: Option<KeyframeTrack<Vec<f32>>>,
+}
+
+impl AnimationChannel {
+    ///
+    /// Returns the time of the last keyframe in any of the tracks.
+    ///
+    pub fn duration(&self) -> f32 {
+        let mut duration: f32 = 0.0;
+        if let Some(ref track) = self.translation {
+            duration = duration.max(track.duration());
+        }
+        if let Some(ref track) = self.rotation {
+            duration = duration.max(track.duration());
+        }
+        if let Some(ref track) = self.scale {
+            duration = duration.max(track.duration());
+        }
+        if let Some(ref track) = self.morph_weights {
+            duration = duration.max(track.duration());
+        }
+        duration
+    }
+
+    ///
+    /// Returns the transformation of the target at the given time in seconds.
+    ///
+    pub fn sample(&self, time: f32) -> AnimationTransformation {
+        AnimationTransformation {
+            translation: self
+                .translation
+                .as_ref()
+                .and_then(|track| track.sample(time))
+                .unwrap_or(self.rest.translation),
+            rotation: self
+                .rotation
+                .as_ref()
+                .and_then(|track| track.sample(time))
+                .unwrap_or(self.rest.rotation),
+            scale: self
+                .scale
+                .as_ref()
+                .and_then(|track| track.sample(time))
+                .unwrap_or(self.rest.scale),
+        }
+    }
+}
+
+///
+/// A keyframe animation of one or more targets, each identified by an [AnimationTarget].
+/// Can be constructed manually or loaded via [io](crate::io).
+///
+/// Sample the clip each frame with for example the elapsed time and apply the result to the targets, either with [AnimationClip::transformation]
+/// and a call to `set_transformation` on the animated object or by using a helper like [CpuSkin::animate] or [SceneNode::animate](crate::SceneNode::animate).
+/// To loop the animation, use the elapsed time modulo [AnimationClip::duration].
+///
+#[derive(Debug, Clone, Default)]
+pub struct AnimationClip {
+    /// Name.
+    pub name: String,
+    /// The animation of each target.
+    pub channels: Vec<AnimationChannel>,
+}
+
+impl AnimationClip {
+    ///
+    /// Returns the time of the last keyframe in any of the channels.
+    ///
+    pub fn duration(&self) -> f32 {
+        self.channels
+            .iter()
+            .map(|channel| channel.duration())
+            .fold(0.0, f32::max)
+    }
+
+    ///
+    /// Returns the transformation of the given target at the given time in seconds
+    /// or `None` if the target is not animated by this clip.
+    ///
+    pub fn transformation(&self, target: &AnimationTarget, time: f32) -> Option<Mat4> {
+        self.channels
+            .iter()
+            .find(|channel| &channel.target == target)
+            .map(|channel| channel.sample(time).matrix())
+    }
+
+    ///
+    /// Returns the morph target weights of the given target at the given time in seconds
+    /// or `None` if the morph target weights of the target are not animated by this clip.
+    ///
+    pub fn morph_weights(&self, target: &AnimationTarget, time: f32) -> Option<Vec<f32>> {
+        self.channels
+            .iter()
+            .find(|channel| &channel.target == target)
+            .and_then(|channel| channel.morph_weights.as_ref())
+            .and_then(|track| track.sample(time))
+    }
+
+    ///
+    /// Returns the target and transformation of each animated target at the given time in seconds.
+    ///
+    pub fn transformations(&self, time: f32) -> Vec<(&AnimationTarget, Mat4)> {
+        self.channels
+            .iter()
+            .map(|channel| (&channel.target, channel.sample(time).matrix()))
+            .collect()
+    }
+
+    ///
+    /// Returns the transformation at the given time in seconds of the object with the given name and node index
+    /// or `None` if the object is not animated by this clip, see [AnimationTarget::matches].
+    ///
+    pub fn transformation_of(
+        &self,
+        name: &str,
+        node_index: Option<usize>,
+        time: f32,
+    ) -> Option<Mat4> {
+        self.channels
+            .iter()
+            .find(|channel| channel.target.matches(name, node_index))
+            .map(|channel| channel.sample(time).matrix())
+    }
+}
@@ -50,6 +50,46 @@ impl LightingModel {
     }
 }
 
+///
+/// Specifies how a texture of a material is mapped onto a mesh, ie. which set of uv coordinates of the mesh is used
+/// and how the uv coordinates are transformed before sampling the texture.
+/// The uv coordinates are first scaled, then rotated and finally offset, as specified by the glTF KHR_texture_transform extension.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextureTransform {
+    /// The offset of the uv coordinates.
+    pub offset: Vec2,
+    /// The counter-clockwise rotation of the uv coordinates around the origin in radians.
+    pub rotation: f32,
+    /// The scale of the uv coordinates.
+    pub scale: Vec2,
+    /// The set of uv coordinates used, 0 for [CpuMesh::uvs] and 1 for [CpuMesh::secondary_uvs].
+    pub uv_set: u32,
+}
+
+impl TextureTransform {
+    ///
+    /// Returns the transformation of the uv coordinates as a matrix.
+    ///
+    pub fn matrix(&self) -> Mat3 {
+        let (sin, cos) = self.rotation.sin_cos();
+        Mat3::from_translation(self.offset)
+            * Mat3::new(cos, -sin, 0.0, sin, cos, 0.0, 0.0, 0.0, 1.0)
+            * Mat3::from_nonuniform_scale(self.scale.x, self.scale.y)
+    }
+}
+
+impl Default for TextureTransform {
+    fn default() -> Self {
+        Self {
+            offset: vec2(0.0, 0.0),
+            rotation: 0.0,
+            scale: vec2(1.0, 1.0),
+            uv_set: 0,
+        }
+    }
+}
+
 /// See [CpuMaterial]
 #[deprecated = "Renamed to CpuMaterial"]
 pub type CPUMaterial = CpuMaterial;
@@ -66,6 +106,8 @@ pub struct CpuMaterial {
     pub albedo: Color,
     /// Texture with albedo base colors, also called diffuse color. Assumed to be in sRGB with or without an alpha channel.
     pub albedo_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::albedo_texture].
+    pub albedo_texture_transform: TextureTransform,
     /// A value in the range `[0..1]` specifying how metallic the material is.
     pub metallic: f32,
     /// A value in the range `[0..1]` specifying how rough the material surface is.
@@ -78,22 +120,86 @@ pub struct CpuMaterial {
     /// The metallic values are sampled from the blue channel and the roughness from the green channel.
     /// Can be combined with occlusion into one texture, see [Self::occlusion_metallic_roughness_texture].
     pub metallic_roughness_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::metallic_roughness_texture] or the [Self::occlusion_metallic_roughness_texture].
+    pub metallic_roughness_texture_transform: TextureTransform,
     /// A scalar multiplier controlling the amount of occlusion applied from the [Self::occlusion_texture]. A value of 0.0 means no occlusion. A value of 1.0 means full occlusion.
     pub occlusion_strength: f32,
     /// An occlusion map. Higher values indicate areas that should receive full indirect lighting and lower values indicate no indirect lighting.
     /// The occlusion values are sampled from the red channel.
     /// Can be combined with metallic and roughness into one texture, see [Self::occlusion_metallic_roughness_texture].
     pub occlusion_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::occlusion_texture].
+    pub occlusion_texture_transform: TextureTransform,
     /// A scalar multiplier applied to each normal vector of the [Self::normal_texture].
     pub normal_scale: f32,
     /// A tangent space normal map, also known as bump map.
     pub normal_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::normal_texture].
+    pub normal_texture_transform: TextureTransform,
     /// Color of light shining from an object.
     pub emissive: Color,
     /// Texture with color of light shining from an object.
     pub emissive_texture: Option<CpuTexture<u8>>,
-    /// Alpha cutout value for transparency in deferred rendering pipeline.
+    /// The mapping of the [Self::emissive_texture].
+    pub emissive_texture_transform: TextureTransform,
+    /// A scalar multiplier applied to the [Self::emissive] color, which allows for emissive colors brighter than white.
+    pub emissive_strength: f32,
+    /// A value in the range `[0..1]` specifying the strength of a clear coat layer on top of the material, for example a layer of varnish or car paint.
+    /// A value of 0.0 means no clear coat.
+    pub clearcoat: f32,
+    /// Texture containing the clear coat strength which is multiplied with [Self::clearcoat] in the shader.
+    /// The clear coat values are sampled from the red channel.
+    pub clearcoat_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::clearcoat_texture].
+    pub clearcoat_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how rough the clear coat layer is.
+    pub clearcoat_roughness: f32,
+    /// Texture containing the clear coat roughness which is multiplied with [Self::clearcoat_roughness] in the shader.
+    /// The roughness values are sampled from the green channel.
+    pub clearcoat_roughness_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::clearcoat_roughness_texture].
+    pub clearcoat_roughness_texture_transform: TextureTransform,
+    /// A scalar multiplier applied to each normal vector of the [Self::clearcoat_normal_texture].
+    pub clearcoat_normal_scale: f32,
+    /// A tangent space normal map for the clear coat layer.
+    /// If not specified, the clear coat layer uses the normal of the geometry, ie. it is not affected by the [Self::normal_texture].
+    pub clearcoat_normal_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::clearcoat_normal_texture].
+    pub clearcoat_normal_texture_transform: TextureTransform,
+    /// The color of a sheen layer on top of the material, for example the sheen of cloth or velvet. Assumed to be in linear color space.
+    /// Black means no sheen.
+    pub sheen_color: Color,
+    /// Texture with sheen colors which are multiplied with [Self::sheen_color] in the shader. Assumed to be in sRGB.
+    pub sheen_color_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::sheen_color_texture].
+    pub sheen_color_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how rough the sheen layer is.
+    pub sheen_roughness: f32,
+    /// Texture containing the sheen roughness which is multiplied with [Self::sheen_roughness] in the shader.
+    /// The roughness values are sampled from the alpha channel.
+    pub sheen_roughness_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::sheen_roughness_texture].
+    pub sheen_roughness_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how much of the light that is not reflected is transmitted through the surface, for example for glass.
+    /// A value of 0.0 means that the material is not transmissive.
+    pub transmission: f32,
+    /// Texture containing the transmission which is multiplied with [Self::transmission] in the shader.
+    /// The transmission values are sampled from the red channel.
+    pub transmission_texture: Option<CpuTexture<u8>>,
+    /// The mapping of the [Self::transmission_texture].
+    pub transmission_texture_transform: TextureTransform,
+    /// The index of refraction of the material, which determines how much light is reflected by a non-metallic surface.
+    /// The default value of 1.5 corresponds to the reflectance of most non-metallic materials.
+    pub index_of_refraction: f32,
+    /// Alpha cutout value. If specified, fragments with an alpha value below the cutout value are discarded
+    /// and the remaining fragments are rendered as opaque.
     pub alpha_cutout: Option<f32>,
+    /// Whether the material is transparent, ie. whether it should be blended with the objects behind it.
+    /// If `None`, transparency is inferred from the alpha value of the albedo color and the alpha values in the albedo texture.
+    pub is_transparent: Option<bool>,
+    /// Specifies which triangles are culled when rendering this material, for example [Cull::Back] if the back side of the triangles is never visible
+    /// or [Cull::None] if the material is double sided.
+    pub cull: Cull,
     /// The lighting model used when rendering this material
     pub lighting_model: LightingModel,
 }
@@ -104,17 +210,44 @@ impl Default for CpuMaterial {
             name: "default".to_string(),
             albedo: Color::WHITE,
             albedo_texture: None,
+            albedo_texture_transform: TextureTransform::default(),
             occlusion_metallic_roughness_texture: None,
             metallic_roughness_texture: None,
+            metallic_roughness_texture_transform: TextureTransform::default(),
             occlusion_texture: None,
+            occlusion_texture_transform: TextureTransform::default(),
             metallic: 0.0,
             roughness: 1.0,
             occlusion_strength: 1.0,
             normal_texture: None,
+            normal_texture_transform: TextureTransform::default(),
             normal_scale: 1.0,
             emissive: Color::BLACK,
             emissive_texture: None,
+            emissive_texture_transform: TextureTransform::default(),
+            emissive_strength: 1.0,
+            clearcoat: 0.0,
+            clearcoat_texture: None,
+            clearcoat_texture_transform: TextureTransform::default(),
+            clearcoat_roughness: 0.0,
+            clearcoat_roughness_texture: None,
+            clearcoat_roughness_texture_transform: TextureTransform::default(),
+            clearcoat_normal_scale: 1.0,
+            clearcoat_normal_texture: None,
+            clearcoat_normal_texture_transform: TextureTransform::default(),
+            sheen_color: Color::BLACK,
+            sheen_color_texture: None,
+            sheen_color_texture_transform: TextureTransform::default(),
+            sheen_roughness: 0.0,
+            sheen_roughness_texture: None,
+            sheen_roughness_texture_transform: TextureTransform::default(),
+            transmission: 0.0,
+            transmission_texture: None,
+            transmission_texture_transform: TextureTransform::default(),
+            index_of_refraction: 1.5,
             alpha_cutout: None,
+            is_transparent: None,
+            cull: Cull::None,
             lighting_model: LightingModel::Blinn,
         }
     }
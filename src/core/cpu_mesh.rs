@@ -132,6 +132,40 @@ impl std::fmt::Debug for Positions {
     }
 }
 
+///
+/// A morph target (also called a blend shape) of a [CpuMesh], ie. offsets to the vertices of the mesh which are added
+/// to the mesh scaled by the weight of the morph target.
+/// The deltas are specified per vertex, so the length of each of the deltas must be the same as the number of vertices of the mesh.
+///
+#[derive(Clone, Default)]
+pub struct CpuMorphTarget {
+    /// The offsets added to the positions of the vertices.
+    pub position_deltas: Option<Vec<Vec3>>,
+    /// The offsets added to the normals of the vertices.
+    pub normal_deltas: Option<Vec<Vec3>>,
+    /// The offsets added to the xyz part of the tangents of the vertices.
+    pub tangent_deltas: Option<Vec<Vec3>>,
+}
+
+impl std::fmt::Debug for CpuMorphTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("CpuMorphTarget");
+        d.field(
+            "position deltas",
+            &self.position_deltas.as_ref().map(|v| v.len()),
+        );
+        d.field(
+            "normal deltas",
+            &self.normal_deltas.as_ref().map(|v| v.len()),
+        );
+        d.field(
+            "tangent deltas",
+            &self.tangent_deltas.as_ref().map(|v| v.len()),
+        );
+        d.finish()
+    }
+}
+
 /// See [CpuMesh]
 #[deprecated = "Renamed to CpuMesh"]
 pub type CPUMesh = CpuMesh;
@@ -159,9 +193,23 @@ pub struct CpuMesh {
     pub tangents: Option<Vec<Vec4>>,
     /// The uv coordinates of the vertices.
     pub uvs: Option<Vec<Vec2>>,
+    /// A second set of uv coordinates of the vertices, for example used for occlusion maps or light maps.
+    /// Which set of uv coordinates is used for each texture of a material is specified by the [TextureTransform::uv_set] of the texture.
+    pub secondary_uvs: Option<Vec<Vec2>>,
     /// The colors of the vertices.
     /// The colors are assumed to be in linear space.
     pub colors: Option<Vec<Color>>,
+    /// The indices of the (up to) four joints that influence each vertex. The indices are into the joints of the skin, see [Self::skin_name] and [CpuSkin::joints].
+    /// Must be combined with [Self::joint_weights].
+    pub joint_indices: Option<Vec<Vector4<u16>>>,
+    /// The weights of the (up to) four joints that influence each vertex, see [Self::joint_indices]. The weights of a vertex should sum to one.
+    pub joint_weights: Option<Vec<Vec4>>,
+    /// Name of the associated skin, use this to match with [CpuSkin::name].
+    pub skin_name: Option<String>,
+    /// The morph targets. All morph targets must specify the same deltas, for example all have position and normal deltas.
+    pub morph_targets: Vec<CpuMorphTarget>,
+    /// The default weight of each of the [Self::morph_targets]. If empty, all weights are zero.
+    pub morph_weights: Vec<f32>,
 }
 
 impl std::fmt::Debug for CpuMesh {
@@ -174,7 +222,22 @@ impl std::fmt::Debug for CpuMesh {
         d.field("normals", &self.normals.as_ref().map(|v| v.len()));
         d.field("tangents", &self.tangents.as_ref().map(|v| v.len()));
         d.field("uvs", &self.uvs.as_ref().map(|v| v.len()));
+        d.field(
+            "secondary uvs",
+            &self.secondary_uvs.as_ref().map(|v| v.len()),
+        );
         d.field("colors", &self.colors.as_ref().map(|v| v.len()));
+        d.field(
+            "joint indices",
+            &self.joint_indices.as_ref().map(|v| v.len()),
+        );
+        d.field(
+            "joint weights",
+            &self.joint_weights.as_ref().map(|v| v.len()),
+        );
+        d.field("skin name", &self.skin_name);
+        d.field("morph targets", &self.morph_targets);
+        d.field("morph weights", &self.morph_weights);
         d.finish()
     }
 }
@@ -192,8 +255,20 @@ impl CpuMesh {
         })
     }
 
+    ///
+    /// Returns the skin for this mesh in the given list of skins. Returns `None` if no suitable skin can be found.
+    ///
+    pub fn skin<'a>(&self, skins: &'a [CpuSkin]) -> Option<&'a CpuSkin> {
+        self.skin_name
+            .as_ref()
+            .and_then(|name| skins.iter().find(|skin| &skin.name == name))
+    }
+
     ///
     /// Transforms the mesh by the given transformation.
+    /// If the transformation is not invertible, for example if it has a zero scale, the normals and tangents are left unchanged.
+    /// If the transformation mirrors the mesh, ie. it has a negative determinant, the winding order of the triangles is reversed
+    /// and the handedness of the tangents is flipped, so that the front faces still face outwards.
     ///
     pub fn transform(&mut self, transform: &Mat4) {
         match self.positions {
@@ -226,7 +301,10 @@ impl CpuMesh {
                 }
             }
         };
-        let normal_transform = transform.invert().unwrap().transpose();
+        let normal_transform = transform
+            .invert()
+            .map(|t| t.transpose())
+            .unwrap_or_else(Mat4::identity);
 
         if let Some(ref mut normals) = self.normals {
             for n in normals.iter_mut() {
@@ -241,6 +319,88 @@ impl CpuMesh {
                     .extend(t.w);
             }
         }
+
+        for morph_target in self.morph_targets.iter_mut() {
+            if let Some(ref mut deltas) = morph_target.position_deltas {
+                for d in deltas.iter_mut() {
+                    *d = (transform * d.extend(0.0)).truncate();
+                }
+            }
+            if let Some(ref mut deltas) = morph_target.normal_deltas {
+                for d in deltas.iter_mut() {
+                    *d = (normal_transform * d.extend(0.0)).truncate();
+                }
+            }
+            if let Some(ref mut deltas) = morph_target.tangent_deltas {
+                for d in deltas.iter_mut() {
+                    *d = (normal_transform * d.extend(0.0)).truncate();
+                }
+            }
+        }
+
+        if transform.determinant() < 0.0 {
+            self.reverse_winding_order();
+        }
+    }
+
+    ///
+    /// Reverses the winding order of the triangles by swapping the last two vertices of each triangle.
+    /// For a mesh without indices, the vertex attributes are reordered instead.
+    ///
+    fn reverse_winding_order(&mut self) {
+        fn swap_triangle_vertices<T>(values: &mut [T]) {
+            for triangle in values.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+        if let Some(ref mut tangents) = self.tangents {
+            for t in tangents.iter_mut() {
+                t.w = -t.w;
+            }
+        }
+        match self.indices {
+            Some(Indices::U8(ref mut indices)) => swap_triangle_vertices(indices),
+            Some(Indices::U16(ref mut indices)) => swap_triangle_vertices(indices),
+            Some(Indices::U32(ref mut indices)) => swap_triangle_vertices(indices),
+            None => {
+                match self.positions {
+                    Positions::F32(ref mut positions) => swap_triangle_vertices(positions),
+                    Positions::F64(ref mut positions) => swap_triangle_vertices(positions),
+                }
+                if let Some(ref mut normals) = self.normals {
+                    swap_triangle_vertices(normals);
+                }
+                if let Some(ref mut tangents) = self.tangents {
+                    swap_triangle_vertices(tangents);
+                }
+                if let Some(ref mut uvs) = self.uvs {
+                    swap_triangle_vertices(uvs);
+                }
+                if let Some(ref mut uvs) = self.secondary_uvs {
+                    swap_triangle_vertices(uvs);
+                }
+                if let Some(ref mut colors) = self.colors {
+                    swap_triangle_vertices(colors);
+                }
+                if let Some(ref mut joint_indices) = self.joint_indices {
+                    swap_triangle_vertices(joint_indices);
+                }
+                if let Some(ref mut joint_weights) = self.joint_weights {
+                    swap_triangle_vertices(joint_weights);
+                }
+                for morph_target in self.morph_targets.iter_mut() {
+                    if let Some(ref mut deltas) = morph_target.position_deltas {
+                        swap_triangle_vertices(deltas);
+                    }
+                    if let Some(ref mut deltas) = morph_target.normal_deltas {
+                        swap_triangle_vertices(deltas);
+                    }
+                    if let Some(ref mut deltas) = morph_target.tangent_deltas {
+                        swap_triangle_vertices(deltas);
+                    }
+                }
+            }
+        }
     }
 
     ///
@@ -775,6 +935,32 @@ impl CpuMesh {
         buffer_check(self.tangents.as_ref().map(|b| b.len()), "tangent")?;
         buffer_check(self.colors.as_ref().map(|b| b.len()), "color")?;
         buffer_check(self.uvs.as_ref().map(|b| b.len()), "uv coordinate")?;
+        buffer_check(
+            self.secondary_uvs.as_ref().map(|b| b.len()),
+            "secondary uv coordinate",
+        )?;
+        buffer_check(
+            self.joint_indices.as_ref().map(|b| b.len()),
+            "joint indices",
+        )?;
+        buffer_check(
+            self.joint_weights.as_ref().map(|b| b.len()),
+            "joint weights",
+        )?;
+        for morph_target in self.morph_targets.iter() {
+            buffer_check(
+                morph_target.position_deltas.as_ref().map(|b| b.len()),
+                "morph target position deltas",
+            )?;
+            buffer_check(
+                morph_target.normal_deltas.as_ref().map(|b| b.len()),
+                "morph target normal deltas",
+            )?;
+            buffer_check(
+                morph_target.tangent_deltas.as_ref().map(|b| b.len()),
+                "morph target tangent deltas",
+            )?;
+        }
 
         Ok(())
     }
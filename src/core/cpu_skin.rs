@@ -0,0 +1,111 @@
+use crate::core::*;
+
+///
+/// A joint in a [CpuSkin], also called a bone.
+///
+#[derive(Debug, Clone)]
+pub struct CpuJoint {
+    /// Name. Used for matching the joint with an [AnimationTarget::Name].
+    pub name: String,
+    /// The index of the node in the file the joint is loaded from, if any. Used for matching the joint with an [AnimationTarget::Node].
+    pub node_index: Option<usize>,
+    /// The index of the parent joint in [CpuSkin::joints] or `None` if this is a root joint.
+    pub parent: Option<usize>,
+    /// A transformation which is applied before the transformation of the parent joint, or the transformation to world space if this is a root joint.
+    /// This is used for any intermediate transformations between two joints that are not part of the skin.
+    pub parent_offset: Mat4,
+    /// The transformation of the joint relative to its parent joint, ie. the transformation which is usually animated.
+    pub transformation: Mat4,
+    /// The inverse of the world transformation of the joint in the pose the mesh was modelled in (the bind pose).
+    /// Transforms a vertex position from world space into the local space of the joint.
+    pub inverse_bind_matrix: Mat4,
+}
+
+impl Default for CpuJoint {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            node_index: None,
+            parent: None,
+            parent_offset: Mat4::identity(),
+            transformation: Mat4::identity(),
+            inverse_bind_matrix: Mat4::identity(),
+        }
+    }
+}
+
+///
+/// A CPU-side version of a skin, ie. a hierarchy of joints which deforms a [CpuMesh] with [CpuMesh::joint_indices] and [CpuMesh::joint_weights].
+/// Can be constructed manually or loaded via [io](crate::io).
+///
+#[derive(Debug, Clone, Default)]
+pub struct CpuSkin {
+    /// Name. Used for matching a mesh and a skin, see [CpuMesh::skin_name].
+    pub name: String,
+    /// The joints. The joint indices of a skinned mesh are indices into this list.
+    pub joints: Vec<CpuJoint>,
+}
+
+impl CpuSkin {
+    ///
+    /// Returns the world transformation of each joint, computed from the current [CpuJoint::transformation] of the joint and all of its ancestors.
+    ///
+    pub fn joint_transformations(&self) -> ThreeDResult<Vec<Mat4>> {
+        let mut world_transformations: Vec<Option<Mat4>> = vec![None; self.joints.len()];
+        for index in 0..self.joints.len() {
+            self.compute_world_transformation(index, &mut world_transformations, 0)?;
+        }
+        Ok(world_transformations
+            .into_iter()
+            .map(|t| t.unwrap())
+            .collect())
+    }
+
+    ///
+    /// Returns the index of the joint with the given name, if it exists.
+    ///
+    pub fn joint_index(&self, name: &str) -> Option<usize> {
+        self.joints.iter().position(|joint| joint.name == name)
+    }
+
+    ///
+    /// Sets the [CpuJoint::transformation] of each joint animated by the given clip to the value at the given time in seconds.
+    /// The joints are matched with the targets of the clip by name or node index, see [AnimationTarget].
+    /// Afterwards, use [CpuSkin::joint_transformations] to get the new pose of the skin.
+    ///
+    pub fn animate(&mut self, clip: &AnimationClip, time: f32) {
+        for joint in self.joints.iter_mut() {
+            if let Some(transformation) =
+                clip.transformation_of(&joint.name, joint.node_index, time)
+            {
+                joint.transformation = transformation;
+            }
+        }
+    }
+
+    fn compute_world_transformation(
+        &self,
+        index: usize,
+        world_transformations: &mut [Option<Mat4>],
+        depth: usize,
+    ) -> ThreeDResult<Mat4> {
+        if let Some(transformation) = world_transformations[index] {
+            return Ok(transformation);
+        }
+        if depth >= self.joints.len() {
+            Err(CoreError::CyclicJointHierarchy(self.name.clone()))?;
+        }
+        let joint = &self.joints[index];
+        let parent_transformation = if let Some(parent) = joint.parent {
+            if parent >= self.joints.len() {
+                Err(CoreError::IndexOutOfRange(parent, self.joints.len() - 1))?;
+            }
+            self.compute_world_transformation(parent, world_transformations, depth + 1)?
+        } else {
+            Mat4::identity()
+        };
+        let transformation = parent_transformation * joint.parent_offset * joint.transformation;
+        world_transformations[index] = Some(transformation);
+        Ok(transformation)
+    }
+}
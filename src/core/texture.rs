@@ -72,6 +72,8 @@ pub enum Format {
     RG,
     RGB,
     RGBA,
+    /// A block compressed format, see [CompressedFormat]. The texture data must be of type `u8` and contain the compressed blocks.
+    Compressed(CompressedFormat),
 }
 
 impl Format {
@@ -82,8 +84,143 @@ impl Format {
             Format::RG => 2,
             Format::RGB => 3,
             Format::RGBA => 4,
+            Format::Compressed(format) => format.color_channel_count(),
         }
     }
+
+    /// Returns whether the given format is a block compressed format.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self, Format::Compressed(_))
+    }
+}
+
+///
+/// Block compressed formats for pixels in a texture. A compressed texture takes up a fraction of the GPU memory of an uncompressed texture
+/// and is uploaded to the GPU without decompression, but not all formats are supported by all graphics hardware,
+/// use [Context::supports_compressed_format] to check whether a format is supported.
+/// The BC formats are usually supported on desktop and the ETC2 and ASTC formats on mobile devices.
+///
+/// The data of a compressed texture consists of blocks of pixels, each block contains 4x4 pixels except for the ASTC formats where the block size is specified.
+/// Since the shaders convert the colors from sRGB to linear color space where needed,
+/// textures in the sRGB variant of a format are uploaded as if they were in the linear variant.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum CompressedFormat {
+    /// BC1 (also called DXT1) with RGB channels.
+    Bc1Rgb,
+    /// BC1 (also called DXT1) with RGB channels and a 1 bit alpha channel.
+    Bc1Rgba,
+    /// BC2 (also called DXT3) with RGBA channels.
+    Bc2,
+    /// BC3 (also called DXT5) with RGBA channels.
+    Bc3,
+    /// BC4 (also called RGTC1) with a single channel.
+    Bc4,
+    /// BC5 (also called RGTC2) with two channels.
+    Bc5,
+    /// BC6H with RGB channels containing unsigned floating point values, used for high dynamic range images.
+    Bc6h,
+    /// BC7 with RGBA channels.
+    Bc7,
+    /// ETC2 with RGB channels.
+    Etc2Rgb,
+    /// ETC2 with RGB channels and a 1 bit alpha channel.
+    Etc2Rgba1,
+    /// ETC2 with RGBA channels.
+    Etc2Rgba,
+    /// EAC with a single channel.
+    EacR,
+    /// EAC with two channels.
+    EacRg,
+    /// ASTC with RGBA channels and the given block size, which must be one of the sizes specified by ASTC (4x4, 5x4, 5x5, 6x5, 6x6, 8x5, 8x6, 8x8, 10x5, 10x6, 10x8, 10x10, 12x10 or 12x12).
+    Astc {
+        /// The width of a block in pixels.
+        block_width: u8,
+        /// The height of a block in pixels.
+        block_height: u8,
+    },
+}
+
+impl CompressedFormat {
+    /// Returns the number of channels for the given format.
+    pub fn color_channel_count(&self) -> u32 {
+        match self {
+            CompressedFormat::Bc4 | CompressedFormat::EacR => 1,
+            CompressedFormat::Bc5 | CompressedFormat::EacRg => 2,
+            CompressedFormat::Bc1Rgb | CompressedFormat::Bc6h | CompressedFormat::Etc2Rgb => 3,
+            _ => 4,
+        }
+    }
+
+    /// Returns the width and height in pixels of a block.
+    pub fn block_size(&self) -> (u32, u32) {
+        match self {
+            CompressedFormat::Astc {
+                block_width,
+                block_height,
+            } => (*block_width as u32, *block_height as u32),
+            _ => (4, 4),
+        }
+    }
+
+    /// Returns the number of bytes used to store a block.
+    pub fn bytes_per_block(&self) -> usize {
+        match self {
+            CompressedFormat::Bc1Rgb
+            | CompressedFormat::Bc1Rgba
+            | CompressedFormat::Bc4
+            | CompressedFormat::Etc2Rgb
+            | CompressedFormat::Etc2Rgba1
+            | CompressedFormat::EacR => 8,
+            _ => 16,
+        }
+    }
+
+    /// Returns the number of bytes used to store an image with the given width and height in this format.
+    pub fn data_size(&self, width: u32, height: u32) -> usize {
+        let (block_width, block_height) = self.block_size();
+        let blocks_x = width.div_ceil(block_width);
+        let blocks_y = height.div_ceil(block_height);
+        blocks_x as usize * blocks_y as usize * self.bytes_per_block()
+    }
+
+    pub(in crate::core) fn internal_format(&self) -> ThreeDResult<u32> {
+        Ok(match self {
+            CompressedFormat::Bc1Rgb => 0x83F0,
+            CompressedFormat::Bc1Rgba => 0x83F1,
+            CompressedFormat::Bc2 => 0x83F2,
+            CompressedFormat::Bc3 => 0x83F3,
+            CompressedFormat::Bc4 => 0x8DBB,
+            CompressedFormat::Bc5 => 0x8DBD,
+            CompressedFormat::Bc6h => 0x8E8F,
+            CompressedFormat::Bc7 => 0x8E8C,
+            CompressedFormat::Etc2Rgb => 0x9274,
+            CompressedFormat::Etc2Rgba1 => 0x9276,
+            CompressedFormat::Etc2Rgba => 0x9278,
+            CompressedFormat::EacR => 0x9270,
+            CompressedFormat::EacRg => 0x9272,
+            CompressedFormat::Astc {
+                block_width,
+                block_height,
+            } => match (block_width, block_height) {
+                (4, 4) => 0x93B0,
+                (5, 4) => 0x93B1,
+                (5, 5) => 0x93B2,
+                (6, 5) => 0x93B3,
+                (6, 6) => 0x93B4,
+                (8, 5) => 0x93B5,
+                (8, 6) => 0x93B6,
+                (8, 8) => 0x93B7,
+                (10, 5) => 0x93B8,
+                (10, 6) => 0x93B9,
+                (10, 8) => 0x93BA,
+                (10, 10) => 0x93BB,
+                (12, 10) => 0x93BC,
+                (12, 12) => 0x93BD,
+                _ => Err(CoreError::UnsupportedCompressedFormat(*self))?,
+            },
+        })
+    }
 }
 
 /// See [CpuTexture]
@@ -104,6 +241,10 @@ pub struct CpuTexture<T: TextureDataType> {
     pub height: u32,
     /// The format of the image
     pub format: Format,
+    /// The pixel data for the mipmaps, starting with the mipmap which is half the width and height of the image.
+    /// If empty, the mipmaps are generated on the GPU when needed, otherwise the given mipmaps are used for the texture.
+    /// Mipmaps cannot be generated for compressed textures, so they have to be given here.
+    pub mip_maps: Vec<Vec<T>>,
     /// The way the pixel data is interpolated when the texture is far away
     pub min_filter: Interpolation,
     /// The way the pixel data is interpolated when the texture is close
@@ -121,8 +262,14 @@ impl<T: TextureDataType> CpuTexture<T> {
     ///
     /// Adds a padding of default values to the texture.
     /// 'left' number of pixels are added to the left of the original texture, 'right' number of pixels to the right and so on.
+    /// Any mipmaps are removed since they no longer match the texture.
+    ///
+    /// **Note:** Does nothing for textures with a compressed format.
     ///
     pub fn add_padding(&mut self, left: u32, right: u32, top: u32, bottom: u32) {
+        if self.format.is_compressed() {
+            return;
+        }
         let channels = self.format.color_channel_count();
         let width = left + self.width + right;
         let height = top + self.height + bottom;
@@ -142,10 +289,22 @@ impl<T: TextureDataType> CpuTexture<T> {
         self.data = new_data;
         self.width = width;
         self.height = height;
+        self.mip_maps.clear();
     }
 
+    ///
     /// Returns whether or not this texture is transparent by looking at its alpha values.
+    /// The alpha values of a texture with a compressed format are not available, so it is considered opaque
+    /// unless the file explicitly chose a format with a 1-bit alpha channel over the same format without, that is [CompressedFormat::Bc1Rgba] or [CompressedFormat::Etc2Rgba1].
+    /// Use [CpuMaterial::is_transparent] or for example `PhysicalMaterial::new_transparent` to render a material with another compressed texture as transparent.
+    ///
     pub fn is_transparent(&self) -> bool {
+        if let Format::Compressed(format) = self.format {
+            return matches!(
+                format,
+                CompressedFormat::Bc1Rgba | CompressedFormat::Etc2Rgba1
+            );
+        }
         if self.format == Format::RGBA {
             for i in 0..self.width as usize * self.height as usize {
                 if !T::is_max(self.data[i * 4 + 3]) {
@@ -164,6 +323,7 @@ impl<T: TextureDataType> Default for CpuTexture<T> {
             width: 1,
             height: 1,
             format: Format::RGBA,
+            mip_maps: Vec::new(),
             min_filter: Interpolation::Linear,
             mag_filter: Interpolation::Linear,
             mip_map_filter: Some(Interpolation::Linear),
@@ -180,6 +340,7 @@ impl<T: TextureDataType> std::fmt::Debug for CpuTexture<T> {
             .field("data length", &self.data.len())
             .field("width", &self.width)
             .field("height", &self.height)
+            .field("mip maps", &self.mip_maps.len())
             .field("min_filter", &self.min_filter)
             .field("mag_filter", &self.mag_filter)
             .field("mip_map_filter", &self.mip_map_filter)
@@ -217,6 +378,9 @@ pub struct CpuTexture3D<T: TextureDataType> {
     pub wrap_t: Wrapping,
     /// Determines how the texture is sampled outside the [0..1] r coordinate range (the third value of the uvw coordinates).
     pub wrap_r: Wrapping,
+    /// The distance between the centers of two neighbouring voxels along the width, height and depth, for example in millimeters.
+    /// This is not used by [Texture3D], but can be used to scale the volume to its physical size when rendering.
+    pub spacing: Vec3,
 }
 
 impl<T: TextureDataType> Default for CpuTexture3D<T> {
@@ -233,6 +397,7 @@ impl<T: TextureDataType> Default for CpuTexture3D<T> {
             wrap_s: Wrapping::Repeat,
             wrap_t: Wrapping::Repeat,
             wrap_r: Wrapping::Repeat,
+            spacing: vec3(1.0, 1.0, 1.0),
         }
     }
 }
@@ -251,6 +416,7 @@ impl<T: TextureDataType> std::fmt::Debug for CpuTexture3D<T> {
             .field("wrap_s", &self.wrap_s)
             .field("wrap_t", &self.wrap_t)
             .field("wrap_r", &self.wrap_r)
+            .field("spacing", &self.spacing)
             .finish()
     }
 }
@@ -282,6 +448,11 @@ pub struct CpuTextureCube<T: TextureDataType> {
     pub height: u32,
     /// The format of the image
     pub format: Format,
+    /// The pixel data for the mipmaps of the 6 images in the order right, left, top, bottom, front and back,
+    /// starting with the mipmaps which are half the width and height of the images.
+    /// If empty, the mipmaps are generated on the GPU when needed, otherwise the given mipmaps are used for the texture.
+    /// Mipmaps cannot be generated for compressed textures, so they have to be given here.
+    pub mip_maps: Vec<[Vec<T>; 6]>,
     /// The way the pixel data is interpolated when the texture is far away
     pub min_filter: Interpolation,
     /// The way the pixel data is interpolated when the texture is close
@@ -297,6 +468,150 @@ pub struct CpuTextureCube<T: TextureDataType> {
     pub wrap_r: Wrapping,
 }
 
+impl<T: TextureDataType> CpuTextureCube<T> {
+    ///
+    /// Creates a cube texture from a single image which contains the 6 sides of the cube in a cross layout.
+    /// In the horizontal cross layout, the image is 4 sides wide and 3 sides high, the middle row contains the left, front, right and back sides
+    /// and the top and bottom sides are placed above and below the front side.
+    /// The vertical cross layout, where the image is 3 sides wide and 4 sides high, is the same except that the back side is placed below the bottom side and is upside down.
+    ///
+    /// # Errors
+    /// Returns an error if the aspect ratio of the image is neither 4:3 nor 3:4 or if the image has a compressed format.
+    ///
+    pub fn from_cross(texture: &CpuTexture<T>) -> ThreeDResult<Self> {
+        if let Format::Compressed(format) = texture.format {
+            Err(CoreError::UnsupportedCompressedFormat(format))?;
+        }
+        let (size, vertical) = if texture.width * 3 == texture.height * 4 {
+            (texture.width / 4, false)
+        } else if texture.width * 4 == texture.height * 3 {
+            (texture.width / 3, true)
+        } else {
+            Err(CoreError::InvalidCubeMapCrossLayout(
+                texture.width,
+                texture.height,
+            ))?
+        };
+        let channels = texture.format.color_channel_count() as usize;
+        let side = |column: u32, row: u32, upside_down: bool| {
+            let mut data = Vec::with_capacity(size as usize * size as usize * channels);
+            for y in 0..size {
+                for x in 0..size {
+                    let (x, y) = if upside_down {
+                        (size - 1 - x, size - 1 - y)
+                    } else {
+                        (x, y)
+                    };
+                    let index =
+                        ((row * size + y) * texture.width + column * size + x) as usize * channels;
+                    data.extend_from_slice(&texture.data[index..index + channels]);
+                }
+            }
+            data
+        };
+        Ok(Self {
+            right_data: side(2, 1, false),
+            left_data: side(0, 1, false),
+            top_data: side(1, 0, false),
+            bottom_data: side(1, 2, false),
+            front_data: side(1, 1, false),
+            back_data: if vertical {
+                side(1, 3, true)
+            } else {
+                side(3, 1, false)
+            },
+            width: size,
+            height: size,
+            format: texture.format,
+            mip_maps: Vec::new(),
+            min_filter: texture.min_filter,
+            mag_filter: texture.mag_filter,
+            mip_map_filter: texture.mip_map_filter,
+            wrap_s: Wrapping::ClampToEdge,
+            wrap_t: Wrapping::ClampToEdge,
+            wrap_r: Wrapping::ClampToEdge,
+        })
+    }
+
+    ///
+    /// Creates a cube texture where each side has the given size from an equirectangular image,
+    /// ie. a panorama where the horizontal axis is the angle around the vertical axis and the vertical axis is the angle from the top.
+    /// The result is the same as using [TextureCubeMap::new_from_equirectangular], except that the conversion is done on the CPU
+    /// using bilinear interpolation, so it also works for textures which are not RGBA and the result can be stored.
+    ///
+    /// # Errors
+    /// Returns an error if the image has a compressed format.
+    ///
+    pub fn from_equirectangular(texture: &CpuTexture<T>, size: u32) -> ThreeDResult<Self> {
+        if let Format::Compressed(format) = texture.format {
+            Err(CoreError::UnsupportedCompressedFormat(format))?;
+        }
+        let channels = texture.format.color_channel_count() as usize;
+        let mut sides = CubeMapSide::iter().map(|side| {
+            let mut data = Vec::with_capacity(size as usize * size as usize * channels);
+            for y in 0..size {
+                for x in 0..size {
+                    let direction = side.direction(
+                        2.0 * (x as f32 + 0.5) / size as f32 - 1.0,
+                        2.0 * (y as f32 + 0.5) / size as f32 - 1.0,
+                    );
+                    sample_equirectangular(texture, direction, &mut data);
+                }
+            }
+            data
+        });
+        Ok(Self {
+            right_data: sides.next().unwrap(),
+            left_data: sides.next().unwrap(),
+            top_data: sides.next().unwrap(),
+            bottom_data: sides.next().unwrap(),
+            front_data: sides.next().unwrap(),
+            back_data: sides.next().unwrap(),
+            width: size,
+            height: size,
+            format: texture.format,
+            mip_maps: Vec::new(),
+            min_filter: texture.min_filter,
+            mag_filter: texture.mag_filter,
+            mip_map_filter: texture.mip_map_filter,
+            wrap_s: Wrapping::ClampToEdge,
+            wrap_t: Wrapping::ClampToEdge,
+            wrap_r: Wrapping::ClampToEdge,
+        })
+    }
+}
+
+///
+/// Samples the equirectangular texture in the given direction using bilinear interpolation and appends the result to the output.
+///
+fn sample_equirectangular<T: TextureDataType>(
+    texture: &CpuTexture<T>,
+    direction: Vec3,
+    output: &mut Vec<T>,
+) {
+    let direction = direction.normalize();
+    let u = direction.z.atan2(direction.x) / (2.0 * std::f32::consts::PI) + 0.5;
+    let v = 0.5 - direction.y.asin() / std::f32::consts::PI;
+    let x = u * texture.width as f32 - 0.5;
+    let y = (v * texture.height as f32 - 0.5).clamp(0.0, (texture.height - 1) as f32);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let column = |x: f32| (x as i64).rem_euclid(texture.width as i64) as usize;
+    let row = |y: f32| (y as usize).min(texture.height as usize - 1);
+    let channels = texture.format.color_channel_count() as usize;
+    let index = |column: usize, row: usize| (row * texture.width as usize + column) * channels;
+    let i00 = index(column(x0), row(y0));
+    let i10 = index(column(x0 + 1.0), row(y0));
+    let i01 = index(column(x0), row(y0 + 1.0));
+    let i11 = index(column(x0 + 1.0), row(y0 + 1.0));
+    for c in 0..channels {
+        let value = |i: usize| T::to_f32(texture.data[i + c]);
+        let top = value(i00) * (1.0 - fx) + value(i10) * fx;
+        let bottom = value(i01) * (1.0 - fx) + value(i11) * fx;
+        output.push(T::from_f32(top * (1.0 - fy) + bottom * fy));
+    }
+}
+
 impl<T: TextureDataType> Default for CpuTextureCube<T> {
     fn default() -> Self {
         Self {
@@ -309,6 +624,7 @@ impl<T: TextureDataType> Default for CpuTextureCube<T> {
             width: 1,
             height: 1,
             format: Format::RGBA,
+            mip_maps: Vec::new(),
             min_filter: Interpolation::Linear,
             mag_filter: Interpolation::Linear,
             mip_map_filter: Some(Interpolation::Linear),
@@ -325,6 +641,7 @@ impl<T: TextureDataType> std::fmt::Debug for CpuTextureCube<T> {
             .field("format", &self.format)
             .field("width", &self.width)
             .field("height", &self.height)
+            .field("mip maps", &self.mip_maps.len())
             .field("min_filter", &self.min_filter)
             .field("mag_filter", &self.mag_filter)
             .field("mip_map_filter", &self.mip_map_filter)
@@ -344,6 +661,7 @@ mod internal {
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
@@ -353,6 +671,10 @@ mod internal {
         fn read(context: &Context, viewport: Viewport, format: Format, pixels: &mut [Self]);
         fn is_max(value: Self) -> bool;
         fn bits_per_channel() -> u8;
+        fn to_f32(value: Self) -> f32;
+        fn to_u32(value: Self) -> u32;
+        fn from_f32(value: f32) -> Self;
+        fn data_type() -> DataType;
     }
 
     impl TextureDataTypeExtension for u8 {
@@ -362,23 +684,36 @@ mod internal {
                 Format::RG => crate::context::consts::RG8,
                 Format::RGB => crate::context::consts::RGB8,
                 Format::RGBA => crate::context::consts::RGBA8,
+                Format::Compressed(format) => format.internal_format()?,
             })
         }
 
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
             format: Format,
             data: &[Self],
         ) {
-            if let Some(depth) = depth {
-                context.tex_sub_image_3d_with_u8_data(
+            if let Format::Compressed(compressed) = format {
+                context.compressed_tex_sub_image_2d(
                     target,
+                    level,
                     0,
                     0,
+                    width,
+                    height,
+                    compressed.internal_format().unwrap(),
+                    data,
+                );
+            } else if let Some(depth) = depth {
+                context.tex_sub_image_3d_with_u8_data(
+                    target,
+                    level,
+                    0,
                     0,
                     0,
                     width,
@@ -391,7 +726,7 @@ mod internal {
             } else {
                 context.tex_sub_image_2d_with_u8_data(
                     target,
-                    0,
+                    level,
                     0,
                     0,
                     width,
@@ -422,6 +757,22 @@ mod internal {
         fn bits_per_channel() -> u8 {
             8
         }
+
+        fn to_f32(value: Self) -> f32 {
+            value as f32
+        }
+
+        fn to_u32(value: Self) -> u32 {
+            value as u32
+        }
+
+        fn from_f32(value: f32) -> Self {
+            value.round() as u8
+        }
+
+        fn data_type() -> DataType {
+            DataType::UnsignedByte
+        }
     }
 
     impl TextureDataTypeExtension for u16 {
@@ -431,12 +782,14 @@ mod internal {
                 Format::RG => crate::context::consts::RG16UI,
                 Format::RGB => crate::context::consts::RGB16UI,
                 Format::RGBA => crate::context::consts::RGBA16UI,
+                Format::Compressed(format) => Err(CoreError::CompressedTextureDataType(format))?,
             })
         }
 
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
@@ -448,7 +801,7 @@ mod internal {
             } else {
                 context.tex_sub_image_2d_with_u16_data(
                     target,
-                    0,
+                    level,
                     0,
                     0,
                     width,
@@ -479,6 +832,22 @@ mod internal {
         fn bits_per_channel() -> u8 {
             16
         }
+
+        fn to_f32(value: Self) -> f32 {
+            value as f32
+        }
+
+        fn to_u32(value: Self) -> u32 {
+            value as u32
+        }
+
+        fn from_f32(value: f32) -> Self {
+            value.round() as u16
+        }
+
+        fn data_type() -> DataType {
+            DataType::UnsignedShort
+        }
     }
 
     impl TextureDataTypeExtension for f16 {
@@ -488,12 +857,14 @@ mod internal {
                 Format::RG => crate::context::consts::RG16F,
                 Format::RGB => crate::context::consts::RGB16F,
                 Format::RGBA => crate::context::consts::RGBA16F,
+                Format::Compressed(format) => Err(CoreError::CompressedTextureDataType(format))?,
             })
         }
 
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
@@ -505,7 +876,7 @@ mod internal {
             } else {
                 context.tex_sub_image_2d_with_u16_data(
                     target,
-                    0,
+                    level,
                     0,
                     0,
                     width,
@@ -540,6 +911,22 @@ mod internal {
         fn bits_per_channel() -> u8 {
             16
         }
+
+        fn to_f32(value: Self) -> f32 {
+            value.to_f32()
+        }
+
+        fn to_u32(value: Self) -> u32 {
+            value.to_f32() as u32
+        }
+
+        fn from_f32(value: f32) -> Self {
+            f16::from_f32(value)
+        }
+
+        fn data_type() -> DataType {
+            DataType::HalfFloat
+        }
     }
 
     impl TextureDataTypeExtension for f32 {
@@ -549,12 +936,14 @@ mod internal {
                 Format::RG => crate::context::consts::RG32F,
                 Format::RGB => crate::context::consts::RGB32F,
                 Format::RGBA => crate::context::consts::RGBA32F,
+                Format::Compressed(format) => Err(CoreError::CompressedTextureDataType(format))?,
             })
         }
 
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
@@ -566,7 +955,7 @@ mod internal {
             } else {
                 context.tex_sub_image_2d_with_f32_data(
                     target,
-                    0,
+                    level,
                     0,
                     0,
                     width,
@@ -597,6 +986,22 @@ mod internal {
         fn bits_per_channel() -> u8 {
             32
         }
+
+        fn to_f32(value: Self) -> f32 {
+            value
+        }
+
+        fn to_u32(value: Self) -> u32 {
+            value as u32
+        }
+
+        fn from_f32(value: f32) -> Self {
+            value
+        }
+
+        fn data_type() -> DataType {
+            DataType::Float
+        }
     }
 
     impl TextureDataTypeExtension for u32 {
@@ -606,12 +1011,14 @@ mod internal {
                 Format::RG => crate::context::consts::RG32UI,
                 Format::RGB => crate::context::consts::RGB32UI,
                 Format::RGBA => crate::context::consts::RGBA32UI,
+                Format::Compressed(format) => Err(CoreError::CompressedTextureDataType(format))?,
             })
         }
 
         fn fill(
             context: &Context,
             target: u32,
+            level: u32,
             width: u32,
             height: u32,
             depth: Option<u32>,
@@ -623,7 +1030,7 @@ mod internal {
             } else {
                 context.tex_sub_image_2d_with_u32_data(
                     target,
-                    0,
+                    level,
                     0,
                     0,
                     width,
@@ -653,6 +1060,22 @@ mod internal {
         fn bits_per_channel() -> u8 {
             32
         }
+
+        fn to_f32(value: Self) -> f32 {
+            value as f32
+        }
+
+        fn to_u32(value: Self) -> u32 {
+            value
+        }
+
+        fn from_f32(value: f32) -> Self {
+            value.round() as u32
+        }
+
+        fn data_type() -> DataType {
+            DataType::UnsignedInt
+        }
     }
 
     fn format_from(format: Format) -> u32 {
@@ -661,6 +1084,7 @@ mod internal {
             Format::RG => consts::RG,
             Format::RGB => consts::RGB,
             Format::RGBA => consts::RGBA,
+            Format::Compressed(_) => unreachable!(),
         }
     }
 }
@@ -796,6 +1220,48 @@ fn calculate_number_of_mip_maps(
     }
 }
 
+///
+/// Returns the number of mip maps of a texture, which is the given mip maps and the original image if any mip maps are given.
+/// Otherwise mip maps are generated, which is not possible for compressed formats.
+///
+fn number_of_mip_maps(
+    format: Format,
+    mip_map_filter: Option<Interpolation>,
+    width: u32,
+    height: u32,
+    mip_map_count: usize,
+) -> u32 {
+    if mip_map_filter.is_some() && mip_map_count > 0 {
+        let max_count = (width.max(height) as f64).log2() as usize;
+        1 + mip_map_count.min(max_count) as u32
+    } else if format.is_compressed() {
+        1
+    } else {
+        calculate_number_of_mip_maps(mip_map_filter, width, height, None)
+    }
+}
+
+///
+/// Returns the given mip maps together with the level they should be uploaded to, skipping mip maps that do not fit in the texture.
+/// Returns nothing if no mip maps are given, in which case the mip maps are generated instead.
+///
+fn given_mip_maps<M>(mip_maps: &[M], number_of_mip_maps: u32) -> impl Iterator<Item = (u32, &M)> {
+    mip_maps
+        .iter()
+        .take(number_of_mip_maps.saturating_sub(1) as usize)
+        .enumerate()
+        .map(|(i, data)| (i as u32 + 1, data))
+}
+
+fn check_format_support(context: &Context, format: Format) -> ThreeDResult<()> {
+    if let Format::Compressed(format) = format {
+        if !context.supports_compressed_format(format) {
+            Err(CoreError::UnsupportedCompressedFormat(format))?;
+        }
+    }
+    Ok(())
+}
+
 fn internal_format_from_depth(format: DepthFormat) -> u32 {
     match format {
         DepthFormat::Depth16 => consts::DEPTH_COMPONENT16,
@@ -826,6 +1292,16 @@ fn check_data_length(
     format: Format,
     length: usize,
 ) -> ThreeDResult<()> {
+    if let Format::Compressed(format) = format {
+        let expected_bytes = format.data_size(width, height) * depth as usize;
+        if expected_bytes != length {
+            Err(CoreError::InvalidCompressedTextureLength(
+                length,
+                expected_bytes,
+            ))?;
+        }
+        return Ok(());
+    }
     let expected_pixels = width as usize * height as usize * depth as usize;
     let actual_pixels = length / format.color_channel_count() as usize;
 
@@ -837,3 +1313,38 @@ fn check_data_length(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_mip_maps() {
+        let filter = Some(Interpolation::Linear);
+        let count = number_of_mip_maps(Format::RGBA, filter, 512, 512, 0);
+        assert_eq!(count, 10);
+        assert_eq!(given_mip_maps::<Vec<u8>>(&[], count).count(), 0);
+        assert_eq!(number_of_mip_maps(Format::RGBA, None, 512, 512, 0), 1);
+        assert_eq!(number_of_mip_maps(Format::RGBA, filter, 512, 256, 0), 1);
+    }
+
+    #[test]
+    fn given_mip_maps_levels() {
+        let filter = Some(Interpolation::Linear);
+        let mip_maps = vec![vec![0u8; 4]; 3];
+        let count = number_of_mip_maps(Format::RGBA, filter, 8, 4, mip_maps.len());
+        assert_eq!(count, 4);
+        let levels = given_mip_maps(&mip_maps, count)
+            .map(|(level, _)| level)
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![1, 2, 3]);
+
+        // Mip maps beyond the smallest level are ignored
+        let mip_maps = vec![vec![0u8; 4]; 5];
+        let count = number_of_mip_maps(Format::RGBA, filter, 4, 4, mip_maps.len());
+        assert_eq!(count, 3);
+        assert_eq!(given_mip_maps(&mip_maps, count).count(), 2);
+        assert_eq!(number_of_mip_maps(Format::RGBA, None, 4, 4, 5), 1);
+        assert_eq!(given_mip_maps(&mip_maps, 1).count(), 0);
+    }
+}
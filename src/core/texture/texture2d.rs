@@ -16,9 +16,13 @@ pub struct Texture2D<T: TextureDataType> {
 impl<T: TextureDataType> Texture2D<T> {
     ///
     /// Construcs a new texture with the given data.
+    /// If the cpu texture contains mip maps and a mip map filter is specified, the given mip maps are used instead of generating them.
+    ///
+    /// # Errors
+    /// Returns an error if the texture has a compressed format which is not supported by the graphics hardware, see [Context::supports_compressed_format].
     ///
     pub fn new(context: &Context, cpu_texture: &CpuTexture<T>) -> ThreeDResult<Texture2D<T>> {
-        let mut texture = Self::new_empty(
+        let mut texture = Self::new_with_mip_maps(
             context,
             cpu_texture.width,
             cpu_texture.height,
@@ -28,8 +32,16 @@ impl<T: TextureDataType> Texture2D<T> {
             cpu_texture.wrap_s,
             cpu_texture.wrap_t,
             cpu_texture.format,
+            cpu_texture.mip_maps.len(),
         )?;
-        texture.fill(&cpu_texture.data)?;
+        if cpu_texture.mip_maps.is_empty() {
+            texture.fill(&cpu_texture.data)?;
+        } else {
+            texture.fill_level(0, &cpu_texture.data)?;
+            for (level, data) in given_mip_maps(&cpu_texture.mip_maps, texture.number_of_mip_maps) {
+                texture.fill_level(level, data)?;
+            }
+        }
         Ok(texture)
     }
 
@@ -47,8 +59,36 @@ impl<T: TextureDataType> Texture2D<T> {
         wrap_t: Wrapping,
         format: Format,
     ) -> ThreeDResult<Self> {
+        Self::new_with_mip_maps(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            format,
+            0,
+        )
+    }
+
+    fn new_with_mip_maps(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+        format: Format,
+        mip_map_count: usize,
+    ) -> ThreeDResult<Self> {
+        check_format_support(context, format)?;
         let id = generate(context)?;
-        let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
+        let number_of_mip_maps =
+            number_of_mip_maps(format, mip_map_filter, width, height, mip_map_count);
         set_parameters(
             context,
             &id,
@@ -91,18 +131,26 @@ impl<T: TextureDataType> Texture2D<T> {
     /// Return an error if the length of the data array is smaller or bigger than the necessary number of bytes to fill the entire texture.
     ///
     pub fn fill(&mut self, data: &[T]) -> ThreeDResult<()> {
-        check_data_length(self.width, self.height, 1, self.format, data.len())?;
+        self.fill_level(0, data)?;
+        self.generate_mip_maps();
+        Ok(())
+    }
+
+    fn fill_level(&mut self, level: u32, data: &[T]) -> ThreeDResult<()> {
+        let width = (self.width >> level).max(1);
+        let height = (self.height >> level).max(1);
+        check_data_length(width, height, 1, self.format, data.len())?;
         self.context.bind_texture(consts::TEXTURE_2D, &self.id);
         T::fill(
             &self.context,
             consts::TEXTURE_2D,
-            self.width,
-            self.height,
+            level,
+            width,
+            height,
             None,
             self.format,
             data,
         );
-        self.generate_mip_maps();
         Ok(())
     }
 
@@ -174,7 +222,7 @@ impl<T: TextureDataType> Texture2D<T> {
     }
 
     pub(crate) fn generate_mip_maps(&self) {
-        if self.number_of_mip_maps > 1 {
+        if self.number_of_mip_maps > 1 && !self.format.is_compressed() {
             self.context.bind_texture(consts::TEXTURE_2D, &self.id);
             self.context.generate_mipmap(consts::TEXTURE_2D);
         }
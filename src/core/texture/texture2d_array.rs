@@ -43,6 +43,9 @@ impl<T: TextureDataType> Texture2DArray<T> {
         wrap_t: Wrapping,
         format: Format,
     ) -> ThreeDResult<Self> {
+        if let Format::Compressed(format) = format {
+            Err(CoreError::UnsupportedCompressedFormat(format))?;
+        }
         let id = generate(context)?;
         let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
         set_parameters(
@@ -53,6 +53,9 @@ impl<T: TextureDataType> Texture3D<T> {
         wrap_r: Wrapping,
         format: Format,
     ) -> ThreeDResult<Self> {
+        if let Format::Compressed(format) = format {
+            Err(CoreError::UnsupportedCompressedFormat(format))?;
+        }
         let id = generate(context)?;
         let number_of_mip_maps =
             calculate_number_of_mip_maps(mip_map_filter, width, height, Some(depth));
@@ -104,6 +107,7 @@ impl<T: TextureDataType> Texture3D<T> {
         T::fill(
             &self.context,
             consts::TEXTURE_3D,
+            0,
             self.width,
             self.height,
             Some(self.depth),
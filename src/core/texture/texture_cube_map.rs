@@ -71,6 +71,21 @@ impl CubeMapSide {
         }
     }
 
+    ///
+    /// Returns the direction from the center of the cube to the point on this side with the given texture coordinates in the range [-1..1],
+    /// where the first coordinate is the horizontal and the second coordinate the vertical position in the image data of the side.
+    ///
+    pub(in crate::core) fn direction(&self, s: f32, t: f32) -> Vec3 {
+        match self {
+            CubeMapSide::Right => vec3(1.0, -t, -s),
+            CubeMapSide::Left => vec3(-1.0, -t, s),
+            CubeMapSide::Top => vec3(s, 1.0, t),
+            CubeMapSide::Bottom => vec3(s, -1.0, -t),
+            CubeMapSide::Front => vec3(s, -t, 1.0),
+            CubeMapSide::Back => vec3(-s, -t, -1.0),
+        }
+    }
+
     pub(in crate::core) fn view(&self) -> Mat4 {
         match self {
             CubeMapSide::Right => Mat4::look_at_rh(
@@ -125,12 +140,16 @@ impl<T: TextureDataType> TextureCubeMap<T> {
     /// Creates a new texture cube map from the given cpu texture.
     /// The cpu texture must contain 6 images all with the width and height specified in the cpu texture.
     /// The images are used in the following order; right, left, top, bottom, front, back.
+    /// If the cpu texture contains mip maps and a mip map filter is specified, the given mip maps are used instead of generating them.
+    ///
+    /// # Errors
+    /// Returns an error if the texture has a compressed format which is not supported by the graphics hardware, see [Context::supports_compressed_format].
     ///
     pub fn new(
         context: &Context,
         cpu_texture: &CpuTextureCube<T>,
     ) -> ThreeDResult<TextureCubeMap<T>> {
-        let mut texture = Self::new_empty(
+        let mut texture = Self::new_with_mip_maps(
             context,
             cpu_texture.width,
             cpu_texture.height,
@@ -141,15 +160,27 @@ impl<T: TextureDataType> TextureCubeMap<T> {
             cpu_texture.wrap_t,
             cpu_texture.wrap_r,
             cpu_texture.format,
+            cpu_texture.mip_maps.len(),
         )?;
-        texture.fill(
-            &cpu_texture.right_data,
+        let data = [
+            &cpu_texture.right_data[..],
             &cpu_texture.left_data,
             &cpu_texture.top_data,
             &cpu_texture.bottom_data,
             &cpu_texture.front_data,
             &cpu_texture.back_data,
-        )?;
+        ];
+        if cpu_texture.mip_maps.is_empty() {
+            texture.fill(data[0], data[1], data[2], data[3], data[4], data[5])?;
+        } else {
+            texture.fill_level(0, data)?;
+            for (level, data) in given_mip_maps(&cpu_texture.mip_maps, texture.number_of_mip_maps) {
+                texture.fill_level(
+                    level,
+                    [&data[0], &data[1], &data[2], &data[3], &data[4], &data[5]],
+                )?;
+            }
+        }
         Ok(texture)
     }
 
@@ -168,8 +199,38 @@ impl<T: TextureDataType> TextureCubeMap<T> {
         wrap_r: Wrapping,
         format: Format,
     ) -> ThreeDResult<Self> {
+        Self::new_with_mip_maps(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            wrap_r,
+            format,
+            0,
+        )
+    }
+
+    fn new_with_mip_maps(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+        wrap_r: Wrapping,
+        format: Format,
+        mip_map_count: usize,
+    ) -> ThreeDResult<Self> {
+        check_format_support(context, format)?;
         let id = generate(context)?;
-        let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
+        let number_of_mip_maps =
+            number_of_mip_maps(format, mip_map_filter, width, height, mip_map_count);
         set_parameters(
             context,
             &id,
@@ -221,35 +282,41 @@ impl<T: TextureDataType> TextureCubeMap<T> {
         front_data: &[T],
         back_data: &[T],
     ) -> ThreeDResult<()> {
-        check_data_length(self.width, self.height, 1, self.format, right_data.len())?;
-        check_data_length(self.width, self.height, 1, self.format, left_data.len())?;
-        check_data_length(self.width, self.height, 1, self.format, top_data.len())?;
-        check_data_length(self.width, self.height, 1, self.format, bottom_data.len())?;
-        check_data_length(self.width, self.height, 1, self.format, front_data.len())?;
-        check_data_length(self.width, self.height, 1, self.format, back_data.len())?;
+        self.fill_level(
+            0,
+            [
+                right_data,
+                left_data,
+                top_data,
+                bottom_data,
+                front_data,
+                back_data,
+            ],
+        )?;
+        self.generate_mip_maps();
+        Ok(())
+    }
+
+    fn fill_level(&mut self, level: u32, data: [&[T]; 6]) -> ThreeDResult<()> {
+        let width = (self.width >> level).max(1);
+        let height = (self.height >> level).max(1);
+        for side in data.iter() {
+            check_data_length(width, height, 1, self.format, side.len())?;
+        }
         self.context
             .bind_texture(consts::TEXTURE_CUBE_MAP, &self.id);
-        for i in 0..6 {
-            let data = match i {
-                0 => right_data,
-                1 => left_data,
-                2 => top_data,
-                3 => bottom_data,
-                4 => front_data,
-                5 => back_data,
-                _ => unreachable!(),
-            };
+        for (i, side) in data.iter().enumerate() {
             T::fill(
                 &self.context,
                 consts::TEXTURE_CUBE_MAP_POSITIVE_X + i as u32,
-                self.width,
-                self.height,
+                level,
+                width,
+                height,
                 None,
                 self.format,
-                data,
+                side,
             );
         }
-        self.generate_mip_maps();
         Ok(())
     }
 
@@ -309,6 +376,55 @@ impl<T: TextureDataType> TextureCubeMap<T> {
         Ok(texture)
     }
 
+    ///
+    /// Converts this cube texture into an equirectangular image with the given size, ie. a panorama where the horizontal axis is the angle around the vertical axis
+    /// and the vertical axis is the angle from the top. This is the inverse of [TextureCubeMap::new_from_equirectangular] and [CpuTextureCube::from_equirectangular]
+    /// and the result can for example be saved to disk.
+    /// The returned texture always has the RGBA format and the first row of the data is the top of the image.
+    ///
+    /// **Note:** Only works for textures with data type `u8`, `f16` or `f32`.
+    ///
+    pub fn to_equirectangular(&self, width: u32, height: u32) -> ThreeDResult<CpuTexture<T>> {
+        let mut texture = Texture2D::<T>::new_empty(
+            &self.context,
+            width,
+            height,
+            Interpolation::Nearest,
+            Interpolation::Nearest,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+            Format::RGBA,
+        )?;
+        let fragment_shader_source = "uniform samplerCube environmentMap;
+            in vec2 uv;
+            layout (location = 0) out vec4 outColor;
+
+            void main()
+            {
+                float phi = (uv.x - 0.5) * 6.2831853;
+                float theta = (0.5 - uv.y) * 3.1415927;
+                vec3 direction = vec3(cos(theta) * cos(phi), sin(theta), cos(theta) * sin(phi));
+                outColor = textureLod(environmentMap, direction, 0.0);
+            }";
+        let viewport = Viewport::new_at_origo(width, height);
+        self.context.effect(fragment_shader_source, |effect| {
+            texture.write(ClearState::default(), || {
+                effect.use_texture_cube("environmentMap", self)?;
+                effect.render(RenderStates::default(), viewport)
+            })
+        })?;
+        Ok(CpuTexture {
+            data: texture.read(viewport)?,
+            width,
+            height,
+            format: Format::RGBA,
+            wrap_s: Wrapping::Repeat,
+            wrap_t: Wrapping::ClampToEdge,
+            ..Default::default()
+        })
+    }
+
     ///
     /// Writes whatever rendered in the `render` closure into the color texture at the cube map side given by the input parameter `side`.
     /// Before writing, the texture side is cleared based on the given clear state.
@@ -361,7 +477,7 @@ impl<T: TextureDataType> TextureCubeMap<T> {
     }
 
     pub(in crate::core) fn generate_mip_maps(&self) {
-        if self.number_of_mip_maps > 1 {
+        if self.number_of_mip_maps > 1 && !self.format.is_compressed() {
             self.context
                 .bind_texture(consts::TEXTURE_CUBE_MAP, &self.id);
             self.context.generate_mipmap(consts::TEXTURE_CUBE_MAP);
@@ -7,6 +7,8 @@ mod loader;
 #[doc(inline)]
 pub use loader::*;
 
+mod mount;
+
 mod parser;
 #[doc(inline)]
 pub use parser::*;
@@ -17,6 +19,18 @@ mod saver;
 #[cfg(not(target_arch = "wasm32"))]
 pub use saver::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod watcher;
+#[doc(inline)]
+#[cfg(not(target_arch = "wasm32"))]
+pub use watcher::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod recorder;
+#[doc(inline)]
+#[cfg(not(target_arch = "wasm32"))]
+pub use recorder::*;
+
 use thiserror::Error;
 ///
 /// Error from the [io](crate::io) module.
@@ -30,13 +44,52 @@ pub enum IOError {
     #[cfg(feature = "3d-io")]
     #[error("error while parsing a .3d file")]
     ThreeD(#[from] bincode::Error),
+    #[cfg(feature = "3d-io")]
+    #[error("the file is not a .3d file")]
+    ThreeDInvalidHeader,
+    #[cfg(feature = "3d-io")]
+    #[error("the .3d file has version {0} which is not supported, expected version {1}")]
+    ThreeDUnsupportedVersion(u32, u32),
+    #[cfg(feature = "3d-io")]
+    #[error("the .3d file contains a texture with the unknown format {0}")]
+    ThreeDUnknownFormat(u16),
+    #[cfg(feature = "image-io")]
+    #[error("cannot encode a texture with the compressed format {0:?} as an image")]
+    CompressedImage(crate::core::CompressedFormat),
+    #[cfg(feature = "image-io")]
+    #[error("cannot save a texture with {0} data as a .{1} image")]
+    ImageDataType(String, String),
+    #[cfg(feature = "exr-io")]
+    #[error("error while writing an .exr file")]
+    Exr(#[from] ::exr::error::Error),
     #[cfg(feature = "obj-io")]
     #[error("error while parsing an .obj file")]
     Obj(#[from] wavefront_obj::ParseError),
+    #[cfg(feature = "ply-io")]
+    #[error("error while parsing a .ply file: {0}")]
+    Ply(String),
+    #[cfg(feature = "stl-io")]
+    #[error("error while parsing an .stl file: {0}")]
+    Stl(String),
+    #[cfg(feature = "point-cloud-io")]
+    #[error("error while parsing a point cloud file: {0}")]
+    PointCloud(String),
+    #[cfg(feature = "ktx2-io")]
+    #[error("error while parsing a .ktx2 file: {0}")]
+    Ktx2(String),
+    #[cfg(feature = "dds-io")]
+    #[error("error while parsing a .dds file: {0}")]
+    Dds(String),
+    #[cfg(feature = "volume-io")]
+    #[error("error while parsing a volume file: {0}")]
+    Volume(String),
     #[cfg(feature = "gltf-io")]
     #[error("error while parsing a .gltf file")]
     Gltf(#[from] ::gltf::Error),
     #[cfg(feature = "gltf-io")]
+    #[error("error while serializing a .gltf file")]
+    GltfSerialization(#[from] ::gltf::json::Error),
+    #[cfg(feature = "gltf-io")]
     #[error("the .gltf file contain corrupt buffer data")]
     GltfCorruptData,
     #[cfg(feature = "gltf-io")]
@@ -47,6 +100,21 @@ pub enum IOError {
     FailedLoading(String, std::io::Error),
     #[error("error while loading the file {0}: {1}")]
     FailedLoadingUrl(String, reqwest::Error),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("the frame has size {0}x{1} which is different from the size {2}x{3} of the first recorded frame")]
+    FrameSizeChanged(u32, u32, u32, u32),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("the frame contains {0} bytes but a frame of size {1}x{2} requires {3} bytes")]
+    FrameDataSize(usize, u32, u32, usize),
+    #[cfg(feature = "zip-io")]
+    #[error("error while reading a .zip archive")]
+    Zip(#[from] zip::result::ZipError),
     #[error("tried to use {0} which was not loaded")]
     NotLoaded(String),
+    #[error("tried to use {0} which failed to load: {1}")]
+    LoadFailed(String, String),
+    #[error("the loading was cancelled")]
+    Cancelled,
+    #[error("error while decoding the loaded resources: {0}")]
+    Decoding(String),
 }
@@ -1,41 +1,322 @@
+use super::mount::*;
 use crate::core::*;
 use crate::io::*;
 use reqwest::Url;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 ///
 /// Convenience functionality to load some resources and, when loaded, use them to create one or more objects (for example a 3D model, a skybox, a texture etc).
 /// To get the loaded object, use the `borrow()` or `borrow_mut()` methods which returns `Some` reference to the object if loaded and `None` otherwise.
+/// Use [progress](Self::progress) to get the progress of the load and [cancel](Self::cancel) to stop it.
+/// A file that fails to load fails the whole load, unless the [Loading] is constructed using [Loading::new_with_failures].
 ///
 pub struct Loading<T> {
     load: Rc<RefCell<Option<ThreeDResult<T>>>>,
+    progress: Rc<Cell<LoadProgress>>,
+    cancellation: LoadCancellation,
+    cpu_ready: Rc<Cell<bool>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    decoding: RefCell<Option<Box<dyn FnMut() -> bool>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    reload: Option<Reload<T>>,
+}
+
+///
+/// A message from the thread which loads and decodes the resources of a [Loading] to the render thread.
+///
+#[cfg(not(target_arch = "wasm32"))]
+enum DecodeMessage<D> {
+    Progress(LoadProgress),
+    Decoded(Result<D, String>),
+}
+
+/// The closure which maps the loaded resources to the object, shared between the first load and any reloads.
+#[cfg(not(target_arch = "wasm32"))]
+type OnLoad<T> = Rc<RefCell<dyn FnMut(Context, ThreeDResult<Loaded>) -> ThreeDResult<T>>>;
+
+///
+/// The state needed to reload the resources of a [Loading] when they change on disk.
+///
+#[cfg(not(target_arch = "wasm32"))]
+struct Reload<T> {
+    watcher: FileWatcher,
+    context: Context,
+    on_load: OnLoad<T>,
+    pending: bool,
 }
 
 impl<T: 'static> Loading<T> {
     ///
     /// Starts loading the resources defined by `paths` and calls the `on_load` closure when everything is loaded.
+    /// If the load is cancelled, the `on_load` closure is called with [IOError::Cancelled].
+    /// If any of the files fails to load, the `on_load` closure is called with that error.
+    ///
+    /// On desktop, the files are loaded before this returns, so the load cannot be cancelled, see [Loading::new_decoded] for loading on a worker thread.
     ///
     pub fn new(
         context: &Context,
         paths: &[impl AsRef<Path>],
         on_load: impl 'static + FnOnce(Context, ThreeDResult<Loaded>) -> ThreeDResult<T>,
+    ) -> Self {
+        Self::new_internal(context, paths, false, on_load)
+    }
+
+    ///
+    /// Starts loading the resources defined by `paths` and calls the `on_load` closure when everything is loaded, just like [Loading::new].
+    /// Unlike [Loading::new], a file that fails to load does not fail the whole load. Instead the error is recorded in the [Loaded] resources,
+    /// see [Loaded::failure], so that for example a 3D model can be loaded without a missing texture.
+    ///
+    pub fn new_with_failures(
+        context: &Context,
+        paths: &[impl AsRef<Path>],
+        on_load: impl 'static + FnOnce(Context, ThreeDResult<Loaded>) -> ThreeDResult<T>,
+    ) -> Self {
+        Self::new_internal(context, paths, true, on_load)
+    }
+
+    fn new_internal(
+        context: &Context,
+        paths: &[impl AsRef<Path>],
+        record_failures: bool,
+        on_load: impl 'static + FnOnce(Context, ThreeDResult<Loaded>) -> ThreeDResult<T>,
     ) -> Self {
         let load = Rc::new(RefCell::new(None));
         let load_clone = load.clone();
         let context_clone = context.clone();
-        Loader::load(paths, move |loaded| {
-            *load_clone.borrow_mut() = Some(on_load(context_clone, loaded));
+        let progress = Rc::new(Cell::new(LoadProgress {
+            file_count: paths.len(),
+            ..Default::default()
+        }));
+        let progress_clone = progress.clone();
+        let cpu_ready = Rc::new(Cell::new(false));
+        let cpu_ready_clone = cpu_ready.clone();
+        let cancellation = LoadCancellation::new();
+        Loader::load_internal(
+            paths,
+            cancellation.clone(),
+            move |p| progress_clone.set(p),
+            record_failures,
+            move |loaded| {
+                cpu_ready_clone.set(true);
+                *load_clone.borrow_mut() = Some(on_load(context_clone, loaded));
+            },
+        );
+        Self {
+            load,
+            progress,
+            cancellation,
+            cpu_ready,
+            #[cfg(not(target_arch = "wasm32"))]
+            decoding: RefCell::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            reload: None,
+        }
+    }
+
+    ///
+    /// Starts loading the resources defined by `paths` and decoding them using the `decode` closure, for example into [CpuTexture]s, [CpuMesh]es and [CpuMaterial]s.
+    /// On desktop, both loading and decoding is done on a pool of worker threads shared by all loads, so that the render thread is not blocked.
+    /// When the decoded data is ready, the `on_load` closure is called on the render thread to create the GPU resources, for example a [Texture2D] or a [Mesh](crate::Mesh).
+    /// This happens the first time the [Loading] is used after the data is ready, for example when calling [is_loaded](Self::is_loaded) or `borrow()`.
+    /// Use [is_cpu_ready](Self::is_cpu_ready) to check whether the decoded data is ready.
+    ///
+    /// On web, the resources are loaded asynchronously and decoded on the main thread since threads are not available.
+    ///
+    /// Errors in the `decode` closure are passed to the `on_load` closure as [IOError::Decoding] since the errors cannot be sent between threads.
+    /// If the `decode` closure panics, the `on_load` closure is called with [IOError::Decoding] as well.
+    ///
+    pub fn new_decoded<D: 'static + Send>(
+        context: &Context,
+        paths: &[impl AsRef<Path>],
+        decode: impl 'static + Send + FnOnce(ThreeDResult<Loaded>) -> ThreeDResult<D>,
+        on_load: impl 'static + FnOnce(Context, ThreeDResult<D>) -> ThreeDResult<T>,
+    ) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::new(context, paths, move |context, loaded| {
+                on_load(context, decode(loaded))
+            })
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let paths: Vec<PathBuf> = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
+            let cancellation = LoadCancellation::new();
+            let cancellation_clone = cancellation.clone();
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let file_count = paths.len();
+            spawn_decode_job(move || {
+                let progress_sender = sender.clone();
+                let mut loaded = Loaded::new();
+                let result = load_from_disk(
+                    paths,
+                    &mut loaded,
+                    &mut Progress::new(
+                        file_count,
+                        &mut |p| {
+                            progress_sender.send(DecodeMessage::Progress(p)).ok();
+                        },
+                        &cancellation_clone,
+                        false,
+                    ),
+                )
+                .map(|_| loaded);
+                let decoded = decode(result).map_err(|e| e.to_string());
+                // The receiver is gone if the Loading is dropped
+                sender.send(DecodeMessage::Decoded(decoded)).ok();
+            });
+
+            let load = Rc::new(RefCell::new(None));
+            let load_clone = load.clone();
+            let progress = Rc::new(Cell::new(LoadProgress {
+                file_count,
+                ..Default::default()
+            }));
+            let progress_clone = progress.clone();
+            let cpu_ready = Rc::new(Cell::new(false));
+            let cpu_ready_clone = cpu_ready.clone();
+            let context = context.clone();
+            let mut on_load = Some(on_load);
+            let poll = move || loop {
+                let decoded = match receiver.try_recv() {
+                    Ok(DecodeMessage::Progress(p)) => {
+                        progress_clone.set(p);
+                        continue;
+                    }
+                    Ok(DecodeMessage::Decoded(decoded)) => decoded,
+                    Err(std::sync::mpsc::TryRecvError::Empty) => return false,
+                    // The thread is gone without sending the decoded data, so the decode closure panicked
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        Err("decode thread panicked".to_string())
+                    }
+                };
+                cpu_ready_clone.set(true);
+                let decoded = decoded.map_err(|e| IOError::Decoding(e).into());
+                *load_clone.borrow_mut() =
+                    Some((on_load.take().unwrap())(context.clone(), decoded));
+                return true;
+            };
+            Self {
+                load,
+                progress,
+                cancellation,
+                cpu_ready,
+                decoding: RefCell::new(Some(Box::new(poll))),
+                reload: None,
+            }
+        }
+    }
+
+    ///
+    /// Receives the progress and decoded data from the worker thread, if any, and calls the `on_load` closure when the decoded data is ready.
+    ///
+    fn update(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // The object cannot be replaced while it is borrowed, so wait until the next time
+            if self.load.try_borrow_mut().is_err() {
+                return;
+            }
+            let mut decoding = self.decoding.borrow_mut();
+            if let Some(poll) = decoding.as_mut() {
+                if poll() {
+                    *decoding = None;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns true if the resources are loaded and decoded, ie. the CPU-side data is ready and only the creation of GPU resources in the `on_load` closure remains or is done.
+    ///
+    pub fn is_cpu_ready(&self) -> bool {
+        self.update();
+        self.cpu_ready.get()
+    }
+
+    ///
+    /// Loads the resources defined by `paths` and calls the `on_load` closure when everything is loaded, just like [Loading::new].
+    /// Furthermore, the files are watched for changes, see [FileWatcher], so that when [reload_if_changed](Self::reload_if_changed) is called after a file has changed,
+    /// all of the files are read again and the `on_load` closure is called again to rebuild the object, for example a [Texture2D], a [Mesh](crate::Mesh) or a material.
+    ///
+    #[cfg_attr(docsrs, doc(not(target_arch = "wasm32")))]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new_watched(
+        context: &Context,
+        paths: &[impl AsRef<Path>],
+        on_load: impl 'static + FnMut(Context, ThreeDResult<Loaded>) -> ThreeDResult<T>,
+    ) -> Self {
+        // Start watching before loading, so that changes made during the load are not missed
+        let watcher = FileWatcher::new(paths);
+        let on_load: OnLoad<T> = Rc::new(RefCell::new(on_load));
+        let on_load_clone = on_load.clone();
+        let mut loading = Self::new(context, paths, move |context, loaded| {
+            (on_load_clone.borrow_mut())(context, loaded)
         });
-        Self { load }
+        loading.reload = Some(Reload {
+            watcher,
+            context: context.clone(),
+            on_load,
+            pending: false,
+        });
+        loading
+    }
+
+    ///
+    /// If this was constructed using [Loading::new_watched] and any of the files have changed on disk since the last load,
+    /// all of the files are read again and the `on_load` closure is called again to replace the object.
+    /// Returns true if the object was reloaded. Call this for example once per frame.
+    /// If the object is borrowed when a change is detected, the reload is postponed until the next call where it is not borrowed.
+    ///
+    #[cfg_attr(docsrs, doc(not(target_arch = "wasm32")))]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_if_changed(&mut self) -> bool {
+        if let Some(reload) = self.reload.as_mut() {
+            if reload.watcher.has_changed() {
+                reload.pending = true;
+            }
+            if reload.pending {
+                // The object cannot be replaced while it is borrowed, so keep the change pending until the next time
+                if let Ok(mut load) = self.load.try_borrow_mut() {
+                    reload.pending = false;
+                    let loaded = reload.watcher.load();
+                    *load = Some((reload.on_load.borrow_mut())(
+                        reload.context.clone(),
+                        loaded,
+                    ));
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    ///
+    /// Returns the progress of loading the resources.
+    /// Note that the object is not loaded until the `on_load` closure is called, see [is_loaded](Self::is_loaded).
+    ///
+    pub fn progress(&self) -> LoadProgress {
+        self.update();
+        self.progress.get()
+    }
+
+    ///
+    /// Cancels the load if it is still in progress, in which case the `on_load` closure is called with [IOError::Cancelled].
+    /// On desktop, only a load started using [Loading::new_decoded] can be cancelled since the other constructors load the files before returning.
+    ///
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
     }
 
     ///
     /// Returns true if the object is loaded and mapped by the `on_load` closure.
     ///
     pub fn is_loaded(&self) -> bool {
+        self.update();
         self.load.borrow().is_some()
     }
 }
@@ -43,12 +324,14 @@ impl<T: 'static> Loading<T> {
 impl<T: 'static> std::ops::Deref for Loading<T> {
     type Target = Rc<RefCell<Option<ThreeDResult<T>>>>;
     fn deref(&self) -> &Self::Target {
+        self.update();
         &self.load
     }
 }
 
 impl<T: 'static> std::ops::DerefMut for Loading<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.update();
         &mut self.load
     }
 }
@@ -57,10 +340,14 @@ impl<T: 'static> std::ops::DerefMut for Loading<T> {
 /// Contains the resources loaded using the [Loader](crate::Loader) and/or manually inserted using the [insert_bytes](Self::insert_bytes) method.
 /// Use the [remove_bytes](crate::Loaded::remove_bytes) or [get_bytes](crate::Loaded::get_bytes) function to extract the raw byte array for the loaded resource
 /// or one of the other methods to both extract and deserialize a loaded resource.
+/// Resources that failed to load using for example [Loader::load_with_progress] are recorded and can be inspected using [failure](Self::failure).
+/// Files can also be mounted from a directory, from memory or from a .zip archive, see for example [mount_memory](Self::mount_memory).
 ///
 #[derive(Default)]
 pub struct Loaded {
     loaded: HashMap<PathBuf, Vec<u8>>,
+    failed: HashMap<PathBuf, IOError>,
+    mounts: Vec<(PathBuf, Mount)>,
 }
 
 impl Loaded {
@@ -76,49 +363,26 @@ impl Loaded {
     /// Remove and returns the loaded byte array for the resource at the given path.
     /// The byte array then has to be deserialized to whatever type this resource is (image, 3D model etc.).
     ///
+    /// The path must be the same as the path the resource was loaded or inserted with, except that `.` and `..` components are resolved,
+    /// or it must be the path to a file in one of the mounts (see [mount_memory](Self::mount_memory)).
+    /// For example "models/a/texture.png" matches "models/a/../a/texture.png" while "texture.png" does not match "models/a/texture.png".
+    ///
+    /// # Errors
+    /// Returns an error if the path is not found.
+    ///
     pub fn remove_bytes(&mut self, path: impl AsRef<Path>) -> ThreeDResult<Vec<u8>> {
-        if let Some((_, bytes)) = self.loaded.remove_entry(path.as_ref()) {
-            Ok(bytes)
-        } else {
-            let key = self
-                .loaded
-                .iter()
-                .find(|(k, _)| {
-                    k.to_str()
-                        .unwrap()
-                        .contains(path.as_ref().to_str().unwrap())
-                })
-                .ok_or(IOError::NotLoaded(
-                    path.as_ref().to_str().unwrap().to_owned(),
-                ))?
-                .0
-                .clone();
-            Ok(self.loaded.remove(&key).unwrap())
-        }
+        let key = self.resolve(path.as_ref())?;
+        Ok(self.loaded.remove(&key).unwrap())
     }
 
     ///
     /// Returns a reference to the loaded byte array for the resource at the given path.
     /// The byte array then has to be deserialized to whatever type this resource is (image, 3D model etc.).
+    /// The path is matched in the same way as in [remove_bytes](Self::remove_bytes).
     ///
     pub fn get_bytes(&mut self, path: impl AsRef<Path>) -> ThreeDResult<&[u8]> {
-        if let Some(bytes) = self.loaded.get(path.as_ref()) {
-            Ok(bytes.as_ref())
-        } else {
-            let key = self
-                .loaded
-                .iter()
-                .find(|(k, _)| {
-                    k.to_str()
-                        .unwrap()
-                        .contains(path.as_ref().to_str().unwrap())
-                })
-                .ok_or(IOError::NotLoaded(
-                    path.as_ref().to_str().unwrap().to_owned(),
-                ))?
-                .0;
-            Ok(self.loaded.get(key).unwrap())
-        }
+        let key = self.resolve(path.as_ref())?;
+        Ok(self.loaded.get(&key).unwrap())
     }
 
     ///
@@ -128,6 +392,116 @@ impl Loaded {
     pub fn insert_bytes(&mut self, path: impl AsRef<Path>, bytes: Vec<u8>) {
         self.loaded.insert(path.as_ref().to_path_buf(), bytes);
     }
+
+    ///
+    /// Returns the error if the resource at the given path failed to load and `None` if it was loaded or never requested.
+    /// The path is matched in the same way as in [remove_bytes](Self::remove_bytes).
+    /// Parsers use this to skip optional resources, for example a missing texture of a 3D model, instead of failing.
+    ///
+    pub fn failure(&self, path: impl AsRef<Path>) -> Option<&IOError> {
+        self.failed
+            .get(path.as_ref())
+            .or_else(|| find_key(&self.failed, path.as_ref()).and_then(|key| self.failed.get(key)))
+    }
+
+    ///
+    /// Returns an iterator over the paths to all resources that failed to load together with the errors.
+    ///
+    pub fn failures(&self) -> impl Iterator<Item = (&Path, &IOError)> {
+        self.failed
+            .iter()
+            .map(|(path, error)| (path.as_path(), error))
+    }
+
+    ///
+    /// Mounts the files in the given directory on disk at the given mount point, for example
+    /// the file `assets/models/tree.obj` can be read using the path `models/tree.obj` when the directory `assets` is mounted at the empty mount point.
+    /// The files are read when needed and only exact paths below the mount point are matched.
+    /// Files in mounts added later take precedence over files in mounts added earlier.
+    ///
+    #[cfg_attr(docsrs, doc(not(target_arch = "wasm32")))]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn mount_directory(&mut self, mount_point: impl AsRef<Path>, directory: impl AsRef<Path>) {
+        self.mount(
+            mount_point.as_ref(),
+            Mount::Directory(directory.as_ref().to_path_buf()),
+        );
+    }
+
+    ///
+    /// Mounts the given files at the given mount point, where the files are given as a map from paths relative to the mount point to their bytes.
+    /// Only exact paths below the mount point are matched and files in mounts added later take precedence over files in mounts added earlier.
+    ///
+    pub fn mount_memory(
+        &mut self,
+        mount_point: impl AsRef<Path>,
+        files: HashMap<PathBuf, Vec<u8>>,
+    ) {
+        let files = files
+            .into_iter()
+            .map(|(path, bytes)| (normalize(&path), bytes))
+            .collect();
+        self.mount(mount_point.as_ref(), Mount::Memory(files));
+    }
+
+    ///
+    /// Mounts the files in the given .zip archive at the given mount point, for example
+    /// the file `models/tree.obj` in the archive can be read using the path `bundle/models/tree.obj` when the archive is mounted at `bundle`.
+    /// The archive itself is typically loaded using the [Loader] and the files are decompressed when needed.
+    /// Only exact paths below the mount point are matched and files in mounts added later take precedence over files in mounts added earlier.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid .zip archive.
+    ///
+    #[cfg(feature = "zip-io")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zip-io")))]
+    pub fn mount_zip(&mut self, mount_point: impl AsRef<Path>, bytes: Vec<u8>) -> ThreeDResult<()> {
+        let archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(IOError::Zip)?;
+        self.mount(mount_point.as_ref(), Mount::Zip(archive));
+        Ok(())
+    }
+
+    fn mount(&mut self, mount_point: &Path, mount: Mount) {
+        self.mounts.push((normalize(mount_point), mount));
+    }
+
+    ///
+    /// Returns the key of the loaded resource matching the given path, reading it from the mounts if necessary.
+    ///
+    fn resolve(&mut self, path: &Path) -> ThreeDResult<PathBuf> {
+        if self.loaded.contains_key(path) {
+            return Ok(path.to_path_buf());
+        }
+        if let Some(key) = find_key(&self.loaded, path) {
+            return Ok(key.clone());
+        }
+        let normalized = normalize(path);
+        for (mount_point, mount) in self.mounts.iter_mut().rev() {
+            if let Ok(relative_path) = normalized.strip_prefix(mount_point) {
+                if let Some(bytes) = mount.read(relative_path) {
+                    self.loaded.insert(normalized.clone(), bytes?);
+                    return Ok(normalized);
+                }
+            }
+        }
+        Err(self.not_loaded(path))?
+    }
+
+    fn not_loaded(&self, path: &Path) -> IOError {
+        let path_name = path.to_string_lossy().into_owned();
+        match self.failure(path) {
+            Some(error) => IOError::LoadFailed(path_name, error.to_string()),
+            None => IOError::NotLoaded(path_name),
+        }
+    }
+}
+
+///
+/// Returns the key in the given map which is equal to the given path when both are normalized.
+///
+fn find_key<'a, V>(map: &'a HashMap<PathBuf, V>, path: &Path) -> Option<&'a PathBuf> {
+    let normalized = normalize(path);
+    map.keys().find(|k| normalize(k) == normalized)
 }
 
 impl std::fmt::Debug for Loaded {
@@ -137,10 +511,71 @@ impl std::fmt::Debug for Loaded {
             d.field("path", key);
             d.field("byte length", &value.len());
         }
+        for (key, value) in self.failed.iter() {
+            d.field("failed path", key);
+            d.field("error", value);
+        }
+        for (mount_point, _) in self.mounts.iter() {
+            d.field("mount point", mount_point);
+        }
         d.finish()
     }
 }
 
+///
+/// The progress of loading a set of resources, see for example [Loader::load_with_progress].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of files that are either loaded or failed to load.
+    pub files_completed: usize,
+    /// The total number of files to load.
+    pub file_count: usize,
+    /// The number of bytes in the files that are loaded.
+    pub bytes_completed: usize,
+}
+
+impl LoadProgress {
+    ///
+    /// Returns true if all files are either loaded or failed to load.
+    ///
+    pub fn is_done(&self) -> bool {
+        self.files_completed == self.file_count
+    }
+}
+
+///
+/// Used to cancel an in-flight load, see for example [Loader::load_with_progress].
+/// All clones of a cancellation share the same state, so a load can be cancelled from another thread or from within the progress callback.
+///
+#[derive(Clone, Debug, Default)]
+pub struct LoadCancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl LoadCancellation {
+    ///
+    /// Constructs a new cancellation which is not cancelled.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Cancels the load, which then returns [IOError::Cancelled] as soon as possible.
+    ///
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    ///
+    /// Returns true if [cancel](Self::cancel) has been called.
+    ///
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
 ///
 /// Functionality for loading any type of resource runtime on both desktop and web.
 ///
@@ -154,17 +589,58 @@ impl Loader {
     /// **Note:** This method must not be called from an async function. In that case, use [Loader::load_async] instead.
     ///
     pub fn load(paths: &[impl AsRef<Path>], on_done: impl 'static + FnOnce(ThreeDResult<Loaded>)) {
+        Self::load_internal(paths, LoadCancellation::new(), |_| {}, false, on_done);
+    }
+
+    ///
+    /// Loads all of the resources in the given paths then calls `on_done` with all of the [Loaded] resources.
+    /// The `on_progress` closure is called each time a file is completed and the load can be stopped using the given cancellation,
+    /// in which case `on_done` is called with [IOError::Cancelled].
+    ///
+    /// Unlike [Loader::load], a file that fails to load does not fail the whole load. Instead the error is recorded in the [Loaded] resources,
+    /// see [Loaded::failure], so that for example a 3D model can be loaded without a missing texture.
+    ///
+    /// **Note:** This method must not be called from an async function. In that case, use [Loader::load_async_with_progress] instead.
+    ///
+    pub fn load_with_progress(
+        paths: &[impl AsRef<Path>],
+        cancellation: &LoadCancellation,
+        on_progress: impl 'static + FnMut(LoadProgress),
+        on_done: impl 'static + FnOnce(ThreeDResult<Loaded>),
+    ) {
+        Self::load_internal(paths, cancellation.clone(), on_progress, true, on_done);
+    }
+
+    fn load_internal(
+        paths: &[impl AsRef<Path>],
+        cancellation: LoadCancellation,
+        mut on_progress: impl 'static + FnMut(LoadProgress),
+        record_failures: bool,
+        on_done: impl 'static + FnOnce(ThreeDResult<Loaded>),
+    ) {
+        let paths: Vec<PathBuf> = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
         #[cfg(target_arch = "wasm32")]
         {
-            let paths: Vec<PathBuf> = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
             wasm_bindgen_futures::spawn_local(async move {
-                let loaded = Self::load_async(&paths).await;
-                on_done(loaded);
+                let mut progress = Progress::new(
+                    paths.len(),
+                    &mut on_progress,
+                    &cancellation,
+                    record_failures,
+                );
+                on_done(load_urls_relative(paths, &mut progress).await);
             });
         }
         #[cfg(not(target_arch = "wasm32"))]
         {
-            on_done(Self::load_blocking(paths));
+            let mut progress = Progress::new(
+                paths.len(),
+                &mut on_progress,
+                &cancellation,
+                record_failures,
+            );
+            let mut loaded = Loaded::new();
+            on_done(load_from_disk(paths, &mut loaded, &mut progress).map(|_| loaded));
         }
     }
 
@@ -183,6 +659,30 @@ impl Loader {
                 .map(|p| p.as_ref().to_path_buf())
                 .collect::<Vec<_>>(),
             &mut loaded,
+            &mut Progress::new(paths.len(), &mut |_| {}, &LoadCancellation::new(), false),
+        )?;
+        Ok(loaded)
+    }
+
+    ///
+    /// Parallel loads all of the resources in the given paths from disk and returns the [Loaded] resources.
+    /// Reports progress and supports cancellation and recording of failed files like [Loader::load_with_progress].
+    ///
+    #[cfg_attr(docsrs, doc(not(target_arch = "wasm32")))]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_blocking_with_progress(
+        paths: &[impl AsRef<Path>],
+        cancellation: &LoadCancellation,
+        mut on_progress: impl FnMut(LoadProgress),
+    ) -> ThreeDResult<Loaded> {
+        let mut loaded = Loaded::new();
+        load_from_disk(
+            paths
+                .iter()
+                .map(|p| p.as_ref().to_path_buf())
+                .collect::<Vec<_>>(),
+            &mut loaded,
+            &mut Progress::new(paths.len(), &mut on_progress, cancellation, true),
         )?;
         Ok(loaded)
     }
@@ -194,18 +694,11 @@ impl Loader {
     ///
     #[cfg(target_arch = "wasm32")]
     pub async fn load_async(paths: &[impl AsRef<Path>]) -> ThreeDResult<Loaded> {
-        let base_path = base_path();
-        let mut urls = Vec::new();
-        for path in paths.iter() {
-            let mut p = path.as_ref().to_path_buf();
-            if !is_absolute_url(p.to_str().unwrap()) {
-                p = base_path.join(p);
-            }
-            urls.push(p);
-        }
-        let mut loaded = Loaded::new();
-        load_urls(urls, &mut loaded).await?;
-        Ok(loaded)
+        load_urls_relative(
+            paths.iter().map(|p| p.as_ref().to_path_buf()).collect(),
+            &mut Progress::new(paths.len(), &mut |_| {}, &LoadCancellation::new(), false),
+        )
+        .await
     }
 
     #[allow(rustdoc::bare_urls)]
@@ -217,60 +710,259 @@ impl Loader {
     ///
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn load_async(paths: &[impl AsRef<Path>]) -> ThreeDResult<Loaded> {
-        let mut urls = Vec::new();
-        let mut local_paths = Vec::new();
-        for path in paths.iter() {
-            let path = path.as_ref().to_path_buf();
-            if is_absolute_url(path.to_str().unwrap()) {
-                urls.push(path);
-            } else {
-                local_paths.push(path);
+        load_urls_and_disk(
+            paths,
+            &mut Progress::new(paths.len(), &mut |_| {}, &LoadCancellation::new(), false),
+        )
+        .await
+    }
+
+    ///
+    /// Async loads all of the resources in the given paths and returns the [Loaded] resources.
+    /// Reports progress and supports cancellation and recording of failed files like [Loader::load_with_progress].
+    ///
+    pub async fn load_async_with_progress(
+        paths: &[impl AsRef<Path>],
+        cancellation: &LoadCancellation,
+        mut on_progress: impl FnMut(LoadProgress),
+    ) -> ThreeDResult<Loaded> {
+        let mut progress = Progress::new(paths.len(), &mut on_progress, cancellation, true);
+        #[cfg(target_arch = "wasm32")]
+        {
+            load_urls_relative(
+                paths.iter().map(|p| p.as_ref().to_path_buf()).collect(),
+                &mut progress,
+            )
+            .await
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            load_urls_and_disk(paths, &mut progress).await
+        }
+    }
+}
+
+///
+/// Keeps track of the progress of a load and decides what happens when a file fails to load.
+///
+struct Progress<'a> {
+    progress: LoadProgress,
+    on_progress: &'a mut dyn FnMut(LoadProgress),
+    cancellation: &'a LoadCancellation,
+    record_failures: bool,
+}
+
+impl<'a> Progress<'a> {
+    fn new(
+        file_count: usize,
+        on_progress: &'a mut dyn FnMut(LoadProgress),
+        cancellation: &'a LoadCancellation,
+        record_failures: bool,
+    ) -> Self {
+        Self {
+            progress: LoadProgress {
+                file_count,
+                ..Default::default()
+            },
+            on_progress,
+            cancellation,
+            record_failures,
+        }
+    }
+
+    fn check_cancelled(&self) -> ThreeDResult<()> {
+        if self.cancellation.is_cancelled() {
+            Err(IOError::Cancelled)?;
+        }
+        Ok(())
+    }
+
+    fn complete(
+        &mut self,
+        loaded: &mut Loaded,
+        path: PathBuf,
+        result: Result<Vec<u8>, IOError>,
+    ) -> ThreeDResult<()> {
+        self.check_cancelled()?;
+        match result {
+            Ok(bytes) => {
+                self.progress.bytes_completed += bytes.len();
+                loaded.loaded.insert(path, bytes);
             }
+            Err(error) if self.record_failures => {
+                loaded.failed.insert(path, error);
+            }
+            Err(error) => Err(error)?,
         }
+        self.progress.files_completed += 1;
+        (self.on_progress)(self.progress);
+        Ok(())
+    }
+}
 
-        let mut loaded = Loaded::new();
-        load_urls(urls, &mut loaded).await?;
-        load_from_disk(local_paths, &mut loaded)?;
-        Ok(loaded)
+#[cfg(not(target_arch = "wasm32"))]
+async fn load_urls_and_disk(
+    paths: &[impl AsRef<Path>],
+    progress: &mut Progress<'_>,
+) -> ThreeDResult<Loaded> {
+    let mut urls = Vec::new();
+    let mut local_paths = Vec::new();
+    for path in paths.iter() {
+        let path = path.as_ref().to_path_buf();
+        if is_absolute_url(path.to_str().unwrap()) {
+            urls.push((path.clone(), path));
+        } else {
+            local_paths.push(path);
+        }
     }
+
+    let mut loaded = Loaded::new();
+    load_urls(urls, &mut loaded, progress).await?;
+    load_from_disk(local_paths, &mut loaded, progress)?;
+    Ok(loaded)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn load_urls_relative(
+    paths: Vec<PathBuf>,
+    progress: &mut Progress<'_>,
+) -> ThreeDResult<Loaded> {
+    let base_path = base_path();
+    let mut urls = Vec::new();
+    for p in paths {
+        // The resources are stored using the given paths, so that they can be found using the same paths
+        let url = if is_absolute_url(p.to_str().unwrap()) {
+            p.clone()
+        } else {
+            base_path.join(&p)
+        };
+        urls.push((p, url));
+    }
+    let mut loaded = Loaded::new();
+    load_urls(urls, &mut loaded, progress).await?;
+    Ok(loaded)
+}
+
+/// A job run on the pool of worker threads used by [Loading::new_decoded].
+#[cfg(not(target_arch = "wasm32"))]
+type DecodeJob = Box<dyn FnOnce() + Send>;
+
+///
+/// Runs the given job on the pool of worker threads used by [Loading::new_decoded].
+/// The pool is started the first time this is called and has one thread per available core.
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_decode_job(job: impl FnOnce() + Send + 'static) {
+    static POOL: std::sync::OnceLock<std::sync::Mutex<std::sync::mpsc::Sender<DecodeJob>>> =
+        std::sync::OnceLock::new();
+    let pool = POOL.get_or_init(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<DecodeJob>();
+        let receiver = Arc::new(std::sync::Mutex::new(receiver));
+        let thread_count = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        for _ in 0..thread_count {
+            let receiver = receiver.clone();
+            std::thread::spawn(move || loop {
+                let job = receiver.lock().unwrap().recv();
+                let Ok(job) = job else {
+                    break;
+                };
+                // A panicking job drops its sender, which the render thread reports as a decoding error,
+                // so the panic is caught to keep the worker thread alive
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(job)).ok();
+            });
+        }
+        std::sync::Mutex::new(sender)
+    });
+    pool.lock().unwrap().send(Box::new(job)).unwrap();
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn load_from_disk(mut paths: Vec<PathBuf>, loaded: &mut Loaded) -> ThreeDResult<()> {
-    let mut handles = Vec::new();
-    for path in paths.drain(..) {
-        handles.push((
-            path.clone(),
-            std::thread::spawn(move || std::fs::read(path)),
-        ));
-    }
-
-    for (path, handle) in handles.drain(..) {
-        let bytes = handle
-            .join()
-            .unwrap()
-            .map_err(|e| IOError::FailedLoading(path.to_str().unwrap().to_string(), e))?;
-        loaded.loaded.insert(path, bytes);
+fn load_from_disk(
+    paths: Vec<PathBuf>,
+    loaded: &mut Loaded,
+    progress: &mut Progress<'_>,
+) -> ThreeDResult<()> {
+    let file_count = paths.len();
+    let thread_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(file_count);
+    let mut remaining = paths.clone();
+    let queue = Arc::new(std::sync::Mutex::new(
+        paths.into_iter().collect::<std::collections::VecDeque<_>>(),
+    ));
+    let (sender, receiver) = std::sync::mpsc::channel();
+    for _ in 0..thread_count {
+        let queue = queue.clone();
+        let sender = sender.clone();
+        std::thread::spawn(move || loop {
+            let path = queue.lock().unwrap().pop_front();
+            let Some(path) = path else {
+                break;
+            };
+            let result = std::fs::read(&path);
+            // The receiver is gone if the load is cancelled
+            if sender.send((path, result)).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    for _ in 0..file_count {
+        let (path, result) = loop {
+            progress.check_cancelled()?;
+            match receiver.recv_timeout(std::time::Duration::from_millis(10)) {
+                Ok(message) => break message,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                    // All of the threads are gone before reading the remaining files, so one of them panicked
+                    for path in remaining {
+                        let error = std::io::Error::other("the thread loading the file panicked");
+                        let error =
+                            IOError::FailedLoading(path.to_string_lossy().into_owned(), error);
+                        progress.complete(loaded, path, Err(error))?;
+                    }
+                    return Ok(());
+                }
+            }
+        };
+        if let Some(index) = remaining.iter().position(|p| *p == path) {
+            remaining.swap_remove(index);
+        }
+        let result =
+            result.map_err(|e| IOError::FailedLoading(path.to_str().unwrap().to_string(), e));
+        progress.complete(loaded, path, result)?;
     }
     Ok(())
 }
 
-async fn load_urls(mut paths: Vec<PathBuf>, loaded: &mut Loaded) -> ThreeDResult<()> {
+///
+/// Downloads the given urls and stores each of them using the path paired with the url.
+///
+async fn load_urls(
+    mut paths: Vec<(PathBuf, PathBuf)>,
+    loaded: &mut Loaded,
+    progress: &mut Progress<'_>,
+) -> ThreeDResult<()> {
     if paths.len() > 0 {
         let mut handles = Vec::new();
         let client = reqwest::Client::new();
-        for path in paths.drain(..) {
-            let url = Url::parse(path.to_str().unwrap())?;
+        for (path, url) in paths.drain(..) {
+            progress.check_cancelled()?;
+            let url = Url::parse(url.to_str().unwrap())?;
             handles.push((path, client.get(url).send().await));
         }
         for (path, handle) in handles.drain(..) {
-            let bytes = handle
-                .map_err(|e| IOError::FailedLoadingUrl(path.to_str().unwrap().to_string(), e))?
-                .bytes()
-                .await
-                .map_err(|e| IOError::FailedLoadingUrl(path.to_str().unwrap().to_string(), e))?
-                .to_vec();
-            loaded.loaded.insert(path, bytes);
+            progress.check_cancelled()?;
+            let result = match handle {
+                Ok(response) => response.bytes().await.map(|bytes| bytes.to_vec()),
+                Err(e) => Err(e),
+            }
+            .map_err(|e| IOError::FailedLoadingUrl(path.to_str().unwrap().to_string(), e));
+            progress.complete(loaded, path, result)?;
         }
     }
     Ok(())
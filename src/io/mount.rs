@@ -0,0 +1,119 @@
+use crate::core::*;
+use crate::io::*;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+///
+/// A source of files mounted at a mount point in the [Loaded] resources, see for example [Loaded::mount_memory].
+///
+pub(super) enum Mount {
+    #[cfg(not(target_arch = "wasm32"))]
+    Directory(PathBuf),
+    Memory(HashMap<PathBuf, Vec<u8>>),
+    #[cfg(feature = "zip-io")]
+    Zip(zip::ZipArchive<std::io::Cursor<Vec<u8>>>),
+}
+
+impl Mount {
+    ///
+    /// Reads the file at the given path relative to the mount point or returns `None` if the file does not exist in this mount.
+    ///
+    pub fn read(&mut self, relative_path: &Path) -> Option<ThreeDResult<Vec<u8>>> {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Mount::Directory(directory) => {
+                // Only paths below the mounted directory are matched
+                if !relative_path
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+                {
+                    return None;
+                }
+                let path = directory.join(relative_path);
+                if !path.is_file() {
+                    return None;
+                }
+                Some(std::fs::read(&path).map_err(|e| {
+                    IOError::FailedLoading(path.to_str().unwrap().to_string(), e).into()
+                }))
+            }
+            Mount::Memory(files) => files.get(relative_path).map(|bytes| Ok(bytes.clone())),
+            #[cfg(feature = "zip-io")]
+            Mount::Zip(archive) => {
+                use std::io::Read;
+                // Paths in a zip archive are always separated by forward slashes
+                let name = relative_path
+                    .components()
+                    .map(|c| c.as_os_str().to_str().unwrap())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let mut file = match archive.by_name(&name) {
+                    Ok(file) => file,
+                    Err(zip::result::ZipError::FileNotFound) => return None,
+                    Err(e) => return Some(Err(IOError::Zip(e).into())),
+                };
+                let mut bytes = Vec::with_capacity(file.size() as usize);
+                Some(
+                    file.read_to_end(&mut bytes)
+                        .map(|_| bytes)
+                        .map_err(|e| IOError::Zip(e.into()).into()),
+                )
+            }
+        }
+    }
+}
+
+///
+/// Removes `.` components and resolves `..` components of the given path without accessing the file system.
+/// URLs are returned unchanged.
+///
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    if path.to_str().map(|p| p.contains("://")).unwrap_or(false) {
+        return path.to_path_buf();
+    }
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    None | Some(Component::ParentDir)
+                ) {
+                    normalized.push("..");
+                } else {
+                    normalized.pop();
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn directory_mount_does_not_escape_directory() {
+        let root = std::env::temp_dir().join(format!("three-d-mount-{}", std::process::id()));
+        let directory = root.join("mounted");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("inside.txt"), b"inside").unwrap();
+        std::fs::write(root.join("outside.txt"), b"outside").unwrap();
+
+        let mut mount = Mount::Directory(directory.clone());
+        assert_eq!(
+            mount.read(Path::new("inside.txt")).unwrap().unwrap(),
+            b"inside"
+        );
+        assert!(mount
+            .read(&normalize(Path::new("../outside.txt")))
+            .is_none());
+        assert!(mount.read(&root.join("outside.txt")).is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
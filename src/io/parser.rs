@@ -5,12 +5,35 @@ mod obj;
 #[cfg(feature = "obj-io")]
 pub use obj::*;
 
+#[cfg(feature = "ply-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ply-io")))]
+mod ply;
+#[doc(inline)]
+#[cfg(feature = "ply-io")]
+pub use ply::*;
+
+#[cfg(feature = "stl-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stl-io")))]
+mod stl;
+#[doc(inline)]
+#[cfg(feature = "stl-io")]
+pub use stl::*;
+
+#[cfg(feature = "point-cloud-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "point-cloud-io")))]
+mod point_cloud;
+#[doc(inline)]
+#[cfg(feature = "point-cloud-io")]
+pub use point_cloud::*;
+
 #[cfg(feature = "gltf-io")]
 #[cfg_attr(docsrs, doc(cfg(feature = "gltf-io")))]
 mod gltf;
 #[doc(inline)]
 #[cfg(feature = "gltf-io")]
 pub use self::gltf::*;
+#[cfg(feature = "gltf-io")]
+mod meshopt;
 
 #[cfg(feature = "image-io")]
 #[cfg_attr(docsrs, doc(cfg(feature = "image-io")))]
@@ -18,3 +41,38 @@ mod img;
 #[cfg(feature = "image-io")]
 #[doc(inline)]
 pub use img::*;
+
+#[cfg(feature = "ktx2-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ktx2-io")))]
+mod ktx2;
+#[doc(inline)]
+#[cfg(feature = "ktx2-io")]
+pub use self::ktx2::*;
+
+#[cfg(feature = "dds-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dds-io")))]
+mod dds;
+#[doc(inline)]
+#[cfg(feature = "dds-io")]
+pub use dds::*;
+
+#[cfg(feature = "exr-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "exr-io")))]
+mod exr;
+#[doc(inline)]
+#[cfg(feature = "exr-io")]
+pub use self::exr::*;
+
+#[cfg(feature = "volume-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-io")))]
+mod volume;
+#[doc(inline)]
+#[cfg(feature = "volume-io")]
+pub use volume::*;
+
+#[cfg(feature = "3d-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "3d-io")))]
+mod threed;
+#[doc(inline)]
+#[cfg(feature = "3d-io")]
+pub use threed::*;
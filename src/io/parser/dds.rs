@@ -0,0 +1,202 @@
+use crate::core::*;
+use crate::io::*;
+use ddsfile::{AlphaMode, Caps2, D3DFormat, Dds, DxgiFormat, MiscFlag, PixelFormatFlags};
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .dds file at the given path into a [CpuTexture].
+    ///
+    /// Supports the common 8-bit uncompressed formats, which are converted to RGB(A) if needed, and the BCn formats listed in [CompressedFormat].
+    /// sRGB formats are returned with the same format as their linear counterparts since the color conversion is done in the shaders.
+    /// Any mip maps stored in the file are returned in [CpuTexture::mip_maps] and used when constructing a [Texture2D].
+    ///
+    /// # Errors
+    /// Returns an error if the file has an unsupported format or is not a 2D texture.
+    ///
+    pub fn dds(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTexture<u8>> {
+        let (format, width, height, mut levels) = parse(self.get_bytes(path)?, 1)?;
+        let data = levels.remove(0).remove(0);
+        Ok(CpuTexture {
+            data,
+            width,
+            height,
+            format,
+            mip_maps: levels
+                .into_iter()
+                .map(|mut faces| faces.remove(0))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Deserialize the loaded .dds cube map file at the given path into a [CpuTextureCube].
+    /// Supports the same formats as [Loaded::dds] and any mip maps stored in the file are returned in [CpuTextureCube::mip_maps].
+    ///
+    /// # Errors
+    /// Returns an error if the file has an unsupported format or is not a cube map.
+    ///
+    pub fn dds_cube(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTextureCube<u8>> {
+        let (format, width, height, levels) = parse(self.get_bytes(path)?, 6)?;
+        let mut levels = levels.into_iter().map(|faces| {
+            let mut faces = faces.into_iter();
+            [(); 6].map(|_| faces.next().unwrap())
+        });
+        let [right_data, left_data, top_data, bottom_data, front_data, back_data] =
+            levels.next().unwrap();
+        Ok(CpuTextureCube {
+            right_data,
+            left_data,
+            top_data,
+            bottom_data,
+            front_data,
+            back_data,
+            width,
+            height,
+            format,
+            mip_maps: levels.collect(),
+            ..Default::default()
+        })
+    }
+}
+
+///
+/// Describes how the pixel data in a .dds file is converted to texture data.
+///
+enum Layout {
+    /// The data can be used as it is.
+    Direct(Format),
+    /// Uncompressed data with the given number of bytes per pixel, where the channels of the texture are taken from the given byte offsets in each pixel.
+    Swizzled(Format, usize, &'static [usize]),
+}
+
+/// The data for each face of each mip level.
+type Levels = Vec<Vec<Vec<u8>>>;
+
+///
+/// Parses a .dds file with the given number of faces and returns the format, the size and the data for each face of each mip level.
+///
+fn parse(mut bytes: &[u8], face_count: u32) -> ThreeDResult<(Format, u32, u32, Levels)> {
+    let dds = Dds::read(&mut bytes).map_err(|e| IOError::Dds(e.to_string()))?;
+    if dds.get_depth() > 1 {
+        Err(IOError::Dds("3D textures are not supported".to_string()))?;
+    }
+    let is_cube = dds.header.caps2.contains(Caps2::CUBEMAP)
+        || dds
+            .header10
+            .as_ref()
+            .map(|h| h.misc_flag.contains(MiscFlag::TEXTURECUBE))
+            .unwrap_or(false);
+    let faces = if is_cube { 6 } else { 1 }
+        * dds
+            .header10
+            .as_ref()
+            .map(|h| h.array_size.max(1))
+            .unwrap_or(1);
+    if faces != face_count {
+        Err(IOError::Dds(format!(
+            "expected {} faces but the texture has {} faces",
+            face_count, faces
+        )))?;
+    }
+    let layout = layout(&dds).ok_or_else(|| {
+        let name = dds
+            .get_dxgi_format()
+            .map(|format| format!("{:?}", format))
+            .or_else(|| dds.get_d3d_format().map(|format| format!("{:?}", format)))
+            .unwrap_or_else(|| "unknown".to_string());
+        IOError::Dds(format!("the format {} is not supported", name))
+    })?;
+
+    let width = dds.get_width();
+    let height = dds.get_height();
+    let level_count = dds.get_num_mipmap_levels().max(1) as usize;
+    let mut levels = vec![Vec::new(); level_count];
+    let mut offset = 0;
+    for _ in 0..face_count {
+        for (level, faces) in levels.iter_mut().enumerate() {
+            let w = (width >> level).max(1);
+            let h = (height >> level).max(1);
+            let size = match layout {
+                Layout::Direct(Format::Compressed(format)) => format.data_size(w, h),
+                Layout::Direct(format) => (w * h * format.color_channel_count()) as usize,
+                Layout::Swizzled(_, bytes_per_pixel, _) => {
+                    w as usize * h as usize * bytes_per_pixel
+                }
+            };
+            let data = dds
+                .data
+                .get(offset..offset + size)
+                .ok_or_else(|| IOError::Dds(format!("mip level {} is too short", level)))?;
+            offset += size;
+            faces.push(match layout {
+                Layout::Direct(_) => data.to_vec(),
+                Layout::Swizzled(_, bytes_per_pixel, channels) => data
+                    .chunks(bytes_per_pixel)
+                    .flat_map(|pixel| channels.iter().map(move |c| pixel[*c]))
+                    .collect(),
+            });
+        }
+    }
+    let format = match layout {
+        Layout::Direct(format) | Layout::Swizzled(format, _, _) => format,
+    };
+    Ok((format, width, height, levels))
+}
+
+fn layout(dds: &Dds) -> Option<Layout> {
+    // BC1 only has a 1-bit alpha channel if the file says so, otherwise the block mode which encodes alpha is treated as black
+    let has_alpha = dds
+        .header
+        .spf
+        .flags
+        .contains(PixelFormatFlags::ALPHA_PIXELS)
+        || dds.header10.as_ref().is_some_and(|h| {
+            matches!(h.alpha_mode, AlphaMode::Straight | AlphaMode::PreMultiplied)
+        });
+    let bc1 = if has_alpha {
+        CompressedFormat::Bc1Rgba
+    } else {
+        CompressedFormat::Bc1Rgb
+    };
+    if let Some(format) = dds.get_dxgi_format() {
+        Some(match format {
+            DxgiFormat::R8_UNorm => Layout::Direct(Format::R),
+            DxgiFormat::R8G8_UNorm => Layout::Direct(Format::RG),
+            DxgiFormat::R8G8B8A8_UNorm | DxgiFormat::R8G8B8A8_UNorm_sRGB => {
+                Layout::Direct(Format::RGBA)
+            }
+            DxgiFormat::B8G8R8A8_UNorm | DxgiFormat::B8G8R8A8_UNorm_sRGB => {
+                Layout::Swizzled(Format::RGBA, 4, &[2, 1, 0, 3])
+            }
+            DxgiFormat::B8G8R8X8_UNorm | DxgiFormat::B8G8R8X8_UNorm_sRGB => {
+                Layout::Swizzled(Format::RGB, 4, &[2, 1, 0])
+            }
+            _ => Layout::Direct(Format::Compressed(match format {
+                DxgiFormat::BC1_UNorm | DxgiFormat::BC1_UNorm_sRGB => bc1,
+                DxgiFormat::BC2_UNorm | DxgiFormat::BC2_UNorm_sRGB => CompressedFormat::Bc2,
+                DxgiFormat::BC3_UNorm | DxgiFormat::BC3_UNorm_sRGB => CompressedFormat::Bc3,
+                DxgiFormat::BC4_UNorm => CompressedFormat::Bc4,
+                DxgiFormat::BC5_UNorm => CompressedFormat::Bc5,
+                DxgiFormat::BC6H_UF16 => CompressedFormat::Bc6h,
+                DxgiFormat::BC7_UNorm | DxgiFormat::BC7_UNorm_sRGB => CompressedFormat::Bc7,
+                _ => return None,
+            })),
+        })
+    } else {
+        Some(match dds.get_d3d_format()? {
+            D3DFormat::L8 => Layout::Direct(Format::R),
+            D3DFormat::A8L8 => Layout::Direct(Format::RG),
+            D3DFormat::A8B8G8R8 => Layout::Direct(Format::RGBA),
+            D3DFormat::X8B8G8R8 => Layout::Swizzled(Format::RGB, 4, &[0, 1, 2]),
+            D3DFormat::A8R8G8B8 => Layout::Swizzled(Format::RGBA, 4, &[2, 1, 0, 3]),
+            D3DFormat::X8R8G8B8 => Layout::Swizzled(Format::RGB, 4, &[2, 1, 0]),
+            D3DFormat::R8G8B8 => Layout::Swizzled(Format::RGB, 3, &[2, 1, 0]),
+            D3DFormat::DXT1 => Layout::Direct(Format::Compressed(bc1)),
+            D3DFormat::DXT3 => Layout::Direct(Format::Compressed(CompressedFormat::Bc2)),
+            D3DFormat::DXT5 => Layout::Direct(Format::Compressed(CompressedFormat::Bc3)),
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use crate::context::DataType;
+use crate::core::*;
+use crate::io::*;
+use ::exr::prelude::{AnyChannel, AnyChannels, FlatSamples, Image, WritableImage};
+use std::path::Path;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given texture as an OpenEXR image, which stores the data losslessly.
+    /// `f16` data is stored as 16-bit floating point values, `u32` data as 32-bit unsigned integers and all other data types as 32-bit floating point values.
+    /// `u8` and `u16` data is mapped to the range [0..1] like for .hdr images saved using [Saver::save_texture].
+    /// A texture with a single channel is stored in the Y channel, otherwise the channels are stored in the R, G, B and A channels.
+    /// The first row of the texture data is the top row of the image.
+    ///
+    pub fn save_exr<T: TextureDataType>(
+        path: impl AsRef<Path>,
+        texture: &CpuTexture<T>,
+    ) -> ThreeDResult<()> {
+        if let Format::Compressed(format) = texture.format {
+            Err(IOError::CompressedImage(format))?;
+        }
+        let channel_count = texture.format.color_channel_count() as usize;
+        let names: &[&str] = match channel_count {
+            1 => &["Y"],
+            2 => &["R", "G"],
+            3 => &["R", "G", "B"],
+            _ => &["R", "G", "B", "A"],
+        };
+        let scale = match T::data_type() {
+            DataType::UnsignedByte => 1.0 / u8::MAX as f32,
+            DataType::UnsignedShort => 1.0 / u16::MAX as f32,
+            _ => 1.0,
+        };
+        let channels = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values = texture.data.iter().skip(i).step_by(channel_count).copied();
+                let samples = match T::data_type() {
+                    DataType::HalfFloat => FlatSamples::F16(
+                        values
+                            .map(|v| ::exr::prelude::f16::from_f32(T::to_f32(v)))
+                            .collect(),
+                    ),
+                    DataType::UnsignedInt => FlatSamples::U32(values.map(T::to_u32).collect()),
+                    _ => FlatSamples::F32(values.map(|v| T::to_f32(v) * scale).collect()),
+                };
+                AnyChannel::new(*name, samples)
+            })
+            .collect();
+        Image::from_channels(
+            (texture.width as usize, texture.height as usize),
+            AnyChannels::sort(channels),
+        )
+        .write()
+        .to_file(path)?;
+        Ok(())
+    }
+}
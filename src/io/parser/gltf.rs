@@ -1,26 +1,290 @@
 use crate::core::*;
 use crate::io::*;
-use ::gltf::Gltf;
+use ::gltf::{Gltf, Semantic};
 use std::path::Path;
 
+///
+/// The content of a glTF file, see [Loaded::gltf_scene].
+///
+#[derive(Debug, Default)]
+pub struct GltfScene {
+    /// The meshes. Meshes without a skin are transformed into the coordinate system of the scene.
+    pub meshes: Vec<CpuMesh>,
+    /// The materials, use [CpuMesh::material] to find the material for a mesh.
+    pub materials: Vec<CpuMaterial>,
+    /// The skins, use [CpuMesh::skin] to find the skin for a skinned mesh.
+    pub skins: Vec<CpuSkin>,
+    /// The animations. The targets of the animations are [AnimationTarget::Node] with the index of the node in [GltfScene::nodes],
+    /// which is also the [CpuJoint::node_index] of the joints in the [GltfScene::skins].
+    pub animations: Vec<AnimationClip>,
+    /// The nodes in the glTF node hierarchy in the order they are defined in the file.
+    pub nodes: Vec<GltfNode>,
+    /// The punctual lights defined by the `KHR_lights_punctual` extension, transformed into the coordinate system of the scene.
+    pub lights: Vec<GltfLight>,
+    /// The cameras, transformed into the coordinate system of the scene.
+    pub cameras: Vec<GltfCamera>,
+}
+
+///
+/// A node in the node hierarchy of a glTF file, see [GltfScene::nodes].
+///
+#[derive(Debug, Clone)]
+pub struct GltfNode {
+    /// Name. If the node is not named in the file, the name is `index {i}` where `i` is the index of the node in [GltfScene::nodes].
+    /// The names are not necessarily unique, so animations identify the node by its index instead, see [AnimationTarget::Node].
+    pub name: String,
+    /// The index of the parent node in [GltfScene::nodes] or `None` if this is a root node.
+    pub parent: Option<usize>,
+    /// The transformation of the node relative to its parent node.
+    pub transformation: Mat4,
+    /// The indices of the meshes in [GltfScene::meshes] which belongs to this node.
+    pub meshes: Vec<usize>,
+}
+
+///
+/// A punctual light in a glTF file, see [GltfScene::lights].
+///
+#[derive(Debug, Clone)]
+pub struct GltfLight {
+    /// Name. If the light is not named in the file, the name of the node it belongs to is used.
+    pub name: String,
+    /// The index of the node in [GltfScene::nodes] which the light belongs to.
+    pub node: usize,
+    /// The intensity of the light, in candela for point and spot lights and in lux for directional lights.
+    pub intensity: f32,
+    /// The color of the light.
+    pub color: Color,
+    /// The type of light and its type specific parameters.
+    pub kind: GltfLightKind,
+}
+
+///
+/// The type of a [GltfLight] and its type specific parameters.
+/// The parameters correspond to the parameters of a [DirectionalLight](crate::DirectionalLight), [PointLight](crate::PointLight) or [SpotLight](crate::SpotLight),
+/// which can be created using for example [GltfScene::point_lights].
+///
+#[derive(Debug, Clone, Copy)]
+pub enum GltfLightKind {
+    /// A light infinitely far away, see [DirectionalLight](crate::DirectionalLight).
+    Directional {
+        /// The direction the light shines.
+        direction: Vec3,
+    },
+    /// A light which shines equally in all directions from a position, see [PointLight](crate::PointLight).
+    Point {
+        /// The position of the light.
+        position: Vec3,
+        /// The distance at which the intensity of the light is considered to be zero or `None` if the light has an infinite range.
+        range: Option<f32>,
+    },
+    /// A light which shines in a cone from a position, see [SpotLight](crate::SpotLight).
+    Spot {
+        /// The position of the light.
+        position: Vec3,
+        /// The direction the light shines.
+        direction: Vec3,
+        /// The cutoff angle, which is the outer cone angle in the file.
+        cutoff: Radians,
+        /// The distance at which the intensity of the light is considered to be zero or `None` if the light has an infinite range.
+        range: Option<f32>,
+    },
+}
+
+///
+/// A camera in a glTF file, see [GltfScene::cameras].
+///
+#[derive(Debug, Clone)]
+pub struct GltfCamera {
+    /// Name. If the camera is not named in the file, the name of the node it belongs to is used.
+    pub name: String,
+    /// The index of the node in [GltfScene::nodes] which the camera belongs to.
+    pub node: usize,
+    /// The transformation of the camera node into the coordinate system of the scene.
+    /// The camera looks along the negative z-axis of the node with the positive y-axis as up.
+    pub transformation: Mat4,
+    /// The projection of the camera.
+    pub projection: GltfProjection,
+}
+
+///
+/// The projection of a [GltfCamera].
+///
+#[derive(Debug, Clone, Copy)]
+pub enum GltfProjection {
+    /// A perspective projection.
+    Perspective {
+        /// The vertical field of view.
+        field_of_view_y: Radians,
+        /// The aspect ratio of the field of view, if specified.
+        aspect_ratio: Option<f32>,
+        /// The distance to the near plane.
+        z_near: f32,
+        /// The distance to the far plane or `None` if the projection is infinite.
+        z_far: Option<f32>,
+    },
+    /// An orthographic projection.
+    Orthographic {
+        /// The half width of the view.
+        x_magnification: f32,
+        /// The half height of the view.
+        y_magnification: f32,
+        /// The distance to the near plane.
+        z_near: f32,
+        /// The distance to the far plane.
+        z_far: f32,
+    },
+}
+
+impl GltfCamera {
+    ///
+    /// Returns the position, target and up direction of the camera, see [Camera::set_view].
+    ///
+    pub fn view(&self) -> (Vec3, Vec3, Vec3) {
+        let position = (self.transformation * vec4(0.0, 0.0, 0.0, 1.0)).truncate();
+        let direction = (self.transformation * vec4(0.0, 0.0, -1.0, 0.0)).truncate();
+        let up = (self.transformation * vec4(0.0, 1.0, 0.0, 0.0)).truncate();
+        (position, position + direction.normalize(), up.normalize())
+    }
+
+    ///
+    /// Creates a [Camera] with the view and projection of this camera.
+    /// The aspect ratio is given by the viewport and an infinite perspective projection is given a far plane 100000 times further away than the near plane.
+    ///
+    pub fn camera(&self, context: &Context, viewport: Viewport) -> ThreeDResult<Camera> {
+        let (position, target, up) = self.view();
+        match self.projection {
+            GltfProjection::Perspective {
+                field_of_view_y,
+                z_near,
+                z_far,
+                ..
+            } => Camera::new_perspective(
+                context,
+                viewport,
+                position,
+                target,
+                up,
+                field_of_view_y,
+                z_near,
+                z_far.unwrap_or(z_near * 100000.0),
+            ),
+            GltfProjection::Orthographic {
+                y_magnification,
+                z_near,
+                z_far,
+                ..
+            } => Camera::new_orthographic(
+                context,
+                viewport,
+                position,
+                target,
+                up,
+                2.0 * y_magnification,
+                z_near,
+                z_far,
+            ),
+        }
+    }
+}
+
+impl GltfScene {
+    ///
+    /// Returns the transformation of each mesh in [GltfScene::meshes] when the nodes are animated by the given clip at the given time in seconds.
+    /// Since the meshes are already transformed into the coordinate system of the scene, the transformation is relative to the pose defined in the file,
+    /// so the result can be applied directly with for example [Mesh::set_transformation](crate::Mesh::set_transformation).
+    /// The transformation of a skinned mesh is always the identity, use [CpuSkin::animate] to animate it instead.
+    ///
+    pub fn mesh_transformations(&self, clip: &AnimationClip, time: f32) -> Vec<Mat4> {
+        let rest = self.world_transformations(|_, node| node.transformation);
+        let animated = self.world_transformations(|index, node| {
+            clip.transformation(&AnimationTarget::Node(index), time)
+                .unwrap_or(node.transformation)
+        });
+        let mut transformations = vec![Mat4::identity(); self.meshes.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            let transformation = animated[index] * rest[index].invert().unwrap_or(Mat4::identity());
+            for mesh_index in node.meshes.iter() {
+                if self.meshes[*mesh_index].skin_name.is_none() {
+                    transformations[*mesh_index] = transformation;
+                }
+            }
+        }
+        transformations
+    }
+
+    fn world_transformations(&self, local: impl Fn(usize, &GltfNode) -> Mat4) -> Vec<Mat4> {
+        let mut world: Vec<Option<Mat4>> = vec![None; self.nodes.len()];
+        for index in 0..self.nodes.len() {
+            // Walk up to the closest ancestor with a known world transformation
+            let mut path = vec![index];
+            while let Some(parent) = self.nodes[*path.last().unwrap()].parent {
+                if world[parent].is_some() || path.len() > self.nodes.len() {
+                    break;
+                }
+                path.push(parent);
+            }
+            for i in path.into_iter().rev() {
+                if world[i].is_none() {
+                    let parent = self.nodes[i]
+                        .parent
+                        .and_then(|parent| world[parent])
+                        .unwrap_or(Mat4::identity());
+                    world[i] = Some(parent * local(i, &self.nodes[i]));
+                }
+            }
+        }
+        world.into_iter().map(|t| t.unwrap()).collect()
+    }
+}
+
 impl Loaded {
     ///
     /// Deserialize a loaded .gltf file and related .bin resource file and related texture resources or a loaded .glb file into a list of meshes and materials.
+    /// The transformations of the nodes in the glTF node hierarchy are applied to the positions, normals and tangents of the meshes,
+    /// so the meshes are returned in the coordinate system of the scene.
     /// It uses the [gltf](https://crates.io/crates/gltf/main.rs) crate.
+    /// Use [Loaded::gltf_scene] to also get the skins, the animations, the node hierarchy, the lights and the cameras of the file.
     ///
     pub fn gltf(
         &mut self,
         path: impl AsRef<Path>,
     ) -> ThreeDResult<(Vec<CpuMesh>, Vec<CpuMaterial>)> {
-        let mut cpu_meshes = Vec::new();
-        let mut cpu_materials = Vec::new();
+        let scene = self.gltf_scene(path)?;
+        Ok((scene.meshes, scene.materials))
+    }
+
+    ///
+    /// Deserialize a loaded .gltf file and related .bin resource file and related texture resources or a loaded .glb file into a [GltfScene].
+    /// Similar to [Loaded::gltf] except that the result also contains the skins, the animations, the node hierarchy, the lights and the cameras.
+    /// The skinned meshes are not transformed by the glTF node hierarchy, instead they are deformed by the joints of the skin, see [SkinnedMesh](crate::SkinnedMesh).
+    /// Meshes with quantized attributes (`KHR_mesh_quantization`) and compressed buffers (`EXT_meshopt_compression`) are decoded into floating point attributes,
+    /// which means that the memory savings of the quantization do not carry over to the GPU.
+    ///
+    pub fn gltf_scene(&mut self, path: impl AsRef<Path>) -> ThreeDResult<GltfScene> {
+        let mut scene = GltfScene::default();
 
-        let Gltf { document, mut blob } = Gltf::from_slice(self.get_bytes(path.as_ref())?)?;
+        let bytes = self.get_bytes(path.as_ref())?;
+        // The gltf crate does not support all of the material extensions, so those are parsed from the json
+        let json: ::gltf::json::Value = if bytes.starts_with(b"glTF") {
+            ::gltf::json::deserialize::from_slice(&::gltf::Glb::from_slice(bytes)?.json)?
+        } else {
+            ::gltf::json::deserialize::from_slice(bytes)?
+        };
+        let Gltf { document, mut blob } = Gltf::from_slice(bytes)?;
         let base_path = path.as_ref().parent().unwrap();
         let mut buffers = Vec::new();
         for buffer in document.buffers() {
+            // The fallback buffer of EXT_meshopt_compression is filled by decoding the compressed buffer views below
+            if json["buffers"][buffer.index()]["extensions"]["EXT_meshopt_compression"]["fallback"]
+                == true
+            {
+                buffers.push(::gltf::buffer::Data(vec![0; buffer.length()]));
+                continue;
+            }
             let mut data = match buffer.source() {
-                ::gltf::buffer::Source::Uri(uri) => self.remove_bytes(base_path.join(uri))?,
+                ::gltf::buffer::Source::Uri(uri) => {
+                    self.remove_bytes(base_path.join(decode_uri(uri)))?
+                }
                 ::gltf::buffer::Source::Bin => blob.take().ok_or(IOError::GltfMissingData)?,
             };
             if data.len() < buffer.length() {
@@ -31,31 +295,181 @@ impl Loaded {
             }
             buffers.push(::gltf::buffer::Data(data));
         }
+        for view in document.views() {
+            let extension =
+                &json["bufferViews"][view.index()]["extensions"]["EXT_meshopt_compression"];
+            if extension.is_object() {
+                let number = |key: &str| {
+                    extension[key]
+                        .as_u64()
+                        .map(|v| v as usize)
+                        .ok_or(IOError::GltfCorruptData)
+                };
+                let offset = extension["byteOffset"].as_u64().unwrap_or(0) as usize;
+                let length = number("byteLength")?;
+                let count = number("count")?;
+                let stride = number("byteStride")?;
+                // The decoded data must fill the target view exactly and the view must be inside the target buffer,
+                // which bounds the size of the decoded data before it is allocated
+                let view_end = view.offset().checked_add(view.length());
+                if count.checked_mul(stride) != Some(view.length())
+                    || view_end.is_none_or(|end| end > buffers[view.buffer().index()].len())
+                {
+                    Err(IOError::GltfCorruptData)?;
+                }
+                let buffer = buffers
+                    .get(number("buffer")?)
+                    .ok_or(IOError::GltfCorruptData)?;
+                let data = offset
+                    .checked_add(length)
+                    .and_then(|end| buffer.get(offset..end))
+                    .ok_or(IOError::GltfCorruptData)?;
+                let decoded = super::meshopt::decode(
+                    data,
+                    count,
+                    stride,
+                    extension["mode"].as_str().unwrap_or_default(),
+                    extension["filter"].as_str().unwrap_or("NONE"),
+                )?;
+                buffers[view.buffer().index()].0[view.offset()..view.offset() + view.length()]
+                    .copy_from_slice(&decoded);
+            }
+        }
 
-        for scene in document.scenes() {
-            for node in scene.nodes() {
+        let mut parents = vec![None; document.nodes().len()];
+        for node in document.nodes() {
+            for child in node.children() {
+                parents[child.index()] = Some(node.index());
+            }
+        }
+        scene.nodes = document
+            .nodes()
+            .map(|node| GltfNode {
+                name: node_name(&node),
+                parent: parents[node.index()],
+                transformation: node.transform().matrix().into(),
+                meshes: Vec::new(),
+            })
+            .collect();
+
+        let mut material_extensions = Vec::new();
+        for material in document.materials() {
+            material_extensions.push(parse_material_extensions(
+                &material,
+                &json["materials"][material.index().unwrap()],
+                &document,
+                self,
+                base_path,
+                &buffers,
+            )?);
+        }
+
+        for gltf_scene in document.scenes() {
+            for node in gltf_scene.nodes() {
                 parse_tree(
                     &node,
+                    &Mat4::identity(),
                     self,
                     &base_path,
                     &buffers,
-                    &mut cpu_meshes,
-                    &mut cpu_materials,
+                    &mut material_extensions,
+                    &mut scene,
                 )?;
             }
         }
-        Ok((cpu_meshes, cpu_materials))
+
+        let world_transformations = scene.world_transformations(|_, node| node.transformation);
+        for node in document.nodes() {
+            let transformation = world_transformations[node.index()];
+            let position = (transformation * vec4(0.0, 0.0, 0.0, 1.0)).truncate();
+            let direction = (transformation * vec4(0.0, 0.0, -1.0, 0.0))
+                .truncate()
+                .normalize();
+            if let Some(light) = node.light() {
+                let kind = match light.kind() {
+                    ::gltf::khr_lights_punctual::Kind::Directional => {
+                        GltfLightKind::Directional { direction }
+                    }
+                    ::gltf::khr_lights_punctual::Kind::Point => GltfLightKind::Point {
+                        position,
+                        range: light.range(),
+                    },
+                    ::gltf::khr_lights_punctual::Kind::Spot {
+                        outer_cone_angle, ..
+                    } => GltfLightKind::Spot {
+                        position,
+                        direction,
+                        cutoff: radians(outer_cone_angle),
+                        range: light.range(),
+                    },
+                };
+                scene.lights.push(GltfLight {
+                    name: light
+                        .name()
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| node_name(&node)),
+                    node: node.index(),
+                    intensity: light.intensity(),
+                    color: Color::from_rgb_slice(&light.color()),
+                    kind,
+                });
+            }
+            if let Some(camera) = node.camera() {
+                let projection = match camera.projection() {
+                    ::gltf::camera::Projection::Perspective(perspective) => {
+                        GltfProjection::Perspective {
+                            field_of_view_y: radians(perspective.yfov()),
+                            aspect_ratio: perspective.aspect_ratio(),
+                            z_near: perspective.znear(),
+                            z_far: perspective.zfar(),
+                        }
+                    }
+                    ::gltf::camera::Projection::Orthographic(orthographic) => {
+                        GltfProjection::Orthographic {
+                            x_magnification: orthographic.xmag(),
+                            y_magnification: orthographic.ymag(),
+                            z_near: orthographic.znear(),
+                            z_far: orthographic.zfar(),
+                        }
+                    }
+                };
+                scene.cameras.push(GltfCamera {
+                    name: camera
+                        .name()
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| node_name(&node)),
+                    node: node.index(),
+                    transformation,
+                    projection,
+                });
+            }
+        }
+
+        for skin in document.skins() {
+            scene
+                .skins
+                .push(parse_skin(&skin, &parents, &document, &buffers));
+        }
+        for animation in document.animations() {
+            scene
+                .animations
+                .push(parse_animation(&animation, &buffers)?);
+        }
+        Ok(scene)
     }
 }
 
 fn parse_tree<'a>(
     node: &::gltf::Node,
+    parent_transform: &Mat4,
     loaded: &mut Loaded,
     path: &Path,
     buffers: &[::gltf::buffer::Data],
-    cpu_meshes: &mut Vec<CpuMesh>,
-    cpu_materials: &mut Vec<CpuMaterial>,
+    material_extensions: &mut [CpuMaterial],
+    scene: &mut GltfScene,
 ) -> ThreeDResult<()> {
+    let node_transform: Mat4 = node.transform().matrix().into();
+    let transform = parent_transform * node_transform;
     if let Some(mesh) = node.mesh() {
         let name: String = mesh
             .name()
@@ -63,27 +477,28 @@ fn parse_tree<'a>(
             .unwrap_or(format!("index {}", mesh.index()));
         for primitive in mesh.primitives() {
             let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
-            if let Some(read_positions) = reader.read_positions() {
-                let mut positions = Vec::new();
-                for value in read_positions {
-                    positions.push(vec3(value[0], value[1], value[2]));
-                }
+            if let Some(accessor) = primitive.get(&Semantic::Positions) {
+                let positions = read_floats(&accessor, buffers)?
+                    .into_iter()
+                    .map(|[x, y, z]| vec3(x, y, z))
+                    .collect();
 
-                let normals = reader.read_normals().map(|values| {
-                    let mut normals = Vec::new();
-                    for n in values {
-                        normals.push(vec3(n[0], n[1], n[2]));
-                    }
-                    normals
-                });
+                let normals = primitive
+                    .get(&Semantic::Normals)
+                    .map(|accessor| read_floats(&accessor, buffers))
+                    .transpose()?
+                    .map(|values| values.into_iter().map(|[x, y, z]| vec3(x, y, z)).collect());
 
-                let tangents = reader.read_tangents().map(|values| {
-                    let mut tangents = Vec::new();
-                    for t in values {
-                        tangents.push(vec4(t[0], t[1], t[2], t[3]));
-                    }
-                    tangents
-                });
+                let tangents = primitive
+                    .get(&Semantic::Tangents)
+                    .map(|accessor| read_floats(&accessor, buffers))
+                    .transpose()?
+                    .map(|values| {
+                        values
+                            .into_iter()
+                            .map(|[x, y, z, w]| vec4(x, y, z, w))
+                            .collect()
+                    });
 
                 let indices = reader.read_indices().map(|values| match values {
                     ::gltf::mesh::util::ReadIndices::U8(iter) => {
@@ -117,7 +532,7 @@ fn parse_tree<'a>(
                         .unwrap_or("default".to_string()),
                 );
                 let mut parsed = false;
-                for material in cpu_materials.iter() {
+                for material in scene.materials.iter() {
                     if material.name == material_name {
                         parsed = true;
                         break;
@@ -127,21 +542,30 @@ fn parse_tree<'a>(
                 if !parsed {
                     let pbr = material.pbr_metallic_roughness();
                     let color = pbr.base_color_factor();
-                    let albedo_texture = if let Some(info) = pbr.base_color_texture() {
-                        Some(parse_texture(loaded, path, buffers, info.texture())?)
-                    } else {
-                        None
-                    };
-                    let metallic_roughness_texture =
+                    let (albedo_texture, albedo_texture_transform) =
+                        if let Some(info) = pbr.base_color_texture() {
+                            (
+                                parse_texture(loaded, path, buffers, info.texture())?,
+                                texture_transform(&info),
+                            )
+                        } else {
+                            (None, TextureTransform::default())
+                        };
+                    let (metallic_roughness_texture, metallic_roughness_texture_transform) =
                         if let Some(info) = pbr.metallic_roughness_texture() {
-                            Some(parse_texture(loaded, path, buffers, info.texture())?)
+                            (
+                                parse_texture(loaded, path, buffers, info.texture())?,
+                                texture_transform(&info),
+                            )
                         } else {
-                            None
+                            (None, TextureTransform::default())
                         };
+                    // The KHR_texture_transform extension is not available for normal and occlusion textures in the gltf crate,
+                    // so the transforms of those are parsed from the json together with the material extensions
                     let (normal_texture, normal_scale) =
                         if let Some(normal) = material.normal_texture() {
                             (
-                                Some(parse_texture(loaded, path, buffers, normal.texture())?),
+                                parse_texture(loaded, path, buffers, normal.texture())?,
                                 normal.scale(),
                             )
                         } else {
@@ -150,24 +574,30 @@ fn parse_tree<'a>(
                     let (occlusion_texture, occlusion_strength) =
                         if let Some(occlusion) = material.occlusion_texture() {
                             (
-                                Some(parse_texture(loaded, path, buffers, occlusion.texture())?),
+                                parse_texture(loaded, path, buffers, occlusion.texture())?,
                                 occlusion.strength(),
                             )
                         } else {
                             (None, 1.0)
                         };
-                    let emissive_texture = if let Some(info) = material.emissive_texture() {
-                        Some(parse_texture(loaded, path, buffers, info.texture())?)
-                    } else {
-                        None
-                    };
-                    cpu_materials.push(CpuMaterial {
+                    let (emissive_texture, emissive_texture_transform) =
+                        if let Some(info) = material.emissive_texture() {
+                            (
+                                parse_texture(loaded, path, buffers, info.texture())?,
+                                texture_transform(&info),
+                            )
+                        } else {
+                            (None, TextureTransform::default())
+                        };
+                    scene.materials.push(CpuMaterial {
                         name: material_name.clone(),
                         albedo: Color::from_rgba_slice(&color),
                         albedo_texture,
+                        albedo_texture_transform,
                         metallic: pbr.metallic_factor(),
                         roughness: pbr.roughness_factor(),
                         metallic_roughness_texture,
+                        metallic_roughness_texture_transform,
                         normal_texture,
                         normal_scale,
                         occlusion_texture,
@@ -175,11 +605,29 @@ fn parse_tree<'a>(
                         occlusion_metallic_roughness_texture: None,
                         emissive: Color::from_rgb_slice(&material.emissive_factor()),
                         emissive_texture,
-                        alpha_cutout: None,
+                        emissive_texture_transform,
+                        alpha_cutout: if material.alpha_mode() == ::gltf::material::AlphaMode::Mask
+                        {
+                            Some(material.alpha_cutoff().unwrap_or(0.5))
+                        } else {
+                            None
+                        },
+                        is_transparent: Some(
+                            material.alpha_mode() == ::gltf::material::AlphaMode::Blend,
+                        ),
+                        cull: if material.double_sided() {
+                            Cull::None
+                        } else {
+                            Cull::Back
+                        },
                         lighting_model: LightingModel::Cook(
                             NormalDistributionFunction::TrowbridgeReitzGGX,
                             GeometryFunction::SmithSchlickGGX,
                         ),
+                        ..material
+                            .index()
+                            .map(|i| std::mem::take(&mut material_extensions[i]))
+                            .unwrap_or_default()
                     });
                 }
 
@@ -191,15 +639,56 @@ fn parse_tree<'a>(
                     cols
                 });
 
-                let uvs = reader.read_tex_coords(0).map(|values| {
-                    let mut uvs = Vec::new();
-                    for value in values.into_f32() {
-                        uvs.push(vec2(value[0], value[1]));
-                    }
-                    uvs
+                let uvs = primitive
+                    .get(&Semantic::TexCoords(0))
+                    .map(|accessor| read_floats(&accessor, buffers))
+                    .transpose()?
+                    .map(|values| values.into_iter().map(|[u, v]| vec2(u, v)).collect());
+                let secondary_uvs = primitive
+                    .get(&Semantic::TexCoords(1))
+                    .map(|accessor| read_floats(&accessor, buffers))
+                    .transpose()?
+                    .map(|values| values.into_iter().map(|[u, v]| vec2(u, v)).collect());
+
+                let joint_indices = reader.read_joints(0).map(|values| {
+                    values
+                        .into_u16()
+                        .map(|j| Vector4::new(j[0], j[1], j[2], j[3]))
+                        .collect::<Vec<_>>()
+                });
+
+                let joint_weights = reader.read_weights(0).map(|values| {
+                    values
+                        .into_f32()
+                        .map(|w| vec4(w[0], w[1], w[2], w[3]))
+                        .collect::<Vec<_>>()
                 });
 
-                cpu_meshes.push(CpuMesh {
+                let skin_name = node.skin().map(|skin| skin_name(&skin));
+
+                let deltas = |accessor: Option<::gltf::Accessor>| {
+                    accessor
+                        .map(|accessor| read_floats(&accessor, buffers))
+                        .transpose()
+                        .map(|values| {
+                            values.map(|values| values.into_iter().map(|v| v.into()).collect())
+                        })
+                };
+                let mut morph_targets = Vec::new();
+                for target in primitive.morph_targets() {
+                    morph_targets.push(CpuMorphTarget {
+                        position_deltas: deltas(target.positions())?,
+                        normal_deltas: deltas(target.normals())?,
+                        tangent_deltas: deltas(target.tangents())?,
+                    });
+                }
+                let morph_weights = node
+                    .weights()
+                    .or_else(|| mesh.weights())
+                    .map(|weights| weights.to_vec())
+                    .unwrap_or_default();
+
+                let mut cpu_mesh = CpuMesh {
                     name: name.clone(),
                     positions: Positions::F32(positions),
                     normals,
@@ -207,28 +696,433 @@ fn parse_tree<'a>(
                     indices,
                     colors,
                     uvs,
+                    secondary_uvs,
                     material_name: Some(material_name),
-                });
+                    joint_indices,
+                    joint_weights,
+                    skin_name,
+                    morph_targets,
+                    morph_weights,
+                };
+                // The transformation of a skinned mesh is defined by the joints and not by the node hierarchy
+                if cpu_mesh.skin_name.is_none() && transform != Mat4::identity() {
+                    cpu_mesh.transform(&transform);
+                }
+                scene.nodes[node.index()].meshes.push(scene.meshes.len());
+                scene.meshes.push(cpu_mesh);
             }
         }
     }
 
     for child in node.children() {
-        parse_tree(&child, loaded, path, buffers, cpu_meshes, cpu_materials)?;
+        parse_tree(
+            &child,
+            &transform,
+            loaded,
+            path,
+            buffers,
+            material_extensions,
+            scene,
+        )?;
     }
     Ok(())
 }
 
+///
+/// Returns a material with only the parameters of the `KHR_materials_emissive_strength`, `KHR_materials_clearcoat`, `KHR_materials_sheen`,
+/// `KHR_materials_transmission` and `KHR_materials_ior` extensions and the texture transforms of the normal and occlusion textures set,
+/// the remaining parameters have their default values.
+///
+fn parse_material_extensions(
+    material: &::gltf::Material,
+    json: &::gltf::json::Value,
+    document: &::gltf::Document,
+    loaded: &mut Loaded,
+    path: &Path,
+    buffers: &[::gltf::buffer::Data],
+) -> ThreeDResult<CpuMaterial> {
+    let mut texture = |info: &::gltf::json::Value| -> ThreeDResult<_> {
+        Ok(
+            match info["index"]
+                .as_u64()
+                .and_then(|i| document.textures().nth(i as usize))
+            {
+                Some(texture) => (
+                    parse_texture(loaded, path, buffers, texture)?,
+                    json_texture_transform(info),
+                ),
+                None => (None, TextureTransform::default()),
+            },
+        )
+    };
+    let factor = |value: &::gltf::json::Value, default: f32| {
+        value.as_f64().map(|v| v as f32).unwrap_or(default)
+    };
+    let extensions = &json["extensions"];
+    let clearcoat = &extensions["KHR_materials_clearcoat"];
+    let sheen = &extensions["KHR_materials_sheen"];
+    let sheen_color = &sheen["sheenColorFactor"];
+    let (clearcoat_texture, clearcoat_texture_transform) = texture(&clearcoat["clearcoatTexture"])?;
+    let (clearcoat_roughness_texture, clearcoat_roughness_texture_transform) =
+        texture(&clearcoat["clearcoatRoughnessTexture"])?;
+    let (clearcoat_normal_texture, clearcoat_normal_texture_transform) =
+        texture(&clearcoat["clearcoatNormalTexture"])?;
+    let (sheen_color_texture, sheen_color_texture_transform) =
+        texture(&sheen["sheenColorTexture"])?;
+    let (sheen_roughness_texture, sheen_roughness_texture_transform) =
+        texture(&sheen["sheenRoughnessTexture"])?;
+    let mut cpu_material = CpuMaterial {
+        emissive_strength: factor(
+            &extensions["KHR_materials_emissive_strength"]["emissiveStrength"],
+            1.0,
+        ),
+        clearcoat: factor(&clearcoat["clearcoatFactor"], 0.0),
+        clearcoat_texture,
+        clearcoat_texture_transform,
+        clearcoat_roughness: factor(&clearcoat["clearcoatRoughnessFactor"], 0.0),
+        clearcoat_roughness_texture,
+        clearcoat_roughness_texture_transform,
+        clearcoat_normal_scale: factor(&clearcoat["clearcoatNormalTexture"]["scale"], 1.0),
+        clearcoat_normal_texture,
+        clearcoat_normal_texture_transform,
+        sheen_color: Color::from_rgb_slice(&[
+            factor(&sheen_color[0], 0.0),
+            factor(&sheen_color[1], 0.0),
+            factor(&sheen_color[2], 0.0),
+        ]),
+        sheen_color_texture,
+        sheen_color_texture_transform,
+        sheen_roughness: factor(&sheen["sheenRoughnessFactor"], 0.0),
+        sheen_roughness_texture,
+        sheen_roughness_texture_transform,
+        index_of_refraction: material.ior().unwrap_or(1.5),
+        normal_texture_transform: json_texture_transform(&json["normalTexture"]),
+        occlusion_texture_transform: json_texture_transform(&json["occlusionTexture"]),
+        ..Default::default()
+    };
+    if let Some(transmission) = material.transmission() {
+        cpu_material.transmission = transmission.transmission_factor();
+        if let Some(info) = transmission.transmission_texture() {
+            cpu_material.transmission_texture =
+                parse_texture(loaded, path, buffers, info.texture())?;
+            cpu_material.transmission_texture_transform = texture_transform(&info);
+        }
+    }
+    Ok(cpu_material)
+}
+
+///
+/// Returns the texture transform of a texture info given as JSON, which is used for textures where the gltf crate does not parse the transform.
+///
+fn json_texture_transform(info: &::gltf::json::Value) -> TextureTransform {
+    let mut transform = TextureTransform {
+        uv_set: info["texCoord"].as_u64().unwrap_or(0) as u32,
+        ..Default::default()
+    };
+    let texture_transform = &info["extensions"]["KHR_texture_transform"];
+    let vector = |value: &::gltf::json::Value, default: f32| {
+        vec2(
+            value[0].as_f64().map(|v| v as f32).unwrap_or(default),
+            value[1].as_f64().map(|v| v as f32).unwrap_or(default),
+        )
+    };
+    if texture_transform.is_object() {
+        transform.offset = vector(&texture_transform["offset"], 0.0);
+        transform.rotation = texture_transform["rotation"].as_f64().unwrap_or(0.0) as f32;
+        transform.scale = vector(&texture_transform["scale"], 1.0);
+        if let Some(uv_set) = texture_transform["texCoord"].as_u64() {
+            transform.uv_set = uv_set as u32;
+        }
+    }
+    transform
+}
+
+fn texture_transform(info: &::gltf::texture::Info) -> TextureTransform {
+    let mut transform = TextureTransform {
+        uv_set: info.tex_coord(),
+        ..Default::default()
+    };
+    if let Some(texture_transform) = info.texture_transform() {
+        transform.offset = texture_transform.offset().into();
+        transform.rotation = texture_transform.rotation();
+        transform.scale = texture_transform.scale().into();
+        if let Some(uv_set) = texture_transform.tex_coord() {
+            transform.uv_set = uv_set;
+        }
+    }
+    transform
+}
+
+///
+/// Reads the values of the given accessor as floats.
+/// Besides floats, the values can be stored as (normalized) integers as allowed by the `KHR_mesh_quantization` extension,
+/// in which case the values are converted to floats on the CPU, so the quantized data is not uploaded to the GPU as is.
+/// Returns [IOError::GltfCorruptData] if the number of components of the accessor is not `N`.
+///
+fn read_floats<const N: usize>(
+    accessor: &::gltf::Accessor,
+    buffers: &[::gltf::buffer::Data],
+) -> ThreeDResult<Vec<[f32; N]>> {
+    use ::gltf::accessor::DataType;
+    if accessor.dimensions().multiplicity() != N {
+        Err(IOError::GltfCorruptData)?;
+    }
+    let data_type = accessor.data_type();
+    let normalized = accessor.normalized();
+    let read = |view: &::gltf::buffer::View,
+                offset: usize,
+                values: &mut dyn Iterator<Item = &mut [f32; N]>|
+     -> ThreeDResult<()> {
+        let stride = view.stride().unwrap_or(accessor.size());
+        let data = buffers[view.buffer().index()]
+            .get(view.offset() + offset..view.offset() + view.length())
+            .ok_or(IOError::GltfCorruptData)?;
+        for (i, value) in values.enumerate() {
+            for (j, component) in value.iter_mut().enumerate() {
+                let start = i * stride + j * data_type.size();
+                let bytes = data
+                    .get(start..start + data_type.size())
+                    .ok_or(IOError::GltfCorruptData)?;
+                *component = match (data_type, normalized) {
+                    (DataType::F32, _) => f32::from_le_bytes(bytes.try_into().unwrap()),
+                    (DataType::I8, true) => (bytes[0] as i8 as f32 / 127.0).max(-1.0),
+                    (DataType::I8, false) => bytes[0] as i8 as f32,
+                    (DataType::U8, true) => bytes[0] as f32 / 255.0,
+                    (DataType::U8, false) => bytes[0] as f32,
+                    (DataType::I16, true) => {
+                        (i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32767.0).max(-1.0)
+                    }
+                    (DataType::I16, false) => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+                    (DataType::U16, true) => {
+                        u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0
+                    }
+                    (DataType::U16, false) => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+                    (DataType::U32, _) => u32::from_le_bytes(bytes.try_into().unwrap()) as f32,
+                };
+            }
+        }
+        Ok(())
+    };
+
+    let mut values = vec![[0.0; N]; accessor.count()];
+    if let Some(view) = accessor.view() {
+        read(&view, accessor.offset(), &mut values.iter_mut())?;
+    }
+    if let Some(sparse) = accessor.sparse() {
+        let indices = sparse.indices();
+        let size = indices.index_type().size();
+        let view = indices.view();
+        let mut sparse_values = vec![[0.0; N]; sparse.count() as usize];
+        read(
+            &sparse.values().view(),
+            sparse.values().offset() as usize,
+            &mut sparse_values.iter_mut(),
+        )?;
+        for (i, value) in sparse_values.into_iter().enumerate() {
+            let start = view.offset() + indices.offset() as usize + i * size;
+            let bytes = buffers[view.buffer().index()]
+                .get(start..start + size)
+                .ok_or(IOError::GltfCorruptData)?;
+            let index = bytes
+                .iter()
+                .rev()
+                .fold(0, |index, byte| (index << 8) | *byte as usize);
+            *values.get_mut(index).ok_or(IOError::GltfCorruptData)? = value;
+        }
+    }
+    Ok(values)
+}
+
+fn parse_skin(
+    skin: &::gltf::Skin,
+    parents: &[Option<usize>],
+    document: &::gltf::Document,
+    buffers: &[::gltf::buffer::Data],
+) -> CpuSkin {
+    let nodes = document.nodes().collect::<Vec<_>>();
+    let local_transformation = |index: usize| -> Mat4 { nodes[index].transform().matrix().into() };
+    let joint_nodes = skin.joints().map(|node| node.index()).collect::<Vec<_>>();
+    let inverse_bind_matrices = skin
+        .reader(|buffer| Some(&buffers[buffer.index()]))
+        .read_inverse_bind_matrices()
+        .map(|values| values.map(|m| m.into()).collect::<Vec<Mat4>>());
+
+    let mut joints = Vec::new();
+    for (index, node_index) in joint_nodes.iter().enumerate() {
+        // Find the closest ancestor which is also a joint and collect the transformations of the nodes in between
+        let mut parent_offset = Mat4::identity();
+        let mut parent = None;
+        let mut ancestor = parents[*node_index];
+        while let Some(ancestor_index) = ancestor {
+            if let Some(joint_index) = joint_nodes.iter().position(|n| *n == ancestor_index) {
+                parent = Some(joint_index);
+                break;
+            }
+            parent_offset = local_transformation(ancestor_index) * parent_offset;
+            ancestor = parents[ancestor_index];
+        }
+        let node = &nodes[*node_index];
+        joints.push(CpuJoint {
+            name: node_name(node),
+            node_index: Some(*node_index),
+            parent,
+            parent_offset,
+            transformation: local_transformation(*node_index),
+            inverse_bind_matrix: inverse_bind_matrices
+                .as_ref()
+                .and_then(|matrices| matrices.get(index).cloned())
+                .unwrap_or(Mat4::identity()),
+        });
+    }
+    CpuSkin {
+        name: skin_name(skin),
+        joints,
+    }
+}
+
+fn parse_animation(
+    animation: &::gltf::Animation,
+    buffers: &[::gltf::buffer::Data],
+) -> ThreeDResult<AnimationClip> {
+    use ::gltf::animation::util::ReadOutputs;
+    let mut channels: Vec<AnimationChannel> = Vec::new();
+    for channel in animation.channels() {
+        let node = channel.target().node();
+        let target = AnimationTarget::Node(node.index());
+        let index = if let Some(index) = channels.iter().position(|c| c.target == target) {
+            index
+        } else {
+            let (translation, rotation, scale) = node.transform().decomposed();
+            channels.push(AnimationChannel {
+                target,
+                rest: AnimationTransformation {
+                    translation: translation.into(),
+                    rotation: Quat::new(rotation[3], rotation[0], rotation[1], rotation[2]),
+                    scale: scale.into(),
+                },
+                ..Default::default()
+            });
+            channels.len() - 1
+        };
+
+        let interpolation = match channel.sampler().interpolation() {
+            ::gltf::animation::Interpolation::Step => KeyframeInterpolation::Step,
+            ::gltf::animation::Interpolation::Linear => KeyframeInterpolation::Linear,
+            ::gltf::animation::Interpolation::CubicSpline => KeyframeInterpolation::CubicSpline,
+        };
+        let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
+        let times = reader
+            .read_inputs()
+            .ok_or(IOError::GltfMissingData)?
+            .collect::<Vec<_>>();
+        match reader.read_outputs().ok_or(IOError::GltfMissingData)? {
+            ReadOutputs::Translations(values) => {
+                channels[index].translation = Some(KeyframeTrack {
+                    interpolation,
+                    times,
+                    values: values.map(|v| v.into()).collect(),
+                });
+            }
+            ReadOutputs::Rotations(values) => {
+                channels[index].rotation = Some(KeyframeTrack {
+                    interpolation,
+                    times,
+                    values: values
+                        .into_f32()
+                        .map(|r| Quat::new(r[3], r[0], r[1], r[2]))
+                        .collect(),
+                });
+            }
+            ReadOutputs::Scales(values) => {
+                channels[index].scale = Some(KeyframeTrack {
+                    interpolation,
+                    times,
+                    values: values.map(|v| v.into()).collect(),
+                });
+            }
+            ReadOutputs::MorphTargetWeights(values) => {
+                let values = values.into_f32().collect::<Vec<_>>();
+                let stride = if interpolation == KeyframeInterpolation::CubicSpline {
+                    3
+                } else {
+                    1
+                };
+                let weight_count = values.len() / (times.len() * stride).max(1);
+                channels[index].morph_weights = Some(KeyframeTrack {
+                    interpolation,
+                    times,
+                    values: values
+                        .chunks(weight_count.max(1))
+                        .map(|weights| weights.to_vec())
+                        .collect(),
+                });
+            }
+        }
+    }
+    Ok(AnimationClip {
+        name: animation
+            .name()
+            .map(|s| s.to_string())
+            .unwrap_or(format!("index {}", animation.index())),
+        channels,
+    })
+}
+
+fn skin_name(skin: &::gltf::Skin) -> String {
+    skin.name()
+        .map(|s| s.to_string())
+        .unwrap_or(format!("index {}", skin.index()))
+}
+
+///
+/// Decodes the percent-encoded characters, for example `%20` for a space, in a relative URI.
+///
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn node_name(node: &::gltf::Node) -> String {
+    node.name()
+        .map(|s| s.to_string())
+        .unwrap_or(format!("index {}", node.index()))
+}
+
 fn parse_texture<'a>(
     loaded: &mut Loaded,
     path: &Path,
     buffers: &[::gltf::buffer::Data],
     gltf_texture: ::gltf::texture::Texture,
-) -> ThreeDResult<CpuTexture<u8>> {
+) -> ThreeDResult<Option<CpuTexture<u8>>> {
     let gltf_image = gltf_texture.source();
     let gltf_source = gltf_image.source();
     let tex = match gltf_source {
-        ::gltf::image::Source::Uri { uri, .. } => loaded.image(path.join(Path::new(uri)))?,
+        ::gltf::image::Source::Uri { uri, .. } => {
+            let texture_path = path.join(decode_uri(uri));
+            // Skip textures that failed to load instead of failing to load the whole model
+            if loaded.failure(&texture_path).is_some() {
+                return Ok(None);
+            }
+            loaded.image(texture_path)?
+        }
         ::gltf::image::Source::View { view, .. } => {
             let mut bytes = Vec::with_capacity(view.length());
             bytes.extend(
@@ -243,5 +1137,682 @@ fn parse_texture<'a>(
         }
     };
     // TODO: Parse sampling parameters
-    Ok(tex)
+    Ok(Some(tex))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given meshes and materials as a .gltf file with a .bin resource file next to it.
+    /// If `embed_textures` is true, the textures are stored as PNG images in the .bin file,
+    /// otherwise each texture is saved as a .png file next to the .gltf file.
+    /// Each mesh is saved as a node in the glTF scene and the material of a mesh is found using [CpuMesh::material].
+    /// Skins are not saved, so neither are the joint indices and weights of the meshes.
+    ///
+    pub fn save_gltf<P: AsRef<Path>>(
+        path: P,
+        cpu_meshes: &[CpuMesh],
+        cpu_materials: &[CpuMaterial],
+        embed_textures: bool,
+    ) -> ThreeDResult<()> {
+        let path = path.as_ref();
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "scene".to_string());
+        let mut builder = GltfBuilder::new(if embed_textures {
+            None
+        } else {
+            Some(stem.clone())
+        });
+        builder.add_scene(cpu_meshes, cpu_materials)?;
+        let bin_name = format!("{}.bin", stem);
+        builder.add_buffer(Some(bin_name.clone()));
+        Saver::save_file(
+            path,
+            &::gltf::json::serialize::to_vec_pretty(&builder.root)?,
+        )?;
+        Saver::save_file(path.with_file_name(bin_name), &builder.buffer)?;
+        for (name, bytes) in builder.images {
+            Saver::save_file(path.with_file_name(name), &bytes)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Saves the given meshes and materials as a binary .glb file with all data, including the textures, embedded.
+    /// Each mesh is saved as a node in the glTF scene and the material of a mesh is found using [CpuMesh::material].
+    /// Skins are not saved, so neither are the joint indices and weights of the meshes.
+    ///
+    pub fn save_glb<P: AsRef<Path>>(
+        path: P,
+        cpu_meshes: &[CpuMesh],
+        cpu_materials: &[CpuMaterial],
+    ) -> ThreeDResult<()> {
+        let mut builder = GltfBuilder::new(None);
+        builder.add_scene(cpu_meshes, cpu_materials)?;
+        builder.add_buffer(None);
+        let json = ::gltf::json::serialize::to_vec(&builder.root)?;
+        let buffer = builder.buffer;
+        let glb = ::gltf::Glb {
+            header: ::gltf::binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                length: 0, // Computed when writing
+            },
+            json: std::borrow::Cow::Owned(json),
+            bin: if buffer.is_empty() {
+                None
+            } else {
+                Some(std::borrow::Cow::Owned(buffer))
+            },
+        };
+        Saver::save_file(path, &glb.to_vec()?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct GltfBuilder {
+    root: ::gltf::json::Root,
+    buffer: Vec<u8>,
+    /// The name prefix of the sidecar images or `None` if the images are embedded in the buffer
+    image_prefix: Option<String>,
+    images: Vec<(String, Vec<u8>)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GltfBuilder {
+    fn new(image_prefix: Option<String>) -> Self {
+        Self {
+            root: ::gltf::json::Root {
+                asset: ::gltf::json::Asset {
+                    generator: Some(format!("three-d {}", env!("CARGO_PKG_VERSION"))),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            buffer: Vec::new(),
+            image_prefix,
+            images: Vec::new(),
+        }
+    }
+
+    fn add_buffer(&mut self, uri: Option<String>) {
+        if !self.buffer.is_empty() {
+            self.root.buffers.push(::gltf::json::Buffer {
+                byte_length: self.buffer.len() as u32,
+                name: None,
+                uri,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+    }
+
+    fn add_scene(
+        &mut self,
+        cpu_meshes: &[CpuMesh],
+        cpu_materials: &[CpuMaterial],
+    ) -> ThreeDResult<()> {
+        use ::gltf::json::{Index, Node, Scene};
+        let mut material_indices = Vec::new();
+        for cpu_material in cpu_materials {
+            material_indices.push(self.add_material(cpu_material)?);
+        }
+        let mut nodes = Vec::new();
+        for cpu_mesh in cpu_meshes {
+            let material = cpu_mesh.material_name.as_ref().and_then(|name| {
+                cpu_materials
+                    .iter()
+                    .position(|m| &m.name == name)
+                    .map(|i| material_indices[i])
+            });
+            let mesh = self.add_mesh(cpu_mesh, material);
+            nodes.push(Index::new(self.root.nodes.len() as u32));
+            self.root.nodes.push(Node {
+                camera: None,
+                children: None,
+                extensions: None,
+                extras: Default::default(),
+                matrix: None,
+                mesh: Some(mesh),
+                name: (!cpu_mesh.name.is_empty()).then(|| cpu_mesh.name.clone()),
+                rotation: None,
+                scale: None,
+                translation: None,
+                skin: None,
+                weights: None,
+            });
+        }
+        self.root.scenes.push(Scene {
+            extensions: None,
+            extras: Default::default(),
+            name: None,
+            nodes,
+        });
+        self.root.scene = Some(Index::new(0));
+        Ok(())
+    }
+
+    fn add_mesh(
+        &mut self,
+        cpu_mesh: &CpuMesh,
+        material: Option<::gltf::json::Index<::gltf::json::Material>>,
+    ) -> ::gltf::json::Index<::gltf::json::Mesh> {
+        use ::gltf::json::accessor::{ComponentType, Type};
+        use ::gltf::json::buffer::Target;
+        use ::gltf::json::mesh::{MorphTarget, Primitive, Semantic};
+        use ::gltf::json::validation::Checked::Valid;
+        use ::gltf::json::Index;
+
+        let mut attributes = std::collections::HashMap::new();
+        let positions = cpu_mesh.positions.to_f32();
+        attributes.insert(Valid(Semantic::Positions), self.add_vec3s(&positions, true));
+        if let Some(ref normals) = cpu_mesh.normals {
+            attributes.insert(Valid(Semantic::Normals), self.add_vec3s(normals, false));
+        }
+        if let Some(ref tangents) = cpu_mesh.tangents {
+            let data = tangents
+                .iter()
+                .flat_map(|t| [t.x, t.y, t.z, t.w])
+                .collect::<Vec<_>>();
+            let accessor = self.add_accessor(
+                &f32_bytes(&data),
+                tangents.len(),
+                ComponentType::F32,
+                Type::Vec4,
+                Some(Target::ArrayBuffer),
+                false,
+                None,
+            );
+            attributes.insert(Valid(Semantic::Tangents), accessor);
+        }
+        for (set, uvs) in [&cpu_mesh.uvs, &cpu_mesh.secondary_uvs].iter().enumerate() {
+            if let Some(ref uvs) = uvs {
+                let data = uvs.iter().flat_map(|uv| [uv.x, uv.y]).collect::<Vec<_>>();
+                let accessor = self.add_accessor(
+                    &f32_bytes(&data),
+                    uvs.len(),
+                    ComponentType::F32,
+                    Type::Vec2,
+                    Some(Target::ArrayBuffer),
+                    false,
+                    None,
+                );
+                attributes.insert(Valid(Semantic::TexCoords(set as u32)), accessor);
+            }
+        }
+        if let Some(ref colors) = cpu_mesh.colors {
+            let data = colors
+                .iter()
+                .flat_map(|c| [c.r, c.g, c.b, c.a])
+                .collect::<Vec<_>>();
+            let accessor = self.add_accessor(
+                &data,
+                colors.len(),
+                ComponentType::U8,
+                Type::Vec4,
+                Some(Target::ArrayBuffer),
+                true,
+                None,
+            );
+            attributes.insert(Valid(Semantic::Colors(0)), accessor);
+        }
+
+        let indices = cpu_mesh.indices.as_ref().map(|indices| {
+            let (bytes, component_type) = match indices {
+                Indices::U8(ind) => (ind.clone(), ComponentType::U8),
+                Indices::U16(ind) => (
+                    ind.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                    ComponentType::U16,
+                ),
+                Indices::U32(ind) => (
+                    ind.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                    ComponentType::U32,
+                ),
+            };
+            self.add_accessor(
+                &bytes,
+                indices.len(),
+                component_type,
+                Type::Scalar,
+                Some(Target::ElementArrayBuffer),
+                false,
+                None,
+            )
+        });
+
+        let targets = if cpu_mesh.morph_targets.is_empty() {
+            None
+        } else {
+            Some(
+                cpu_mesh
+                    .morph_targets
+                    .iter()
+                    .map(|morph_target| MorphTarget {
+                        positions: morph_target
+                            .position_deltas
+                            .as_ref()
+                            .map(|deltas| self.add_vec3s(deltas, true)),
+                        normals: morph_target
+                            .normal_deltas
+                            .as_ref()
+                            .map(|deltas| self.add_vec3s(deltas, false)),
+                        tangents: morph_target
+                            .tangent_deltas
+                            .as_ref()
+                            .map(|deltas| self.add_vec3s(deltas, false)),
+                    })
+                    .collect(),
+            )
+        };
+
+        let index = Index::new(self.root.meshes.len() as u32);
+        self.root.meshes.push(::gltf::json::Mesh {
+            extensions: None,
+            extras: Default::default(),
+            name: (!cpu_mesh.name.is_empty()).then(|| cpu_mesh.name.clone()),
+            primitives: vec![Primitive {
+                attributes,
+                extensions: None,
+                extras: Default::default(),
+                indices,
+                material,
+                mode: Valid(::gltf::json::mesh::Mode::Triangles),
+                targets,
+            }],
+            weights: if cpu_mesh.morph_weights.is_empty() {
+                None
+            } else {
+                Some(cpu_mesh.morph_weights.clone())
+            },
+        });
+        index
+    }
+
+    fn add_material(
+        &mut self,
+        cpu_material: &CpuMaterial,
+    ) -> ThreeDResult<::gltf::json::Index<::gltf::json::Material>> {
+        use ::gltf::json::material::*;
+        use ::gltf::json::validation::Checked::Valid;
+        use ::gltf::json::Index;
+
+        let texture_info = |builder: &mut Self,
+                            texture: &Option<CpuTexture<u8>>,
+                            transform: &TextureTransform|
+         -> ThreeDResult<Option<::gltf::json::texture::Info>> {
+            Ok(if let Some(ref texture) = texture {
+                let default = TextureTransform {
+                    uv_set: transform.uv_set,
+                    ..Default::default()
+                };
+                let extensions = if *transform != default {
+                    builder.add_extension_used("KHR_texture_transform");
+                    Some(::gltf::json::extensions::texture::Info {
+                        texture_transform: Some(
+                            ::gltf::json::extensions::texture::TextureTransform {
+                                offset: ::gltf::json::extensions::texture::TextureTransformOffset(
+                                    transform.offset.into(),
+                                ),
+                                rotation:
+                                    ::gltf::json::extensions::texture::TextureTransformRotation(
+                                        transform.rotation,
+                                    ),
+                                scale: ::gltf::json::extensions::texture::TextureTransformScale(
+                                    transform.scale.into(),
+                                ),
+                                tex_coord: None,
+                                extras: Default::default(),
+                            },
+                        ),
+                    })
+                } else {
+                    None
+                };
+                Some(::gltf::json::texture::Info {
+                    index: builder.add_texture(texture)?,
+                    tex_coord: transform.uv_set,
+                    extensions,
+                    extras: Default::default(),
+                })
+            } else {
+                None
+            })
+        };
+
+        let base_color_texture = texture_info(
+            self,
+            &cpu_material.albedo_texture,
+            &cpu_material.albedo_texture_transform,
+        )?;
+        let metallic_roughness_texture = if cpu_material.metallic_roughness_texture.is_some() {
+            texture_info(
+                self,
+                &cpu_material.metallic_roughness_texture,
+                &cpu_material.metallic_roughness_texture_transform,
+            )?
+        } else {
+            texture_info(
+                self,
+                &cpu_material.occlusion_metallic_roughness_texture,
+                &cpu_material.metallic_roughness_texture_transform,
+            )?
+        };
+        // Only the set of uv coordinates of normal and occlusion textures can be saved, see the loader
+        let occlusion_texture = if cpu_material.occlusion_texture.is_some() {
+            texture_info(
+                self,
+                &cpu_material.occlusion_texture,
+                &TextureTransform {
+                    uv_set: cpu_material.occlusion_texture_transform.uv_set,
+                    ..Default::default()
+                },
+            )?
+        } else if cpu_material.occlusion_metallic_roughness_texture.is_some() {
+            // The occlusion is stored in the red channel of the combined texture, so the texture can be shared
+            metallic_roughness_texture.clone()
+        } else {
+            None
+        }
+        .map(|info| OcclusionTexture {
+            index: info.index,
+            strength: StrengthFactor(cpu_material.occlusion_strength),
+            tex_coord: info.tex_coord,
+            extensions: None,
+            extras: Default::default(),
+        });
+        let normal_texture = texture_info(
+            self,
+            &cpu_material.normal_texture,
+            &TextureTransform {
+                uv_set: cpu_material.normal_texture_transform.uv_set,
+                ..Default::default()
+            },
+        )?
+        .map(|info| NormalTexture {
+            index: info.index,
+            scale: cpu_material.normal_scale,
+            tex_coord: info.tex_coord,
+            extensions: None,
+            extras: Default::default(),
+        });
+        let emissive_texture = texture_info(
+            self,
+            &cpu_material.emissive_texture,
+            &cpu_material.emissive_texture_transform,
+        )?;
+
+        let index = Index::new(self.root.materials.len() as u32);
+        self.root.materials.push(Material {
+            alpha_cutoff: cpu_material.alpha_cutout.map(AlphaCutoff),
+            alpha_mode: Valid(if cpu_material.alpha_cutout.is_some() {
+                AlphaMode::Mask
+            } else if cpu_material
+                .is_transparent
+                .unwrap_or(cpu_material.albedo.a < 255)
+            {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            }),
+            double_sided: cpu_material.cull == Cull::None,
+            name: Some(cpu_material.name.clone()),
+            pbr_metallic_roughness: PbrMetallicRoughness {
+                base_color_factor: PbrBaseColorFactor(cpu_material.albedo.to_rgba_slice()),
+                base_color_texture,
+                metallic_factor: StrengthFactor(cpu_material.metallic),
+                roughness_factor: StrengthFactor(cpu_material.roughness),
+                metallic_roughness_texture,
+                extensions: None,
+                extras: Default::default(),
+            },
+            normal_texture,
+            occlusion_texture,
+            emissive_texture,
+            emissive_factor: EmissiveFactor(cpu_material.emissive.to_rgb_slice()),
+            extensions: None,
+            extras: Default::default(),
+        });
+        Ok(index)
+    }
+
+    fn add_extension_used(&mut self, extension: &str) {
+        if !self.root.extensions_used.iter().any(|e| e == extension) {
+            self.root.extensions_used.push(extension.to_string());
+        }
+    }
+
+    fn add_texture(
+        &mut self,
+        texture: &CpuTexture<u8>,
+    ) -> ThreeDResult<::gltf::json::Index<::gltf::json::Texture>> {
+        use ::gltf::json::texture::*;
+        use ::gltf::json::validation::Checked::Valid;
+        use ::gltf::json::Index;
+
+        let bytes = image_to_png_bytes(texture)?;
+        let image_index = self.root.images.len();
+        let image = if let Some(ref prefix) = self.image_prefix {
+            let name = format!("{}_{}.png", prefix, image_index);
+            self.images.push((name.clone(), bytes));
+            ::gltf::json::Image {
+                buffer_view: None,
+                mime_type: None,
+                name: None,
+                uri: Some(name),
+                extensions: None,
+                extras: Default::default(),
+            }
+        } else {
+            ::gltf::json::Image {
+                buffer_view: Some(self.add_view(&bytes, None)),
+                mime_type: Some(::gltf::json::image::MimeType("image/png".to_string())),
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            }
+        };
+        self.root.images.push(image);
+
+        let wrapping = |wrapping: Wrapping| match wrapping {
+            Wrapping::Repeat => WrappingMode::Repeat,
+            Wrapping::MirroredRepeat => WrappingMode::MirroredRepeat,
+            Wrapping::ClampToEdge => WrappingMode::ClampToEdge,
+        };
+        let min_filter = match (texture.min_filter, texture.mip_map_filter) {
+            (Interpolation::Nearest, None) => MinFilter::Nearest,
+            (Interpolation::Linear, None) => MinFilter::Linear,
+            (Interpolation::Nearest, Some(Interpolation::Nearest)) => {
+                MinFilter::NearestMipmapNearest
+            }
+            (Interpolation::Linear, Some(Interpolation::Nearest)) => MinFilter::LinearMipmapNearest,
+            (Interpolation::Nearest, Some(Interpolation::Linear)) => MinFilter::NearestMipmapLinear,
+            (Interpolation::Linear, Some(Interpolation::Linear)) => MinFilter::LinearMipmapLinear,
+        };
+        self.root.samplers.push(Sampler {
+            mag_filter: Some(Valid(match texture.mag_filter {
+                Interpolation::Nearest => MagFilter::Nearest,
+                Interpolation::Linear => MagFilter::Linear,
+            })),
+            min_filter: Some(Valid(min_filter)),
+            name: None,
+            wrap_s: Valid(wrapping(texture.wrap_s)),
+            wrap_t: Valid(wrapping(texture.wrap_t)),
+            extensions: None,
+            extras: Default::default(),
+        });
+
+        let index = Index::new(self.root.textures.len() as u32);
+        self.root.textures.push(Texture {
+            name: None,
+            sampler: Some(Index::new(self.root.samplers.len() as u32 - 1)),
+            source: Index::new(image_index as u32),
+            extensions: None,
+            extras: Default::default(),
+        });
+        Ok(index)
+    }
+
+    fn add_vec3s(
+        &mut self,
+        values: &[Vec3],
+        with_bounds: bool,
+    ) -> ::gltf::json::Index<::gltf::json::Accessor> {
+        use ::gltf::json::accessor::{ComponentType, Type};
+        let bounds = if with_bounds && !values.is_empty() {
+            let aabb = AxisAlignedBoundingBox::new_with_positions(values);
+            let (min, max) = (aabb.min(), aabb.max());
+            Some((vec![min.x, min.y, min.z], vec![max.x, max.y, max.z]))
+        } else {
+            None
+        };
+        let data = values
+            .iter()
+            .flat_map(|v| [v.x, v.y, v.z])
+            .collect::<Vec<_>>();
+        self.add_accessor(
+            &f32_bytes(&data),
+            values.len(),
+            ComponentType::F32,
+            Type::Vec3,
+            Some(::gltf::json::buffer::Target::ArrayBuffer),
+            false,
+            bounds,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_accessor(
+        &mut self,
+        bytes: &[u8],
+        count: usize,
+        component_type: ::gltf::json::accessor::ComponentType,
+        type_: ::gltf::json::accessor::Type,
+        target: Option<::gltf::json::buffer::Target>,
+        normalized: bool,
+        bounds: Option<(Vec<f32>, Vec<f32>)>,
+    ) -> ::gltf::json::Index<::gltf::json::Accessor> {
+        use ::gltf::json::accessor::GenericComponentType;
+        use ::gltf::json::validation::Checked::Valid;
+        let view = self.add_view(bytes, target);
+        let index = ::gltf::json::Index::new(self.root.accessors.len() as u32);
+        let (min, max) = bounds
+            .map(|(min, max)| (Some(min.into()), Some(max.into())))
+            .unwrap_or((None, None));
+        self.root.accessors.push(::gltf::json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: 0,
+            count: count as u32,
+            component_type: Valid(GenericComponentType(component_type)),
+            extensions: None,
+            extras: Default::default(),
+            type_: Valid(type_),
+            min,
+            max,
+            name: None,
+            normalized,
+            sparse: None,
+        });
+        index
+    }
+
+    fn add_view(
+        &mut self,
+        bytes: &[u8],
+        target: Option<::gltf::json::buffer::Target>,
+    ) -> ::gltf::json::Index<::gltf::json::buffer::View> {
+        // Buffer views with vertex data must be aligned to 4 bytes
+        self.buffer.resize(self.buffer.len().div_ceil(4) * 4, 0);
+        let index = ::gltf::json::Index::new(self.root.buffer_views.len() as u32);
+        self.root.buffer_views.push(::gltf::json::buffer::View {
+            buffer: ::gltf::json::Index::new(0),
+            byte_length: bytes.len() as u32,
+            byte_offset: Some(self.buffer.len() as u32),
+            byte_stride: None,
+            name: None,
+            target: target.map(::gltf::json::validation::Checked::Valid),
+            extensions: None,
+            extras: Default::default(),
+        });
+        self.buffer.extend_from_slice(bytes);
+        index
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///
+    /// Returns a .gltf file with a triangle which is mirrored by the node transformation and the .bin file it refers to.
+    ///
+    fn mirrored_triangle(indexed: bool) -> (Vec<u8>, Vec<u8>) {
+        let mut bin = [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+        bin.extend([0u16, 1, 2, 0].iter().flat_map(|i| i.to_le_bytes()));
+        let indices = if indexed { r#","indices":1"# } else { "" };
+        let json = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "scene": 0,
+                "scenes": [{{ "nodes": [0] }}],
+                "nodes": [{{ "mesh": 0, "scale": [-1.0, 1.0, 1.0] }}],
+                "meshes": [{{ "primitives": [{{ "attributes": {{ "POSITION": 0 }}{} }}] }}],
+                "accessors": [
+                    {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+                    {{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }}
+                ],
+                "bufferViews": [
+                    {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+                    {{ "buffer": 0, "byteOffset": 36, "byteLength": 6 }}
+                ],
+                "buffers": [{{ "uri": "triangle.bin", "byteLength": 44 }}]
+            }}"#,
+            indices
+        );
+        (json.into_bytes(), bin)
+    }
+
+    fn load(indexed: bool) -> CpuMesh {
+        let (gltf, bin) = mirrored_triangle(indexed);
+        let mut loaded = Loaded::new();
+        loaded.insert_bytes("models/triangle.gltf", gltf);
+        loaded.insert_bytes("models/triangle.bin", bin);
+        let (mut meshes, _) = loaded.gltf("models/triangle.gltf").unwrap();
+        meshes.remove(0)
+    }
+
+    #[test]
+    fn mirrored_node_reverses_winding_order() {
+        let mesh = load(true);
+        assert_eq!(mesh.indices.unwrap().to_u32(), vec![0, 2, 1]);
+        assert_eq!(
+            mesh.positions.to_f32(),
+            vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(-1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0)
+            ]
+        );
+
+        let mesh = load(false);
+        assert!(mesh.indices.is_none());
+        assert_eq!(
+            mesh.positions.to_f32(),
+            vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(-1.0, 0.0, 0.0)
+            ]
+        );
+    }
 }
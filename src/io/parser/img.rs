@@ -36,6 +36,28 @@ pub fn image_from_bytes(bytes: &[u8]) -> ThreeDResult<crate::core::CpuTexture<u8
     })
 }
 
+///
+/// Serialize the given [CpuTexture] into the bytes of a PNG image.
+///
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn image_to_png_bytes(texture: &CpuTexture<u8>) -> ThreeDResult<Vec<u8>> {
+    let color_type = match texture.format {
+        Format::R => image::ColorType::L8,
+        Format::RG => image::ColorType::La8,
+        Format::RGB => image::ColorType::Rgb8,
+        Format::RGBA => image::ColorType::Rgba8,
+        Format::Compressed(format) => Err(IOError::CompressedImage(format))?,
+    };
+    let mut bytes = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut bytes).encode(
+        &texture.data,
+        texture.width,
+        texture.height,
+        color_type,
+    )?;
+    Ok(bytes)
+}
+
 ///
 /// Deserialize the given bytes representing a hdr image into a [CpuTexture] using
 /// the [image](https://crates.io/crates/image/main.rs) crate.
@@ -100,6 +122,7 @@ pub fn cube_image_from_bytes(
         wrap_s: right.wrap_s,
         wrap_t: right.wrap_t,
         wrap_r: right.wrap_s,
+        mip_maps: Vec::new(),
     })
 }
 
@@ -162,12 +185,67 @@ impl Loaded {
             wrap_s: right.wrap_s,
             wrap_t: right.wrap_t,
             wrap_r: right.wrap_s,
+            mip_maps: Vec::new(),
         })
     }
+
+    ///
+    /// Deserialize the loaded image resource at the given path, which contains the 6 sides of a cube in a horizontal or vertical cross layout,
+    /// into a [CpuTextureCube] using the [image](https://crates.io/crates/image/main.rs) crate, see [CpuTextureCube::from_cross] for the supported layouts.
+    /// The CpuTextureCube can then be used to create a [TextureCubeMap].
+    ///
+    pub fn cross_cube_image(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTextureCube<u8>> {
+        CpuTextureCube::from_cross(&self.image(path)?)
+    }
+
+    ///
+    /// Deserialize the loaded equirectangular image resource at the given path into a [CpuTextureCube] where each side has the given size
+    /// using the [image](https://crates.io/crates/image/main.rs) crate, see [CpuTextureCube::from_equirectangular].
+    /// To load a high dynamic range image, use [CpuTextureCube::from_equirectangular] with the result of [Loaded::hdr_image] instead.
+    ///
+    pub fn equirectangular_cube_image(
+        &mut self,
+        path: impl AsRef<Path>,
+        size: u32,
+    ) -> ThreeDResult<CpuTextureCube<u8>> {
+        CpuTextureCube::from_equirectangular(&self.image(path)?, size)
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Saver {
+    ///
+    /// Saves the given texture as an image where the image format is determined by the extension of the path.
+    /// Unlike [Saver::save_pixels], the first row of the texture data is the top row of the image,
+    /// which is the case for textures loaded by the [Loader](crate::Loader) and for example the result of [TextureCubeMap::to_equirectangular].
+    ///
+    /// - `.hdr` images store the RGB channels as floating point values, where `u8` and `u16` data is mapped to the range [0..1].
+    ///   A single channel is stored as gray, a missing blue channel is stored as zero and the alpha channel is ignored.
+    /// - `.exr` images store all channels, where `u8` and `u16` data is mapped to the range [0..1] like for `.hdr` images, see `Saver::save_exr`. Requires the `exr-io` feature.
+    /// - Other image types, for example `.png`, store `u8` data with 8 bits per channel and `u16` data with 16 bits per channel.
+    ///   Floating point data, for example depth values, is clamped to the range [0..1] and stored with 16 bits per channel.
+    ///   Note that not all image types support 16 bits per channel or all channel counts.
+    ///
+    pub fn save_texture<T: TextureDataType>(
+        path: impl AsRef<Path>,
+        texture: &CpuTexture<T>,
+    ) -> ThreeDResult<()> {
+        if let Format::Compressed(format) = texture.format {
+            Err(IOError::CompressedImage(format))?;
+        }
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "hdr" => save_hdr(path, texture),
+            #[cfg(feature = "exr-io")]
+            "exr" => Self::save_exr(path, texture),
+            _ => save_image(path, &extension, texture),
+        }
+    }
+
     ///
     /// Saves the given RGB pixels as an image.
     ///
@@ -197,3 +275,98 @@ impl Saver {
         Ok(())
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_hdr<T: TextureDataType>(path: &Path, texture: &CpuTexture<T>) -> ThreeDResult<()> {
+    use crate::context::DataType;
+    let scale = match T::data_type() {
+        DataType::UnsignedByte => 1.0 / u8::MAX as f32,
+        DataType::UnsignedShort => 1.0 / u16::MAX as f32,
+        _ => 1.0,
+    };
+    let channels = texture.format.color_channel_count() as usize;
+    let pixels = texture
+        .data
+        .chunks(channels)
+        .map(|pixel| {
+            let value = |i: usize| pixel.get(i).map(|v| T::to_f32(*v) * scale).unwrap_or(0.0);
+            if channels == 1 {
+                image::Rgb([value(0); 3])
+            } else {
+                image::Rgb([value(0), value(1), value(2)])
+            }
+        })
+        .collect::<Vec<_>>();
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    image::codecs::hdr::HdrEncoder::new(file).encode(
+        &pixels,
+        texture.width as usize,
+        texture.height as usize,
+    )?;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_image<T: TextureDataType>(
+    path: &Path,
+    extension: &str,
+    texture: &CpuTexture<T>,
+) -> ThreeDResult<()> {
+    use crate::context::DataType;
+    use image::ColorType;
+    let (bytes, color_types) = match T::data_type() {
+        DataType::UnsignedByte => (
+            texture
+                .data
+                .iter()
+                .map(|v| T::to_f32(*v) as u8)
+                .collect::<Vec<_>>(),
+            [
+                ColorType::L8,
+                ColorType::La8,
+                ColorType::Rgb8,
+                ColorType::Rgba8,
+            ],
+        ),
+        DataType::UnsignedShort => (
+            texture
+                .data
+                .iter()
+                .flat_map(|v| (T::to_f32(*v) as u16).to_ne_bytes())
+                .collect(),
+            [
+                ColorType::L16,
+                ColorType::La16,
+                ColorType::Rgb16,
+                ColorType::Rgba16,
+            ],
+        ),
+        DataType::HalfFloat | DataType::Float => (
+            texture
+                .data
+                .iter()
+                .flat_map(|v| {
+                    ((T::to_f32(*v).clamp(0.0, 1.0) * u16::MAX as f32).round() as u16).to_ne_bytes()
+                })
+                .collect(),
+            [
+                ColorType::L16,
+                ColorType::La16,
+                ColorType::Rgb16,
+                ColorType::Rgba16,
+            ],
+        ),
+        _ => Err(IOError::ImageDataType(
+            "u32".to_string(),
+            extension.to_string(),
+        ))?,
+    };
+    image::save_buffer(
+        path,
+        &bytes,
+        texture.width,
+        texture.height,
+        color_types[texture.format.color_channel_count() as usize - 1],
+    )?;
+    Ok(())
+}
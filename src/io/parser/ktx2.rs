@@ -0,0 +1,182 @@
+use crate::core::*;
+use crate::io::*;
+use ::ktx2::Format as KtxFormat;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .ktx2 file at the given path into a [CpuTexture].
+    ///
+    /// Supports 8-bit uncompressed formats and the block compressed formats listed in [CompressedFormat].
+    /// sRGB formats are returned with the same format as their linear counterparts since the color conversion is done in the shaders.
+    /// Any mip maps stored in the file are returned in [CpuTexture::mip_maps] and used when constructing a [Texture2D].
+    ///
+    /// # Errors
+    /// Returns an error if the file is supercompressed (for example Basis Universal), has an unsupported format or is not a 2D texture.
+    ///
+    pub fn ktx2(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTexture<u8>> {
+        let (format, width, height, mut levels) = parse(self.get_bytes(path)?, 1)?;
+        let data = levels.remove(0).remove(0);
+        Ok(CpuTexture {
+            data,
+            width,
+            height,
+            format,
+            mip_maps: levels
+                .into_iter()
+                .map(|mut faces| faces.remove(0))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Deserialize the loaded .ktx2 cube map file at the given path into a [CpuTextureCube].
+    /// Supports the same formats as [Loaded::ktx2] and any mip maps stored in the file are returned in [CpuTextureCube::mip_maps].
+    ///
+    /// # Errors
+    /// Returns an error if the file is supercompressed (for example Basis Universal), has an unsupported format or is not a cube map.
+    ///
+    pub fn ktx2_cube(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTextureCube<u8>> {
+        let (format, width, height, levels) = parse(self.get_bytes(path)?, 6)?;
+        let mut levels = levels.into_iter().map(|faces| {
+            let mut faces = faces.into_iter();
+            [(); 6].map(|_| faces.next().unwrap())
+        });
+        let [right_data, left_data, top_data, bottom_data, front_data, back_data] =
+            levels.next().unwrap();
+        Ok(CpuTextureCube {
+            right_data,
+            left_data,
+            top_data,
+            bottom_data,
+            front_data,
+            back_data,
+            width,
+            height,
+            format,
+            mip_maps: levels.collect(),
+            ..Default::default()
+        })
+    }
+}
+
+/// The data for each face of each mip level.
+type Levels = Vec<Vec<Vec<u8>>>;
+
+///
+/// Parses a .ktx2 file with the given number of faces and returns the format, the size and the data for each face of each mip level.
+///
+fn parse(bytes: &[u8], face_count: u32) -> ThreeDResult<(Format, u32, u32, Levels)> {
+    let reader = ::ktx2::Reader::new(bytes).map_err(|e| IOError::Ktx2(e.to_string()))?;
+    let header = reader.header();
+    if let Some(scheme) = header.supercompression_scheme {
+        Err(IOError::Ktx2(format!(
+            "supercompression scheme {:?} is not supported",
+            scheme
+        )))?;
+    }
+    if header.pixel_depth > 1 || header.layer_count > 1 {
+        Err(IOError::Ktx2(
+            "3D textures and texture arrays are not supported".to_string(),
+        ))?;
+    }
+    if header.face_count != face_count {
+        Err(IOError::Ktx2(format!(
+            "expected {} faces but the texture has {} faces",
+            face_count, header.face_count
+        )))?;
+    }
+    let format = header
+        .format
+        .ok_or_else(|| IOError::Ktx2("the texture format is not specified".to_string()))?;
+    let format = format_from(format)
+        .ok_or_else(|| IOError::Ktx2(format!("the format {:?} is not supported", format)))?;
+
+    let mut levels = Vec::new();
+    for (level, data) in reader.levels().enumerate() {
+        let width = (header.pixel_width >> level).max(1);
+        let height = (header.pixel_height.max(1) >> level).max(1);
+        let face_size = match format {
+            Format::Compressed(format) => format.data_size(width, height),
+            _ => (width * height * format.color_channel_count()) as usize,
+        };
+        if data.data.len() < face_size * face_count as usize {
+            Err(IOError::Ktx2(format!("mip level {} is too short", level)))?;
+        }
+        levels.push(
+            data.data
+                .chunks(face_size)
+                .take(face_count as usize)
+                .map(|face| face.to_vec())
+                .collect(),
+        );
+    }
+    Ok((
+        format,
+        header.pixel_width,
+        header.pixel_height.max(1),
+        levels,
+    ))
+}
+
+fn format_from(format: KtxFormat) -> Option<Format> {
+    Some(match format {
+        KtxFormat::R8_UNORM => Format::R,
+        KtxFormat::R8G8_UNORM => Format::RG,
+        KtxFormat::R8G8B8_UNORM | KtxFormat::R8G8B8_SRGB => Format::RGB,
+        KtxFormat::R8G8B8A8_UNORM | KtxFormat::R8G8B8A8_SRGB => Format::RGBA,
+        _ => Format::Compressed(match format {
+            KtxFormat::BC1_RGB_UNORM_BLOCK | KtxFormat::BC1_RGB_SRGB_BLOCK => {
+                CompressedFormat::Bc1Rgb
+            }
+            KtxFormat::BC1_RGBA_UNORM_BLOCK | KtxFormat::BC1_RGBA_SRGB_BLOCK => {
+                CompressedFormat::Bc1Rgba
+            }
+            KtxFormat::BC2_UNORM_BLOCK | KtxFormat::BC2_SRGB_BLOCK => CompressedFormat::Bc2,
+            KtxFormat::BC3_UNORM_BLOCK | KtxFormat::BC3_SRGB_BLOCK => CompressedFormat::Bc3,
+            KtxFormat::BC4_UNORM_BLOCK => CompressedFormat::Bc4,
+            KtxFormat::BC5_UNORM_BLOCK => CompressedFormat::Bc5,
+            KtxFormat::BC6H_UFLOAT_BLOCK => CompressedFormat::Bc6h,
+            KtxFormat::BC7_UNORM_BLOCK | KtxFormat::BC7_SRGB_BLOCK => CompressedFormat::Bc7,
+            KtxFormat::ETC2_R8G8B8_UNORM_BLOCK | KtxFormat::ETC2_R8G8B8_SRGB_BLOCK => {
+                CompressedFormat::Etc2Rgb
+            }
+            KtxFormat::ETC2_R8G8B8A1_UNORM_BLOCK | KtxFormat::ETC2_R8G8B8A1_SRGB_BLOCK => {
+                CompressedFormat::Etc2Rgba1
+            }
+            KtxFormat::ETC2_R8G8B8A8_UNORM_BLOCK | KtxFormat::ETC2_R8G8B8A8_SRGB_BLOCK => {
+                CompressedFormat::Etc2Rgba
+            }
+            KtxFormat::EAC_R11_UNORM_BLOCK => CompressedFormat::EacR,
+            KtxFormat::EAC_R11G11_UNORM_BLOCK => CompressedFormat::EacRg,
+            _ => {
+                // The ASTC formats are ordered by block size with a linear and an sRGB variant of each
+                const ASTC_BLOCK_SIZES: [(u8, u8); 14] = [
+                    (4, 4),
+                    (5, 4),
+                    (5, 5),
+                    (6, 5),
+                    (6, 6),
+                    (8, 5),
+                    (8, 6),
+                    (8, 8),
+                    (10, 5),
+                    (10, 6),
+                    (10, 8),
+                    (10, 10),
+                    (12, 10),
+                    (12, 12),
+                ];
+                let index = format
+                    .value()
+                    .checked_sub(KtxFormat::ASTC_4x4_UNORM_BLOCK.value())?;
+                let (block_width, block_height) = *ASTC_BLOCK_SIZES.get(index as usize / 2)?;
+                CompressedFormat::Astc {
+                    block_width,
+                    block_height,
+                }
+            }
+        }),
+    })
+}
@@ -0,0 +1,535 @@
+//!
+//! Decoding of buffer views compressed with the [meshoptimizer](https://github.com/zeux/meshoptimizer) codecs,
+//! as used by the `EXT_meshopt_compression` glTF extension.
+//!
+
+use crate::core::*;
+use crate::io::*;
+
+const VERTEX_HEADER: u8 = 0xa0;
+const INDEX_HEADER: u8 = 0xe0;
+const SEQUENCE_HEADER: u8 = 0xd0;
+const BYTE_GROUP_SIZE: usize = 16;
+const VERTEX_BLOCK_SIZE_BYTES: usize = 8192;
+const VERTEX_BLOCK_MAX_SIZE: usize = 256;
+const TAIL_MAX_SIZE: usize = 32;
+
+///
+/// Decodes `count` elements of `stride` bytes from data encoded with the given mode and filter as specified by `EXT_meshopt_compression`.
+/// The caller must make sure that `count` times `stride` bytes is a reasonable allocation, for example by checking it against the size of the target buffer view.
+///
+pub(super) fn decode(
+    data: &[u8],
+    count: usize,
+    stride: usize,
+    mode: &str,
+    filter: &str,
+) -> ThreeDResult<Vec<u8>> {
+    if count.checked_mul(stride).is_none() {
+        Err(IOError::GltfCorruptData)?;
+    }
+    let mut result = match mode {
+        "ATTRIBUTES" => decode_vertex_buffer(data, count, stride),
+        "TRIANGLES" => decode_index_buffer(data, count, stride),
+        "INDICES" => decode_index_sequence(data, count, stride),
+        _ => None,
+    }
+    .ok_or(IOError::GltfCorruptData)?;
+    match filter {
+        "NONE" => {}
+        "OCTAHEDRAL" if stride == 4 => decode_octahedral_i8(&mut result),
+        "OCTAHEDRAL" if stride == 8 => decode_octahedral_i16(&mut result),
+        "QUATERNION" if stride == 8 => decode_quaternion(&mut result),
+        "EXPONENTIAL" if stride.is_multiple_of(4) => decode_exponential(&mut result),
+        _ => Err(IOError::GltfCorruptData)?,
+    }
+    Ok(result)
+}
+
+fn decode_vertex_buffer(data: &[u8], count: usize, stride: usize) -> Option<Vec<u8>> {
+    if stride == 0 || stride > 256 || !stride.is_multiple_of(4) || data.len() < 1 + stride {
+        return None;
+    }
+    if data[0] != VERTEX_HEADER {
+        return None;
+    }
+    let mut last_vertex = data[data.len() - stride..].to_vec();
+    let block_size =
+        ((VERTEX_BLOCK_SIZE_BYTES / stride) & !(BYTE_GROUP_SIZE - 1)).min(VERTEX_BLOCK_MAX_SIZE);
+
+    let mut result = vec![0; count * stride];
+    let mut position = 1;
+    let mut bytes = [0u8; VERTEX_BLOCK_MAX_SIZE];
+    let mut vertex_offset = 0;
+    while vertex_offset < count {
+        let block_count = block_size.min(count - vertex_offset);
+        let aligned_count = (block_count + BYTE_GROUP_SIZE - 1) & !(BYTE_GROUP_SIZE - 1);
+        for k in 0..stride {
+            position = decode_bytes(data, position, &mut bytes[..aligned_count])?;
+            let mut previous = last_vertex[k];
+            for i in 0..block_count {
+                let value = unzigzag8(bytes[i]).wrapping_add(previous);
+                result[(vertex_offset + i) * stride + k] = value;
+                previous = value;
+            }
+            last_vertex[k] = previous;
+        }
+        vertex_offset += block_count;
+    }
+    if data.len() - position != stride.max(TAIL_MAX_SIZE) {
+        return None;
+    }
+    Some(result)
+}
+
+fn decode_bytes(data: &[u8], position: usize, bytes: &mut [u8]) -> Option<usize> {
+    let group_count = bytes.len() / BYTE_GROUP_SIZE;
+    let header = data.get(position..position + group_count.div_ceil(4))?;
+    let mut position = position + header.len();
+    for group in 0..group_count {
+        let bits = (header[group / 4] >> ((group % 4) * 2)) & 3;
+        let output = &mut bytes[group * BYTE_GROUP_SIZE..(group + 1) * BYTE_GROUP_SIZE];
+        position = match bits {
+            0 => {
+                output.iter_mut().for_each(|b| *b = 0);
+                position
+            }
+            1 => decode_bytes_group(data, position, output, 2)?,
+            2 => decode_bytes_group(data, position, output, 4)?,
+            _ => {
+                output.copy_from_slice(data.get(position..position + BYTE_GROUP_SIZE)?);
+                position + BYTE_GROUP_SIZE
+            }
+        };
+    }
+    Some(position)
+}
+
+///
+/// Decodes a group of 16 values stored with the given number of bits each, followed by the values which did not fit in the bits.
+///
+fn decode_bytes_group(data: &[u8], position: usize, output: &mut [u8], bits: u32) -> Option<usize> {
+    let values_per_byte = 8 / bits as usize;
+    let packed = data.get(position..position + BYTE_GROUP_SIZE / values_per_byte)?;
+    let mut extra = position + packed.len();
+    let max = (1u8 << bits) - 1;
+    for (i, value) in output.iter_mut().enumerate() {
+        let byte = packed[i / values_per_byte];
+        let shift = 8 - bits as usize * (i % values_per_byte + 1);
+        let encoded = (byte >> shift) & max;
+        *value = if encoded == max {
+            extra += 1;
+            *data.get(extra - 1)?
+        } else {
+            encoded
+        };
+    }
+    Some(extra)
+}
+
+fn unzigzag8(v: u8) -> u8 {
+    (0u8.wrapping_sub(v & 1)) ^ (v >> 1)
+}
+
+///
+/// A ring buffer of the most recently used vertices or edges.
+///
+struct Fifo<T> {
+    values: [T; 16],
+    offset: usize,
+}
+
+impl<T: Copy> Fifo<T> {
+    fn new(value: T) -> Self {
+        Self {
+            values: [value; 16],
+            offset: 0,
+        }
+    }
+
+    fn get(&self, i: usize) -> T {
+        self.values[self.offset.wrapping_sub(i) & 15]
+    }
+
+    fn push(&mut self, value: T, condition: bool) {
+        self.values[self.offset] = value;
+        self.offset = (self.offset + condition as usize) & 15;
+    }
+}
+
+fn decode_index_buffer(data: &[u8], count: usize, index_size: usize) -> Option<Vec<u8>> {
+    if !count.is_multiple_of(3) || (index_size != 2 && index_size != 4) {
+        return None;
+    }
+    if data.len() < 1 + count / 3 + 16 || data[0] & 0xf0 != INDEX_HEADER {
+        return None;
+    }
+    let version = data[0] & 0x0f;
+    if version > 1 {
+        return None;
+    }
+    let fecmax = if version >= 1 { 13 } else { 15 };
+
+    let mut edges = Fifo::new([u32::MAX; 2]);
+    let mut vertices = Fifo::new(u32::MAX);
+    let mut next = 0u32;
+    let mut last = 0u32;
+    let mut position = 1 + count / 3;
+    let safe_end = data.len() - 16;
+    let codeaux_table = &data[safe_end..];
+    let mut indices = Vec::with_capacity(count);
+
+    for &codetri in &data[1..1 + count / 3] {
+        if position > safe_end {
+            return None;
+        }
+        let (a, b, c);
+        if codetri < 0xf0 {
+            let fe = (codetri >> 4) as usize;
+            [a, b] = edges.get(1 + fe);
+            let fec = (codetri & 15) as i32;
+            if fec < fecmax {
+                let fec0 = fec == 0;
+                c = if fec0 {
+                    next
+                } else {
+                    vertices.get(1 + fec as usize)
+                };
+                next += fec0 as u32;
+                vertices.push(c, fec0);
+            } else {
+                c = if fec != 15 {
+                    last.wrapping_add((fec - (fec ^ 3)) as u32)
+                } else {
+                    decode_index(data, &mut position, last)?
+                };
+                last = c;
+                vertices.push(c, true);
+            }
+            edges.push([c, b], true);
+            edges.push([a, c], true);
+        } else {
+            let (feb, fec);
+            if codetri < 0xfe {
+                let codeaux = codeaux_table[(codetri & 15) as usize];
+                feb = (codeaux >> 4) as usize;
+                fec = (codeaux & 15) as usize;
+                a = next;
+                next += 1;
+                b = if feb == 0 { next } else { vertices.get(feb) };
+                next += (feb == 0) as u32;
+                c = if fec == 0 { next } else { vertices.get(fec) };
+                next += (fec == 0) as u32;
+                vertices.push(a, true);
+                vertices.push(b, feb == 0);
+                vertices.push(c, fec == 0);
+            } else {
+                let codeaux = *data.get(position)?;
+                position += 1;
+                feb = (codeaux >> 4) as usize;
+                fec = (codeaux & 15) as usize;
+                if codeaux == 0 {
+                    next = 0;
+                }
+                let free = |fe: usize, next: &mut u32| -> Option<u32> {
+                    Some(match fe {
+                        0 => {
+                            *next += 1;
+                            *next - 1
+                        }
+                        15 => None?,
+                        _ => vertices.get(fe),
+                    })
+                };
+                let va = if codetri == 0xfe {
+                    free(0, &mut next)
+                } else {
+                    None
+                };
+                let vb = free(feb, &mut next);
+                let vc = free(fec, &mut next);
+                let mut decode = |v: Option<u32>| -> Option<u32> {
+                    if v.is_none() {
+                        last = decode_index(data, &mut position, last)?;
+                    }
+                    Some(v.unwrap_or(last))
+                };
+                a = decode(va)?;
+                b = decode(vb)?;
+                c = decode(vc)?;
+                vertices.push(a, true);
+                vertices.push(b, feb == 0 || feb == 15);
+                vertices.push(c, fec == 0 || fec == 15);
+            }
+            edges.push([b, a], true);
+            edges.push([c, b], true);
+            edges.push([a, c], true);
+        }
+        indices.extend_from_slice(&[a, b, c]);
+    }
+    if position != safe_end {
+        return None;
+    }
+    Some(index_bytes(&indices, index_size))
+}
+
+fn decode_index_sequence(data: &[u8], count: usize, index_size: usize) -> Option<Vec<u8>> {
+    if index_size != 2 && index_size != 4 {
+        return None;
+    }
+    if data.len() < 1 + count + 4 || data[0] & 0xf0 != SEQUENCE_HEADER || data[0] & 0x0f > 1 {
+        return None;
+    }
+    let safe_end = data.len() - 4;
+    let mut position = 1;
+    let mut last = [0u32; 2];
+    let mut indices = Vec::with_capacity(count);
+    for _ in 0..count {
+        if position >= safe_end {
+            return None;
+        }
+        let v = decode_vbyte(data, &mut position)?;
+        let baseline = (v & 1) as usize;
+        let v = v >> 1;
+        let delta = (v >> 1) ^ 0u32.wrapping_sub(v & 1);
+        let index = last[baseline].wrapping_add(delta);
+        last[baseline] = index;
+        indices.push(index);
+    }
+    if position != safe_end {
+        return None;
+    }
+    Some(index_bytes(&indices, index_size))
+}
+
+fn index_bytes(indices: &[u32], index_size: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(indices.len() * index_size);
+    for index in indices {
+        if index_size == 2 {
+            bytes.extend_from_slice(&(*index as u16).to_le_bytes());
+        } else {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn decode_vbyte(data: &[u8], position: &mut usize) -> Option<u32> {
+    let lead = *data.get(*position)?;
+    *position += 1;
+    if lead < 128 {
+        return Some(lead as u32);
+    }
+    let mut result = (lead & 127) as u32;
+    let mut shift = 7;
+    for _ in 0..4 {
+        let group = *data.get(*position)?;
+        *position += 1;
+        result |= ((group & 127) as u32) << shift;
+        shift += 7;
+        if group < 128 {
+            break;
+        }
+    }
+    Some(result)
+}
+
+fn decode_index(data: &[u8], position: &mut usize, last: u32) -> Option<u32> {
+    let v = decode_vbyte(data, position)?;
+    let delta = (v >> 1) ^ 0u32.wrapping_sub(v & 1);
+    Some(last.wrapping_add(delta))
+}
+
+fn decode_octahedral_i8(data: &mut [u8]) {
+    for element in data.chunks_exact_mut(4) {
+        let v = decode_octahedral(
+            [
+                element[0] as i8 as f32,
+                element[1] as i8 as f32,
+                element[2] as i8 as f32,
+            ],
+            127.0,
+        );
+        for i in 0..3 {
+            element[i] = v[i] as i8 as u8;
+        }
+    }
+}
+
+fn decode_octahedral_i16(data: &mut [u8]) {
+    for element in data.chunks_exact_mut(8) {
+        let component = |i: usize| i16::from_le_bytes([element[2 * i], element[2 * i + 1]]) as f32;
+        let v = decode_octahedral([component(0), component(1), component(2)], 32767.0);
+        for i in 0..3 {
+            element[2 * i..2 * i + 2].copy_from_slice(&(v[i] as i16).to_le_bytes());
+        }
+    }
+}
+
+///
+/// Reconstructs a unit vector from octahedral coordinates where the third component contains the value that encodes one.
+///
+fn decode_octahedral(v: [f32; 3], max: f32) -> [i32; 3] {
+    let (mut x, mut y) = (v[0], v[1]);
+    let z = v[2] - x.abs() - y.abs();
+    let t = z.min(0.0);
+    x += if x >= 0.0 { t } else { -t };
+    y += if y >= 0.0 { t } else { -t };
+    let scale = max / (x * x + y * y + z * z).sqrt();
+    [round(x * scale), round(y * scale), round(z * scale)]
+}
+
+fn decode_quaternion(data: &mut [u8]) {
+    let scale = 1.0 / 2f32.sqrt();
+    for element in data.chunks_exact_mut(8) {
+        let component = |i: usize| i16::from_le_bytes([element[2 * i], element[2 * i + 1]]);
+        let last = component(3);
+        let s = scale / (last | 3) as f32;
+        let x = component(0) as f32 * s;
+        let y = component(1) as f32 * s;
+        let z = component(2) as f32 * s;
+        let w = (1.0 - x * x - y * y - z * z).max(0.0).sqrt();
+        let max_component = (last & 3) as usize;
+        for (offset, value) in [(1, x), (2, y), (3, z), (0, w)] {
+            let i = (max_component + offset) & 3;
+            element[2 * i..2 * i + 2]
+                .copy_from_slice(&(round(value * 32767.0) as i16).to_le_bytes());
+        }
+    }
+}
+
+fn decode_exponential(data: &mut [u8]) {
+    for element in data.chunks_exact_mut(4) {
+        let v = u32::from_le_bytes([element[0], element[1], element[2], element[3]]);
+        let mantissa = ((v << 8) as i32) >> 8;
+        let exponent = (v as i32) >> 24;
+        let value = f32::from_bits(((exponent + 127) as u32) << 23) * mantissa as f32;
+        element.copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn round(value: f32) -> i32 {
+    (value + if value >= 0.0 { 0.5 } else { -0.5 }) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX_BUFFER: [u32; 12] = [0, 1, 2, 2, 1, 3, 4, 6, 5, 7, 8, 9];
+
+    fn u32s(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    }
+
+    fn i16s(bytes: &[u8]) -> Vec<i16> {
+        bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect()
+    }
+
+    fn i16_bytes(values: &[i16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn decode_index_buffer_v0() {
+        let data = [
+            0xe0, 0xf0, 0x10, 0xfe, 0xff, 0xf0, 0x0c, 0xff, 0x02, 0x02, 0x02, 0x00, 0x76, 0x87,
+            0x56, 0x67, 0x78, 0xa9, 0x86, 0x65, 0x89, 0x68, 0x98, 0x01, 0x69, 0x00, 0x00,
+        ];
+        let decoded = decode(&data, 12, 4, "TRIANGLES", "NONE").unwrap();
+        assert_eq!(u32s(&decoded), INDEX_BUFFER);
+
+        let decoded = decode(&data, 12, 2, "TRIANGLES", "NONE").unwrap();
+        assert_eq!(
+            i16s(&decoded),
+            INDEX_BUFFER.iter().map(|i| *i as i16).collect::<Vec<_>>()
+        );
+        assert!(decode(&data[..data.len() - 1], 12, 4, "TRIANGLES", "NONE").is_err());
+    }
+
+    #[test]
+    fn decode_index_buffer_v1() {
+        // The last two triangles reuse the most recent edge and encode the third vertex relative to the last explicit index,
+        // which version 1 of the codec supports through the edge fifo codes 13 and 14
+        let mut data = vec![0xe1, 0xf0, 0x10, 0xfe, 0x0e, 0x0d, 0xf0, 0x0c];
+        data.extend_from_slice(&[0; 16]);
+        let decoded = decode(&data, 15, 4, "TRIANGLES", "NONE").unwrap();
+        assert_eq!(
+            u32s(&decoded),
+            [0, 1, 2, 2, 1, 3, 4, 6, 5, 4, 5, 7, 4, 7, 6]
+        );
+
+        data[0] = 0xe2;
+        assert!(decode(&data, 15, 4, "TRIANGLES", "NONE").is_err());
+    }
+
+    #[test]
+    fn decode_index_sequence() {
+        let data = [
+            0xd1, 0x00, 0x04, 0xcd, 0x01, 0x04, 0x07, 0x98, 0x1f, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let decoded = decode(&data, 6, 4, "INDICES", "NONE").unwrap();
+        assert_eq!(u32s(&decoded), [0, 1, 51, 2, 49, 1000]);
+        assert!(decode(&data, 7, 4, "INDICES", "NONE").is_err());
+    }
+
+    #[test]
+    fn decode_vertex_buffer() {
+        // The four bytes of each vertex are encoded with the zero, 2-bit, 4-bit and raw byte group modes respectively,
+        // followed by the tail containing the base vertex [10, 20, 30, 40]
+        let mut data = vec![0xa0];
+        data.extend_from_slice(&[0x01, 0x2a, 0x00, 0x00, 0x00]);
+        data.extend_from_slice(&[0x02, 0x04, 0x44, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[0x01, 0x3f, 0x00, 0x00, 0x00, 6, 6, 6]);
+        data.extend_from_slice(&[0x03, 0, 8, 8, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[0; 28]);
+        data.extend_from_slice(&[10, 20, 30, 40]);
+        let decoded = decode(&data, 4, 4, "ATTRIBUTES", "NONE").unwrap();
+        assert_eq!(
+            decoded,
+            [10, 20, 30, 40, 11, 22, 33, 44, 12, 24, 36, 48, 13, 26, 39, 52]
+        );
+        assert!(decode(&data[1..], 4, 4, "ATTRIBUTES", "NONE").is_err());
+    }
+
+    #[test]
+    fn decode_filter_octahedral_8() {
+        let mut data = vec![0, 1, 127, 0, 0, 187, 127, 1];
+        decode_octahedral_i8(&mut data);
+        assert_eq!(data, [0, 1, 127, 0, 0, 159, 82, 1]);
+    }
+
+    #[test]
+    fn decode_filter_octahedral_12() {
+        let mut data = i16_bytes(&[0, 1, 2047, 0, 0, 1870, 2047, 1]);
+        decode_octahedral_i16(&mut data);
+        assert_eq!(i16s(&data), [0, 16, 32767, 0, 0, 32621, 3088, 1]);
+    }
+
+    #[test]
+    fn decode_filter_quaternion() {
+        let mut data = i16_bytes(&[0, 1, 0, 0x7fc, 0, 1870, 0, 0x7fd]);
+        decode_quaternion(&mut data);
+        assert_eq!(i16s(&data), [32767, 0, 11, 0, 0, 25013, 0, 21166]);
+    }
+
+    #[test]
+    fn decode_filter_exponential() {
+        let mut data: Vec<u8> = [0u32, 0xff000003, 0x02fffff7, 0xfe7fffff]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        decode_exponential(&mut data);
+        let values = u32s(&data)
+            .into_iter()
+            .map(f32::from_bits)
+            .collect::<Vec<_>>();
+        assert_eq!(values, [0.0, 1.5, -36.0, 8388607.0 / 4.0]);
+    }
+}
@@ -7,6 +7,7 @@ impl Loaded {
     ///
     /// Deserialize a loaded .obj file resource and .mtl material file resource (if present) into a list of meshes and materials.
     /// It uses the [wavefront-obj](https://crates.io/crates/wavefront_obj/main.rs) crate.
+    /// A material file or texture that failed to load (see [Loaded::failure]) is skipped instead of failing.
     ///
     pub fn obj(
         &mut self,
@@ -18,8 +19,11 @@ impl Loaded {
 
         // Parse materials
         let mut cpu_materials = Vec::new();
-        if let Some(material_library) = obj.material_library {
-            let bytes = self.remove_bytes(p.join(material_library).to_str().unwrap())?;
+        if let Some(material_library) = obj
+            .material_library
+            .filter(|library| self.failure(p.join(library)).is_none())
+        {
+            let bytes = self.remove_bytes(p.join(material_library))?;
             let materials = wavefront_obj::mtl::parse(String::from_utf8(bytes).unwrap())?.materials;
 
             for material in materials {
@@ -39,16 +43,8 @@ impl Loaded {
                     material.color_diffuse
                 };
 
-                let normal_texture = if let Some(ref texture_name) = material.bump_map {
-                    Some(self.image(p.join(texture_name))?)
-                } else {
-                    None
-                };
-                let albedo_texture = if let Some(ref texture_name) = material.diffuse_map {
-                    Some(self.image(p.join(texture_name))?)
-                } else {
-                    None
-                };
+                let normal_texture = self.optional_image(p, &material.bump_map)?;
+                let albedo_texture = self.optional_image(p, &material.diffuse_map)?;
 
                 cpu_materials.push(CpuMaterial {
                     name: material.name,
@@ -158,9 +154,158 @@ impl Loaded {
                     },
                     colors: None,
                     tangents: None,
+                    ..Default::default()
                 });
             }
         }
         Ok((cpu_meshes, cpu_materials))
     }
 }
+
+impl Loaded {
+    fn optional_image(
+        &mut self,
+        directory: &Path,
+        texture_name: &Option<String>,
+    ) -> ThreeDResult<Option<CpuTexture<u8>>> {
+        Ok(match texture_name {
+            Some(texture_name) if self.failure(directory.join(texture_name)).is_none() => {
+                Some(self.image(directory.join(texture_name))?)
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given meshes as a .obj file and, if any materials are given, the materials as a .mtl material library next to it.
+    /// The albedo and normal textures of the materials are saved as .png files next to the .obj file.
+    /// Since the .mtl format does not support physically based materials, the metallic and roughness parameters are saved as the specular color and specular exponent
+    /// in the same way as they are interpreted by [Loaded::obj], and the remaining textures are not saved.
+    ///
+    pub fn save_obj<P: AsRef<Path>>(
+        path: P,
+        cpu_meshes: &[CpuMesh],
+        cpu_materials: &[CpuMaterial],
+    ) -> ThreeDResult<()> {
+        use std::fmt::Write;
+        let path = path.as_ref();
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "mesh".to_string());
+
+        let mut obj = String::new();
+        if !cpu_materials.is_empty() {
+            let mtl_name = format!("{}.mtl", stem);
+            writeln!(obj, "mtllib {}", mtl_name).unwrap();
+            let mut mtl = String::new();
+            for (i, material) in cpu_materials.iter().enumerate() {
+                let albedo = material.albedo.to_rgba_slice();
+                let emissive = material.emissive.to_rgb_slice();
+                let roughness = material.roughness.max(0.001);
+                writeln!(mtl, "newmtl {}", obj_name(&material.name, i)).unwrap();
+                writeln!(mtl, "Ns {}", 1.999 / (roughness * roughness)).unwrap();
+                writeln!(mtl, "Ka 0 0 0").unwrap();
+                writeln!(mtl, "Kd {} {} {}", albedo[0], albedo[1], albedo[2]).unwrap();
+                writeln!(
+                    mtl,
+                    "Ks {} {} {}",
+                    material.metallic, material.metallic, material.metallic
+                )
+                .unwrap();
+                writeln!(mtl, "Ke {} {} {}", emissive[0], emissive[1], emissive[2]).unwrap();
+                writeln!(mtl, "d {}", albedo[3]).unwrap();
+                writeln!(mtl, "illum 2").unwrap();
+                if let Some(ref texture) = material.albedo_texture {
+                    let name = format!("{}_{}_albedo.png", stem, i);
+                    Saver::save_file(path.with_file_name(&name), &image_to_png_bytes(texture)?)?;
+                    writeln!(mtl, "map_Kd {}", name).unwrap();
+                }
+                if let Some(ref texture) = material.normal_texture {
+                    let name = format!("{}_{}_normal.png", stem, i);
+                    Saver::save_file(path.with_file_name(&name), &image_to_png_bytes(texture)?)?;
+                    writeln!(mtl, "map_bump {}", name).unwrap();
+                }
+                writeln!(mtl).unwrap();
+            }
+            Saver::save_file(path.with_file_name(mtl_name), mtl.as_bytes())?;
+        }
+
+        // The indices in an .obj file are global, separate for positions, uvs and normals and starts at 1
+        let mut position_offset = 1;
+        let mut uv_offset = 1;
+        let mut normal_offset = 1;
+        for (i, mesh) in cpu_meshes.iter().enumerate() {
+            writeln!(obj, "o {}", obj_name(&mesh.name, i)).unwrap();
+            match mesh.positions {
+                Positions::F32(ref positions) => {
+                    for p in positions.iter() {
+                        writeln!(obj, "v {} {} {}", p.x, p.y, p.z).unwrap();
+                    }
+                }
+                Positions::F64(ref positions) => {
+                    for p in positions.iter() {
+                        writeln!(obj, "v {} {} {}", p.x, p.y, p.z).unwrap();
+                    }
+                }
+            }
+            if let Some(ref uvs) = mesh.uvs {
+                for uv in uvs.iter() {
+                    writeln!(obj, "vt {} {}", uv.x, 1.0 - uv.y).unwrap();
+                }
+            }
+            if let Some(ref normals) = mesh.normals {
+                for n in normals.iter() {
+                    writeln!(obj, "vn {} {} {}", n.x, n.y, n.z).unwrap();
+                }
+            }
+            if let Some(material_index) = mesh
+                .material_name
+                .as_ref()
+                .and_then(|name| cpu_materials.iter().position(|m| &m.name == name))
+            {
+                writeln!(
+                    obj,
+                    "usemtl {}",
+                    obj_name(&cpu_materials[material_index].name, material_index)
+                )
+                .unwrap();
+            }
+            let vertex = |index: usize| {
+                let (p, t, n) = (
+                    index + position_offset,
+                    index + uv_offset,
+                    index + normal_offset,
+                );
+                match (mesh.uvs.is_some(), mesh.normals.is_some()) {
+                    (true, true) => format!("{}/{}/{}", p, t, n),
+                    (true, false) => format!("{}/{}", p, t),
+                    (false, true) => format!("{}//{}", p, n),
+                    (false, false) => format!("{}", p),
+                }
+            };
+            mesh.for_each_triangle(|i0, i1, i2| {
+                writeln!(obj, "f {} {} {}", vertex(i0), vertex(i1), vertex(i2)).unwrap();
+            });
+            position_offset += mesh.positions.len();
+            uv_offset += mesh.uvs.as_ref().map(|uvs| uvs.len()).unwrap_or(0);
+            normal_offset += mesh.normals.as_ref().map(|n| n.len()).unwrap_or(0);
+        }
+        Saver::save_file(path, obj.as_bytes())
+    }
+}
+
+///
+/// Returns a name without whitespace, since names in .obj and .mtl files cannot contain whitespace.
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn obj_name(name: &str, index: usize) -> String {
+    if name.is_empty() {
+        format!("unnamed_{}", index)
+    } else {
+        name.split_whitespace().collect::<Vec<_>>().join("_")
+    }
+}
@@ -0,0 +1,502 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize a loaded .ply file resource into a mesh.
+    /// Supports ASCII as well as binary little and big endian .ply files.
+    ///
+    /// The positions, normals, uv coordinates and colors are read from the vertex element
+    /// and the positions are returned as [Positions::F64] if they are stored as doubles in the file.
+    /// Faces with more than three vertices are triangulated. If the file does not contain any faces, for example if it contains a point cloud,
+    /// the returned mesh does not have any indices.
+    ///
+    pub fn ply(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuMesh> {
+        let name = path
+            .as_ref()
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let bytes = self.get_bytes(path)?;
+        let (format, elements, body_start) = parse_header(bytes)?;
+        let mut reader = PlyReader {
+            format,
+            bytes,
+            position: body_start,
+        };
+
+        let mut mesh = CpuMesh {
+            name,
+            ..Default::default()
+        };
+        for element in elements.iter() {
+            match element.name.as_str() {
+                "vertex" => read_vertices(&mut reader, element, &mut mesh)?,
+                "face" => read_faces(&mut reader, element, &mut mesh)?,
+                _ => {
+                    for _ in 0..element.count {
+                        for property in element.properties.iter() {
+                            reader.read_value(property)?;
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(Indices::U32(ref indices)) = mesh.indices {
+            let vertex_count = mesh.positions.len();
+            if let Some(index) = indices.iter().find(|i| **i as usize >= vertex_count) {
+                Err(IOError::Ply(format!(
+                    "face index {} is out of range for {} vertices",
+                    index, vertex_count
+                )))?;
+            }
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given mesh as a binary little endian .ply file.
+    /// The positions are saved as doubles if they are [Positions::F64] and otherwise as floats.
+    /// The normals, uv coordinates and colors are saved if present.
+    /// The faces are only saved if the mesh has indices, so a mesh without indices is saved as a point cloud.
+    ///
+    pub fn save_ply<P: AsRef<Path>>(path: P, cpu_mesh: &CpuMesh) -> ThreeDResult<()> {
+        use std::fmt::Write;
+        let mut header = String::new();
+        writeln!(header, "ply").unwrap();
+        writeln!(header, "format binary_little_endian 1.0").unwrap();
+        writeln!(header, "comment Created by three-d").unwrap();
+        writeln!(header, "element vertex {}", cpu_mesh.positions.len()).unwrap();
+        let position_type = match cpu_mesh.positions {
+            Positions::F32(_) => "float",
+            Positions::F64(_) => "double",
+        };
+        for name in ["x", "y", "z"] {
+            writeln!(header, "property {} {}", position_type, name).unwrap();
+        }
+        if cpu_mesh.normals.is_some() {
+            for name in ["nx", "ny", "nz"] {
+                writeln!(header, "property float {}", name).unwrap();
+            }
+        }
+        if cpu_mesh.uvs.is_some() {
+            for name in ["s", "t"] {
+                writeln!(header, "property float {}", name).unwrap();
+            }
+        }
+        if cpu_mesh.colors.is_some() {
+            for name in ["red", "green", "blue", "alpha"] {
+                writeln!(header, "property uchar {}", name).unwrap();
+            }
+        }
+        if let Some(ref indices) = cpu_mesh.indices {
+            writeln!(header, "element face {}", indices.len() / 3).unwrap();
+            writeln!(header, "property list uchar uint vertex_indices").unwrap();
+        }
+        writeln!(header, "end_header").unwrap();
+
+        let mut bytes = header.into_bytes();
+        for i in 0..cpu_mesh.positions.len() {
+            match cpu_mesh.positions {
+                Positions::F32(ref positions) => {
+                    for value in [positions[i].x, positions[i].y, positions[i].z] {
+                        bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                }
+                Positions::F64(ref positions) => {
+                    for value in [positions[i].x, positions[i].y, positions[i].z] {
+                        bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                }
+            }
+            if let Some(ref normals) = cpu_mesh.normals {
+                for value in [normals[i].x, normals[i].y, normals[i].z] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            if let Some(ref uvs) = cpu_mesh.uvs {
+                // The t coordinate in a .ply file points up while the v coordinate in three-d points down
+                for value in [uvs[i].x, 1.0 - uvs[i].y] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            if let Some(ref colors) = cpu_mesh.colors {
+                bytes.extend_from_slice(&[colors[i].r, colors[i].g, colors[i].b, colors[i].a]);
+            }
+        }
+        if let Some(ref indices) = cpu_mesh.indices {
+            for triangle in indices.to_u32().chunks_exact(3) {
+                bytes.push(3);
+                for index in triangle {
+                    bytes.extend_from_slice(&index.to_le_bytes());
+                }
+            }
+        }
+        Saver::save_file(path, &bytes)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PlyType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyType {
+    fn from_name(name: &str) -> ThreeDResult<Self> {
+        Ok(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => Err(IOError::Ply(format!("unknown property type '{}'", name)))?,
+        })
+    }
+
+    fn is_float(&self) -> bool {
+        *self == Self::F32 || *self == Self::F64
+    }
+}
+
+struct PlyProperty {
+    name: String,
+    data_type: PlyType,
+    /// The type of the count if this is a list property.
+    count_type: Option<PlyType>,
+}
+
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+impl PlyElement {
+    fn property_index(&self, names: &[&str]) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|p| p.count_type.is_none() && names.contains(&p.name.as_str()))
+    }
+}
+
+fn parse_header(bytes: &[u8]) -> ThreeDResult<(PlyFormat, Vec<PlyElement>, usize)> {
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    let mut position = 0;
+    let mut first = true;
+    loop {
+        let end = bytes[position..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or_else(|| IOError::Ply("missing end_header".to_string()))?;
+        let line = String::from_utf8_lossy(&bytes[position..position + end]);
+        position += end + 1;
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        if first {
+            if words != ["ply"] {
+                Err(IOError::Ply("not a .ply file".to_string()))?;
+            }
+            first = false;
+            continue;
+        }
+        match words.as_slice() {
+            ["format", name, _] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => Err(IOError::Ply(format!("unknown format '{}'", name)))?,
+                })
+            }
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| IOError::Ply(format!("invalid element count '{}'", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, data_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| IOError::Ply("property before element".to_string()))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    data_type: PlyType::from_name(data_type)?,
+                    count_type: Some(PlyType::from_name(count_type)?),
+                }),
+            ["property", data_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| IOError::Ply("property before element".to_string()))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    data_type: PlyType::from_name(data_type)?,
+                    count_type: None,
+                }),
+            ["end_header"] => break,
+            _ => {}
+        }
+    }
+    let format = format.ok_or_else(|| IOError::Ply("missing format".to_string()))?;
+    Ok((format, elements, position))
+}
+
+struct PlyReader<'a> {
+    format: PlyFormat,
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PlyReader<'a> {
+    fn read(&mut self, data_type: PlyType) -> ThreeDResult<f64> {
+        if self.format == PlyFormat::Ascii {
+            while self
+                .bytes
+                .get(self.position)
+                .map(|b| b.is_ascii_whitespace())
+                .unwrap_or(false)
+            {
+                self.position += 1;
+            }
+            let start = self.position;
+            while self
+                .bytes
+                .get(self.position)
+                .map(|b| !b.is_ascii_whitespace())
+                .unwrap_or(false)
+            {
+                self.position += 1;
+            }
+            let word = std::str::from_utf8(&self.bytes[start..self.position]).unwrap_or("");
+            return Ok(word
+                .parse::<f64>()
+                .map_err(|_| IOError::Ply(format!("invalid value '{}'", word)))?);
+        }
+
+        let size = match data_type {
+            PlyType::I8 | PlyType::U8 => 1,
+            PlyType::I16 | PlyType::U16 => 2,
+            PlyType::I32 | PlyType::U32 | PlyType::F32 => 4,
+            PlyType::F64 => 8,
+        };
+        let mut data = [0u8; 8];
+        data[..size].copy_from_slice(
+            self.bytes
+                .get(self.position..self.position + size)
+                .ok_or_else(|| IOError::Ply("unexpected end of file".to_string()))?,
+        );
+        self.position += size;
+        if self.format == PlyFormat::BinaryBigEndian {
+            data[..size].reverse();
+        }
+        let d = data;
+        Ok(match data_type {
+            PlyType::I8 => d[0] as i8 as f64,
+            PlyType::U8 => d[0] as f64,
+            PlyType::I16 => i16::from_le_bytes([d[0], d[1]]) as f64,
+            PlyType::U16 => u16::from_le_bytes([d[0], d[1]]) as f64,
+            PlyType::I32 => i32::from_le_bytes([d[0], d[1], d[2], d[3]]) as f64,
+            PlyType::U32 => u32::from_le_bytes([d[0], d[1], d[2], d[3]]) as f64,
+            PlyType::F32 => f32::from_le_bytes([d[0], d[1], d[2], d[3]]) as f64,
+            PlyType::F64 => f64::from_le_bytes(d),
+        })
+    }
+
+    ///
+    /// Reads the given property and returns its value or, if the property is a list, the first value in the list.
+    ///
+    fn read_value(&mut self, property: &PlyProperty) -> ThreeDResult<f64> {
+        if let Some(count_type) = property.count_type {
+            let count = self.read(count_type)? as usize;
+            let mut first = 0.0;
+            for i in 0..count {
+                let value = self.read(property.data_type)?;
+                if i == 0 {
+                    first = value;
+                }
+            }
+            Ok(first)
+        } else {
+            self.read(property.data_type)
+        }
+    }
+
+    ///
+    /// Reads all values of the given property into the given buffer, which is cleared first.
+    ///
+    fn read_values(&mut self, property: &PlyProperty, values: &mut Vec<f64>) -> ThreeDResult<()> {
+        values.clear();
+        if let Some(count_type) = property.count_type {
+            let count = self.read(count_type)? as usize;
+            for _ in 0..count {
+                values.push(self.read(property.data_type)?);
+            }
+        } else {
+            values.push(self.read(property.data_type)?);
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the number of bytes left to read, which is an upper bound for the number of elements left in the file.
+    ///
+    fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+}
+
+fn read_vertices(
+    reader: &mut PlyReader,
+    element: &PlyElement,
+    mesh: &mut CpuMesh,
+) -> ThreeDResult<()> {
+    let position = [
+        element.property_index(&["x"]),
+        element.property_index(&["y"]),
+        element.property_index(&["z"]),
+    ];
+    let normal = [
+        element.property_index(&["nx"]),
+        element.property_index(&["ny"]),
+        element.property_index(&["nz"]),
+    ];
+    let uv = [
+        element.property_index(&["s", "u", "texture_u"]),
+        element.property_index(&["t", "v", "texture_v"]),
+    ];
+    let color = [
+        element.property_index(&["red", "r", "diffuse_red"]),
+        element.property_index(&["green", "g", "diffuse_green"]),
+        element.property_index(&["blue", "b", "diffuse_blue"]),
+    ];
+    let alpha = element.property_index(&["alpha", "a", "diffuse_alpha"]);
+    let has_positions = position.iter().all(|i| i.is_some());
+    let has_normals = normal.iter().all(|i| i.is_some());
+    let has_uvs = uv.iter().all(|i| i.is_some());
+    let has_colors = color.iter().all(|i| i.is_some());
+    if !has_positions {
+        Err(IOError::Ply("missing vertex positions".to_string()))?;
+    }
+    let is_double = position
+        .iter()
+        .any(|i| element.properties[i.unwrap()].data_type == PlyType::F64);
+    let color_value = |values: &[f64], index: usize| {
+        if element.properties[index].data_type.is_float() {
+            (values[index] * 255.0).round().clamp(0.0, 255.0) as u8
+        } else {
+            values[index].clamp(0.0, 255.0) as u8
+        }
+    };
+
+    let mut positions = Vec::with_capacity(element.count.min(reader.remaining()));
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut colors = Vec::new();
+    let mut values = vec![0.0; element.properties.len()];
+    for _ in 0..element.count {
+        for (i, property) in element.properties.iter().enumerate() {
+            values[i] = reader.read_value(property)?;
+        }
+        let value = |index: Option<usize>| values[index.unwrap()];
+        positions.push(Vector3::new(
+            value(position[0]),
+            value(position[1]),
+            value(position[2]),
+        ));
+        if has_normals {
+            normals.push(vec3(
+                value(normal[0]) as f32,
+                value(normal[1]) as f32,
+                value(normal[2]) as f32,
+            ));
+        }
+        if has_uvs {
+            uvs.push(vec2(value(uv[0]) as f32, 1.0 - value(uv[1]) as f32));
+        }
+        if has_colors {
+            colors.push(Color::new(
+                color_value(&values, color[0].unwrap()),
+                color_value(&values, color[1].unwrap()),
+                color_value(&values, color[2].unwrap()),
+                alpha.map(|a| color_value(&values, a)).unwrap_or(255),
+            ));
+        }
+    }
+    mesh.positions = if is_double {
+        Positions::F64(positions)
+    } else {
+        Positions::F32(
+            positions
+                .into_iter()
+                .map(|p| vec3(p.x as f32, p.y as f32, p.z as f32))
+                .collect(),
+        )
+    };
+    mesh.normals = if has_normals { Some(normals) } else { None };
+    mesh.uvs = if has_uvs { Some(uvs) } else { None };
+    mesh.colors = if has_colors { Some(colors) } else { None };
+    Ok(())
+}
+
+fn read_faces(
+    reader: &mut PlyReader,
+    element: &PlyElement,
+    mesh: &mut CpuMesh,
+) -> ThreeDResult<()> {
+    let vertex_indices = element
+        .properties
+        .iter()
+        .position(|p| {
+            p.count_type.is_some() && (p.name == "vertex_indices" || p.name == "vertex_index")
+        })
+        .ok_or_else(|| IOError::Ply("missing face vertex indices".to_string()))?;
+    let index = |value: f64| {
+        if value >= 0.0 && value <= u32::MAX as f64 && value.fract() == 0.0 {
+            Ok(value as u32)
+        } else {
+            Err(IOError::Ply(format!("invalid face index {}", value)))
+        }
+    };
+    let mut indices = Vec::with_capacity(element.count.min(reader.remaining()) * 3);
+    let mut values = Vec::new();
+    for _ in 0..element.count {
+        for (i, property) in element.properties.iter().enumerate() {
+            if i == vertex_indices {
+                reader.read_values(property, &mut values)?;
+                // Triangulate the polygon as a triangle fan
+                for j in 2..values.len() {
+                    indices.push(index(values[0])?);
+                    indices.push(index(values[j - 1])?);
+                    indices.push(index(values[j])?);
+                }
+            } else {
+                reader.read_value(property)?;
+            }
+        }
+    }
+    if !indices.is_empty() {
+        mesh.indices = Some(Indices::U32(indices));
+    }
+    Ok(())
+}
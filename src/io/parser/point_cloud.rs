@@ -0,0 +1,253 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+///
+/// A point cloud loaded from for example a LiDAR scan, see [Loaded::las], [Loaded::xyz] and [Loaded::pts].
+///
+/// The positions of the points in [CpuPointCloud::mesh] are stored as [Positions::F64] relative to [CpuPointCloud::origin],
+/// which is the center of the bounding box of the points. Since the positions are close to zero, they can be converted to `f32`,
+/// for example using [Positions::to_f32] to create [Sprites](crate::Sprites) or the transformations of an [InstancedMesh](crate::InstancedMesh), without losing precision.
+///
+#[derive(Debug)]
+pub struct CpuPointCloud {
+    /// A mesh with the positions and, if available, the colors of the points, but without any indices.
+    pub mesh: CpuMesh,
+    /// The position which is subtracted from the original position of each point.
+    /// Add this to the positions of the mesh to get the original positions, for example the geographic coordinates.
+    pub origin: Vector3<f64>,
+    /// The intensity of each point, if available in the file. The values are not normalized, so the range depends on the scanner.
+    pub intensities: Option<Vec<f32>>,
+}
+
+impl Loaded {
+    ///
+    /// Deserialize a loaded uncompressed .las file resource into a point cloud.
+    /// Supports LAS version 1.0 to 1.4 and point data record formats 0 to 10. Compressed .laz files are not supported.
+    ///
+    /// The colors of point data record formats with RGB values are converted to 8 bits per channel
+    /// and the intensity is returned as the 16-bit value stored in the file.
+    ///
+    pub fn las(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuPointCloud> {
+        let name = file_name(path.as_ref());
+        let bytes = self.get_bytes(path)?;
+        if bytes.len() < 227 || &bytes[0..4] != b"LASF" {
+            Err(IOError::PointCloud(
+                "the file is not a .las file".to_string(),
+            ))?;
+        }
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let f64_at = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+        let header_size = u16_at(94) as usize;
+        let point_offset = u32_at(96) as usize;
+        let point_format = bytes[104];
+        let record_length = u16_at(105) as usize;
+        // LAS 1.4 stores the number of points as a 64-bit value and sets the legacy 32-bit value to zero for large files
+        let point_count = if header_size >= 255 && bytes.len() >= 255 && u32_at(107) == 0 {
+            usize::try_from(u64::from_le_bytes(bytes[247..255].try_into().unwrap()))
+                .unwrap_or(usize::MAX)
+        } else {
+            u32_at(107) as usize
+        };
+        let scale = Vector3::new(f64_at(131), f64_at(139), f64_at(147));
+        let offset = Vector3::new(f64_at(155), f64_at(163), f64_at(171));
+
+        if point_format & 0x80 != 0 {
+            Err(IOError::PointCloud(
+                "compressed .laz files are not supported".to_string(),
+            ))?;
+        }
+        let color_offset = match point_format {
+            0 | 1 | 4 | 6 | 9 => None,
+            2 => Some(20),
+            3 | 5 => Some(28),
+            7 | 8 | 10 => Some(30),
+            _ => Err(IOError::PointCloud(format!(
+                "the point data record format {} is not supported",
+                point_format
+            )))?,
+        };
+        let minimum_length = color_offset.map(|o| o + 6).unwrap_or(14);
+        let point_end = point_count
+            .checked_mul(record_length)
+            .and_then(|length| length.checked_add(point_offset))
+            .filter(|end| record_length >= minimum_length && *end <= bytes.len())
+            .ok_or_else(|| {
+                IOError::PointCloud("the file does not contain the expected point data".to_string())
+            })?;
+
+        let mut positions = Vec::with_capacity(point_count);
+        let mut intensities = Vec::with_capacity(point_count);
+        let mut colors = Vec::with_capacity(if color_offset.is_some() {
+            point_count
+        } else {
+            0
+        });
+        for record in bytes[point_offset..point_end].chunks_exact(record_length) {
+            let coordinate = |i: usize| i32::from_le_bytes(record[i..i + 4].try_into().unwrap());
+            positions.push(Vector3::new(
+                coordinate(0) as f64 * scale.x + offset.x,
+                coordinate(4) as f64 * scale.y + offset.y,
+                coordinate(8) as f64 * scale.z + offset.z,
+            ));
+            intensities.push(u16::from_le_bytes([record[12], record[13]]) as f32);
+            if let Some(o) = color_offset {
+                colors.push([
+                    u16::from_le_bytes([record[o], record[o + 1]]),
+                    u16::from_le_bytes([record[o + 2], record[o + 3]]),
+                    u16::from_le_bytes([record[o + 4], record[o + 5]]),
+                ]);
+            }
+        }
+
+        // The colors should be stored with 16 bits per channel, but some files use 8 bits per channel
+        let eight_bit = colors.iter().flatten().all(|c| *c <= 255);
+        let colors = if colors.is_empty() {
+            None
+        } else {
+            Some(
+                colors
+                    .into_iter()
+                    .map(|[r, g, b]| {
+                        let convert = |c: u16| if eight_bit { c as u8 } else { (c >> 8) as u8 };
+                        Color::new_opaque(convert(r), convert(g), convert(b))
+                    })
+                    .collect(),
+            )
+        };
+        Ok(point_cloud(name, positions, colors, Some(intensities)))
+    }
+
+    ///
+    /// Deserialize a loaded ASCII .xyz file resource into a point cloud.
+    ///
+    /// Each line contains the position of a point followed by optional values separated by spaces, tabs, commas or semicolons.
+    /// The number of values on the first line determines the layout: `x y z`, `x y z intensity`, `x y z r g b` or `x y z intensity r g b`,
+    /// where the colors are in the range 0 to 255. Empty lines and comments starting with `#` or `//` are skipped.
+    ///
+    pub fn xyz(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuPointCloud> {
+        let name = file_name(path.as_ref());
+        let (positions, colors, intensities) = parse_ascii(self.get_bytes(path)?)?;
+        Ok(point_cloud(name, positions, colors, intensities))
+    }
+
+    ///
+    /// Deserialize a loaded ASCII .pts file resource, as exported from for example Leica scanners, into a point cloud.
+    ///
+    /// The file consists of one or more scans, each starting with a line containing the number of points in the scan, followed by one line per point
+    /// with the same layout as in [Loaded::xyz], typically `x y z intensity r g b`.
+    ///
+    pub fn pts(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuPointCloud> {
+        self.xyz(path)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// The positions, colors and intensities of the points in a point cloud.
+type Points = (Vec<Vector3<f64>>, Option<Vec<Color>>, Option<Vec<f32>>);
+
+///
+/// Parses an ASCII point cloud with one point per line, where lines with a single value, for example the point count in a .pts file, are skipped.
+///
+fn parse_ascii(bytes: &[u8]) -> ThreeDResult<Points> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut intensities = Vec::new();
+    let mut value_count = None;
+    let mut values = Vec::with_capacity(7);
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        values.clear();
+        for value in line
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|v| !v.is_empty())
+        {
+            values.push(value.parse::<f64>().map_err(|_| {
+                IOError::PointCloud(format!(
+                    "invalid value {} on line {}",
+                    value,
+                    line_number + 1
+                ))
+            })?);
+        }
+        if values.len() == 1 {
+            continue;
+        }
+        let count = *value_count.get_or_insert(values.len());
+        if values.len() < count || !matches!(count, 3 | 4 | 6 | 7) {
+            Err(IOError::PointCloud(format!(
+                "expected {} values on line {} but found {}",
+                if matches!(count, 3 | 4 | 6 | 7) {
+                    count.to_string()
+                } else {
+                    "3, 4, 6 or 7".to_string()
+                },
+                line_number + 1,
+                values.len()
+            )))?;
+        }
+        positions.push(Vector3::new(values[0], values[1], values[2]));
+        if count == 4 || count == 7 {
+            intensities.push(values[3] as f32);
+        }
+        if count >= 6 {
+            let color = |v: f64| v.clamp(0.0, 255.0).round() as u8;
+            colors.push(Color::new_opaque(
+                color(values[count - 3]),
+                color(values[count - 2]),
+                color(values[count - 1]),
+            ));
+        }
+    }
+    Ok((
+        positions,
+        Some(colors).filter(|c| !c.is_empty()),
+        Some(intensities).filter(|i| !i.is_empty()),
+    ))
+}
+
+///
+/// Constructs a point cloud where the positions are moved so that the center of their bounding box is at the origin.
+///
+fn point_cloud(
+    name: String,
+    mut positions: Vec<Vector3<f64>>,
+    colors: Option<Vec<Color>>,
+    intensities: Option<Vec<f32>>,
+) -> CpuPointCloud {
+    let origin = if positions.is_empty() {
+        Vector3::new(0.0, 0.0, 0.0)
+    } else {
+        let mut min = Vector3::new(f64::MAX, f64::MAX, f64::MAX);
+        let mut max = Vector3::new(f64::MIN, f64::MIN, f64::MIN);
+        for p in positions.iter() {
+            min = Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        0.5 * (min + max)
+    };
+    for p in positions.iter_mut() {
+        *p -= origin;
+    }
+    CpuPointCloud {
+        mesh: CpuMesh {
+            name,
+            positions: Positions::F64(positions),
+            colors,
+            ..Default::default()
+        },
+        origin,
+        intensities,
+    }
+}
@@ -0,0 +1,160 @@
+use crate::core::*;
+use crate::io::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize a loaded binary or ASCII .stl file resource into a mesh.
+    ///
+    /// An .stl file contains a list of separate triangles, so by default the returned mesh has no indices and three vertices per triangle.
+    /// If `weld_vertices` is true, vertices with exactly the same position are merged into one vertex and the mesh is returned with indices instead.
+    /// If `compute_normals` is true, the normals are computed from the triangles, see [CpuMesh::compute_normals],
+    /// which gives smooth normals if the vertices are welded and flat normals otherwise.
+    /// The facet normals stored in the file are ignored since they are often missing or inconsistent with the triangles.
+    ///
+    pub fn stl(
+        &mut self,
+        path: impl AsRef<Path>,
+        weld_vertices: bool,
+        compute_normals: bool,
+    ) -> ThreeDResult<CpuMesh> {
+        let name = path
+            .as_ref()
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let bytes = self.get_bytes(path)?;
+        let positions = if is_binary(bytes) {
+            parse_binary(bytes)
+        } else {
+            parse_ascii(bytes)?
+        };
+
+        let mut mesh = if weld_vertices {
+            let mut map: HashMap<[u32; 3], u32> = HashMap::new();
+            let mut welded_positions = Vec::new();
+            let mut indices = Vec::with_capacity(positions.len());
+            for p in positions {
+                let index = *map
+                    .entry([p.x.to_bits(), p.y.to_bits(), p.z.to_bits()])
+                    .or_insert_with(|| {
+                        welded_positions.push(p);
+                        welded_positions.len() as u32 - 1
+                    });
+                indices.push(index);
+            }
+            CpuMesh {
+                name,
+                positions: Positions::F32(welded_positions),
+                indices: Some(Indices::U32(indices)),
+                ..Default::default()
+            }
+        } else {
+            CpuMesh {
+                name,
+                positions: Positions::F32(positions),
+                ..Default::default()
+            }
+        };
+        if compute_normals {
+            mesh.compute_normals();
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the triangles of the given mesh as a binary .stl file.
+    /// Only the positions are saved since that is all the .stl format supports, the facet normals are computed from the positions.
+    ///
+    pub fn save_stl<P: AsRef<Path>>(path: P, cpu_mesh: &CpuMesh) -> ThreeDResult<()> {
+        let positions = cpu_mesh.positions.to_f32();
+        let mut triangles = Vec::new();
+        cpu_mesh.for_each_triangle(|i0, i1, i2| {
+            triangles.push([positions[i0], positions[i1], positions[i2]]);
+        });
+
+        let mut bytes = vec![0u8; 80];
+        let header = b"Binary STL created by three-d";
+        bytes[..header.len()].copy_from_slice(header);
+        bytes.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+        for [p0, p1, p2] in triangles {
+            let normal = (p1 - p0).cross(p2 - p0);
+            let normal = if normal.magnitude2() > 0.0 {
+                normal.normalize()
+            } else {
+                normal
+            };
+            for v in [normal, p0, p1, p2] {
+                for value in [v.x, v.y, v.z] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            // Attribute byte count
+            bytes.extend_from_slice(&[0, 0]);
+        }
+        Saver::save_file(path, &bytes)
+    }
+}
+
+///
+/// Returns whether the given .stl file is binary. An ASCII file starts with `solid`, but so do some binary files,
+/// so the size of the binary file given by the triangle count in the header is checked as well.
+///
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.len() >= 84 {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        if bytes.len() == 84 + count * 50 {
+            return true;
+        }
+    }
+    !bytes.starts_with(b"solid")
+}
+
+fn parse_binary(bytes: &[u8]) -> Vec<Vec3> {
+    let count = bytes.len().saturating_sub(84) / 50;
+    let value = |offset: usize| {
+        f32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    let mut positions = Vec::with_capacity(count * 3);
+    for triangle in 0..count {
+        // Skip the facet normal
+        let offset = 84 + triangle * 50 + 12;
+        for vertex in 0..3 {
+            let offset = offset + vertex * 12;
+            positions.push(vec3(value(offset), value(offset + 4), value(offset + 8)));
+        }
+    }
+    positions
+}
+
+fn parse_ascii(bytes: &[u8]) -> ThreeDResult<Vec<Vec3>> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut positions = Vec::new();
+    let mut words = text.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "vertex" {
+            let mut value = || -> ThreeDResult<f32> {
+                let word = words.next().unwrap_or("");
+                Ok(word
+                    .parse::<f32>()
+                    .map_err(|_| IOError::Stl(format!("invalid vertex coordinate '{}'", word)))?)
+            };
+            positions.push(vec3(value()?, value()?, value()?));
+        }
+    }
+    if positions.len() % 3 != 0 {
+        Err(IOError::Stl(
+            "the number of vertices is not divisible by three".to_string(),
+        ))?;
+    }
+    Ok(positions)
+}
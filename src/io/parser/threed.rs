@@ -0,0 +1,584 @@
+use crate::core::*;
+use crate::io::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The first bytes of every .3d file.
+const MAGIC_NUMBER: [u8; 4] = *b"3DIO";
+/// The version of the .3d format written by [Saver::save_3d]. Must be increased whenever the format changes.
+const VERSION: u32 = 3;
+/// The size of the header, ie. the magic number followed by the version.
+const HEADER_SIZE: usize = 8;
+
+impl Loaded {
+    ///
+    /// Deserialize a loaded .3d file resource into a list of meshes and materials.
+    /// The .3d format is a compact binary format native to three-d which is fast to load since it requires no parsing,
+    /// use [Saver::save_3d] to create one, for example from meshes and materials loaded from a .gltf or .obj file.
+    /// All textures are embedded in the .3d file, so no other files have to be loaded.
+    ///
+    pub fn three_d(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> ThreeDResult<(Vec<CpuMesh>, Vec<CpuMaterial>)> {
+        let bytes = self.get_bytes(path)?;
+        if bytes.len() < HEADER_SIZE || bytes[..4] != MAGIC_NUMBER {
+            Err(IOError::ThreeDInvalidHeader)?;
+        }
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if version != VERSION {
+            Err(IOError::ThreeDUnsupportedVersion(version, VERSION))?;
+        }
+        let file: ThreeDFile = bincode::deserialize(&bytes[HEADER_SIZE..])?;
+        Ok((
+            file.meshes.into_iter().map(|m| m.into()).collect(),
+            file.materials
+                .into_iter()
+                .map(|m| m.try_into())
+                .collect::<Result<_, IOError>>()?,
+        ))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given meshes and materials, including all textures, as a single .3d file which can be loaded using [Loaded::three_d].
+    ///
+    pub fn save_3d<P: AsRef<Path>>(
+        path: P,
+        cpu_meshes: &[CpuMesh],
+        cpu_materials: &[CpuMaterial],
+    ) -> ThreeDResult<()> {
+        let file = ThreeDFile {
+            meshes: cpu_meshes.iter().map(|m| m.into()).collect(),
+            materials: cpu_materials.iter().map(|m| m.into()).collect(),
+        };
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.extend_from_slice(&MAGIC_NUMBER);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, &file)?;
+        Saver::save_file(path, &bytes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDFile {
+    meshes: Vec<ThreeDMesh>,
+    materials: Vec<ThreeDMaterial>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum ThreeDPositions {
+    F32(Vec<[f32; 3]>),
+    F64(Vec<[f64; 3]>),
+}
+
+#[derive(Serialize, Deserialize)]
+enum ThreeDIndices {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDMorphTarget {
+    position_deltas: Option<Vec<[f32; 3]>>,
+    normal_deltas: Option<Vec<[f32; 3]>>,
+    tangent_deltas: Option<Vec<[f32; 3]>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDMesh {
+    name: String,
+    material_name: Option<String>,
+    positions: ThreeDPositions,
+    indices: Option<ThreeDIndices>,
+    normals: Option<Vec<[f32; 3]>>,
+    tangents: Option<Vec<[f32; 4]>>,
+    uvs: Option<Vec<[f32; 2]>>,
+    secondary_uvs: Option<Vec<[f32; 2]>>,
+    colors: Option<Vec<[u8; 4]>>,
+    joint_indices: Option<Vec<[u16; 4]>>,
+    joint_weights: Option<Vec<[f32; 4]>>,
+    skin_name: Option<String>,
+    morph_targets: Vec<ThreeDMorphTarget>,
+    morph_weights: Vec<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDTexture {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: u16,
+    mip_maps: Vec<Vec<u8>>,
+    min_filter: u8,
+    mag_filter: u8,
+    mip_map_filter: Option<u8>,
+    wrap_s: u8,
+    wrap_t: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDTextureTransform {
+    offset: [f32; 2],
+    rotation: f32,
+    scale: [f32; 2],
+    uv_set: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreeDMaterial {
+    name: String,
+    albedo: [u8; 4],
+    albedo_texture: Option<ThreeDTexture>,
+    albedo_texture_transform: ThreeDTextureTransform,
+    metallic: f32,
+    roughness: f32,
+    occlusion_metallic_roughness_texture: Option<ThreeDTexture>,
+    metallic_roughness_texture: Option<ThreeDTexture>,
+    metallic_roughness_texture_transform: ThreeDTextureTransform,
+    occlusion_strength: f32,
+    occlusion_texture: Option<ThreeDTexture>,
+    occlusion_texture_transform: ThreeDTextureTransform,
+    normal_scale: f32,
+    normal_texture: Option<ThreeDTexture>,
+    normal_texture_transform: ThreeDTextureTransform,
+    emissive: [u8; 4],
+    emissive_texture: Option<ThreeDTexture>,
+    emissive_texture_transform: ThreeDTextureTransform,
+    emissive_strength: f32,
+    clearcoat: f32,
+    clearcoat_texture: Option<ThreeDTexture>,
+    clearcoat_texture_transform: ThreeDTextureTransform,
+    clearcoat_roughness: f32,
+    clearcoat_roughness_texture: Option<ThreeDTexture>,
+    clearcoat_roughness_texture_transform: ThreeDTextureTransform,
+    clearcoat_normal_scale: f32,
+    clearcoat_normal_texture: Option<ThreeDTexture>,
+    clearcoat_normal_texture_transform: ThreeDTextureTransform,
+    sheen_color: [u8; 4],
+    sheen_color_texture: Option<ThreeDTexture>,
+    sheen_color_texture_transform: ThreeDTextureTransform,
+    sheen_roughness: f32,
+    sheen_roughness_texture: Option<ThreeDTexture>,
+    sheen_roughness_texture_transform: ThreeDTextureTransform,
+    transmission: f32,
+    transmission_texture: Option<ThreeDTexture>,
+    transmission_texture_transform: ThreeDTextureTransform,
+    index_of_refraction: f32,
+    alpha_cutout: Option<f32>,
+    is_transparent: Option<bool>,
+    cull: u8,
+    lighting_model: u8,
+}
+
+fn to_arrays<T: Copy + Into<A>, A>(values: &[T]) -> Vec<A> {
+    values.iter().map(|v| (*v).into()).collect()
+}
+
+fn from_arrays<A: Copy + Into<T>, T>(values: Vec<A>) -> Vec<T> {
+    values.into_iter().map(|v| v.into()).collect()
+}
+
+fn color_to_array(color: &Color) -> [u8; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+fn color_from_array(color: [u8; 4]) -> Color {
+    Color::new(color[0], color[1], color[2], color[3])
+}
+
+impl From<&CpuMesh> for ThreeDMesh {
+    fn from(mesh: &CpuMesh) -> Self {
+        Self {
+            name: mesh.name.clone(),
+            material_name: mesh.material_name.clone(),
+            positions: match mesh.positions {
+                Positions::F32(ref positions) => ThreeDPositions::F32(to_arrays(positions)),
+                Positions::F64(ref positions) => ThreeDPositions::F64(to_arrays(positions)),
+            },
+            indices: mesh.indices.as_ref().map(|indices| match indices {
+                Indices::U8(ref indices) => ThreeDIndices::U8(indices.clone()),
+                Indices::U16(ref indices) => ThreeDIndices::U16(indices.clone()),
+                Indices::U32(ref indices) => ThreeDIndices::U32(indices.clone()),
+            }),
+            normals: mesh.normals.as_deref().map(to_arrays),
+            tangents: mesh.tangents.as_deref().map(to_arrays),
+            uvs: mesh.uvs.as_deref().map(to_arrays),
+            secondary_uvs: mesh.secondary_uvs.as_deref().map(to_arrays),
+            colors: mesh
+                .colors
+                .as_ref()
+                .map(|colors| colors.iter().map(color_to_array).collect()),
+            joint_indices: mesh.joint_indices.as_deref().map(to_arrays),
+            joint_weights: mesh.joint_weights.as_deref().map(to_arrays),
+            skin_name: mesh.skin_name.clone(),
+            morph_targets: mesh
+                .morph_targets
+                .iter()
+                .map(|target| ThreeDMorphTarget {
+                    position_deltas: target.position_deltas.as_deref().map(to_arrays),
+                    normal_deltas: target.normal_deltas.as_deref().map(to_arrays),
+                    tangent_deltas: target.tangent_deltas.as_deref().map(to_arrays),
+                })
+                .collect(),
+            morph_weights: mesh.morph_weights.clone(),
+        }
+    }
+}
+
+impl From<ThreeDMesh> for CpuMesh {
+    fn from(mesh: ThreeDMesh) -> Self {
+        Self {
+            name: mesh.name,
+            material_name: mesh.material_name,
+            positions: match mesh.positions {
+                ThreeDPositions::F32(positions) => Positions::F32(from_arrays(positions)),
+                ThreeDPositions::F64(positions) => Positions::F64(from_arrays(positions)),
+            },
+            indices: mesh.indices.map(|indices| match indices {
+                ThreeDIndices::U8(indices) => Indices::U8(indices),
+                ThreeDIndices::U16(indices) => Indices::U16(indices),
+                ThreeDIndices::U32(indices) => Indices::U32(indices),
+            }),
+            normals: mesh.normals.map(from_arrays),
+            tangents: mesh.tangents.map(from_arrays),
+            uvs: mesh.uvs.map(from_arrays),
+            secondary_uvs: mesh.secondary_uvs.map(from_arrays),
+            colors: mesh
+                .colors
+                .map(|colors| colors.into_iter().map(color_from_array).collect()),
+            joint_indices: mesh.joint_indices.map(from_arrays),
+            joint_weights: mesh.joint_weights.map(from_arrays),
+            skin_name: mesh.skin_name,
+            morph_targets: mesh
+                .morph_targets
+                .into_iter()
+                .map(|target| CpuMorphTarget {
+                    position_deltas: target.position_deltas.map(from_arrays),
+                    normal_deltas: target.normal_deltas.map(from_arrays),
+                    tangent_deltas: target.tangent_deltas.map(from_arrays),
+                })
+                .collect(),
+            morph_weights: mesh.morph_weights,
+        }
+    }
+}
+
+fn format_to_u16(format: Format) -> u16 {
+    match format {
+        Format::Compressed(compressed) => match compressed {
+            CompressedFormat::Bc1Rgb => 5,
+            CompressedFormat::Bc1Rgba => 6,
+            CompressedFormat::Bc2 => 7,
+            CompressedFormat::Bc3 => 8,
+            CompressedFormat::Bc4 => 9,
+            CompressedFormat::Bc5 => 10,
+            CompressedFormat::Bc6h => 11,
+            CompressedFormat::Bc7 => 12,
+            CompressedFormat::Etc2Rgb => 13,
+            CompressedFormat::Etc2Rgba1 => 14,
+            CompressedFormat::Etc2Rgba => 15,
+            CompressedFormat::EacR => 16,
+            CompressedFormat::EacRg => 17,
+            CompressedFormat::Astc {
+                block_width,
+                block_height,
+            } => 0x100 | (block_width as u16) << 4 | block_height as u16,
+        },
+        _ => format.color_channel_count() as u16,
+    }
+}
+
+fn format_from_u16(format: u16) -> Result<Format, IOError> {
+    Ok(Format::Compressed(match format {
+        1 => return Ok(Format::R),
+        2 => return Ok(Format::RG),
+        3 => return Ok(Format::RGB),
+        4 => return Ok(Format::RGBA),
+        5 => CompressedFormat::Bc1Rgb,
+        6 => CompressedFormat::Bc1Rgba,
+        7 => CompressedFormat::Bc2,
+        8 => CompressedFormat::Bc3,
+        9 => CompressedFormat::Bc4,
+        10 => CompressedFormat::Bc5,
+        11 => CompressedFormat::Bc6h,
+        12 => CompressedFormat::Bc7,
+        13 => CompressedFormat::Etc2Rgb,
+        14 => CompressedFormat::Etc2Rgba1,
+        15 => CompressedFormat::Etc2Rgba,
+        16 => CompressedFormat::EacR,
+        17 => CompressedFormat::EacRg,
+        _ if format & !0xff == 0x100 => CompressedFormat::Astc {
+            block_width: (format >> 4 & 0xf) as u8,
+            block_height: (format & 0xf) as u8,
+        },
+        _ => return Err(IOError::ThreeDUnknownFormat(format)),
+    }))
+}
+
+fn interpolation_to_u8(interpolation: Interpolation) -> u8 {
+    match interpolation {
+        Interpolation::Nearest => 0,
+        Interpolation::Linear => 1,
+    }
+}
+
+fn interpolation_from_u8(interpolation: u8) -> Interpolation {
+    match interpolation {
+        0 => Interpolation::Nearest,
+        _ => Interpolation::Linear,
+    }
+}
+
+fn wrapping_to_u8(wrapping: Wrapping) -> u8 {
+    match wrapping {
+        Wrapping::Repeat => 0,
+        Wrapping::MirroredRepeat => 1,
+        Wrapping::ClampToEdge => 2,
+    }
+}
+
+fn wrapping_from_u8(wrapping: u8) -> Wrapping {
+    match wrapping {
+        1 => Wrapping::MirroredRepeat,
+        2 => Wrapping::ClampToEdge,
+        _ => Wrapping::Repeat,
+    }
+}
+
+impl From<&CpuTexture<u8>> for ThreeDTexture {
+    fn from(texture: &CpuTexture<u8>) -> Self {
+        Self {
+            data: texture.data.clone(),
+            width: texture.width,
+            height: texture.height,
+            format: format_to_u16(texture.format),
+            mip_maps: texture.mip_maps.clone(),
+            min_filter: interpolation_to_u8(texture.min_filter),
+            mag_filter: interpolation_to_u8(texture.mag_filter),
+            mip_map_filter: texture.mip_map_filter.map(interpolation_to_u8),
+            wrap_s: wrapping_to_u8(texture.wrap_s),
+            wrap_t: wrapping_to_u8(texture.wrap_t),
+        }
+    }
+}
+
+impl TryFrom<ThreeDTexture> for CpuTexture<u8> {
+    type Error = IOError;
+    fn try_from(texture: ThreeDTexture) -> Result<Self, IOError> {
+        Ok(Self {
+            data: texture.data,
+            width: texture.width,
+            height: texture.height,
+            format: format_from_u16(texture.format)?,
+            mip_maps: texture.mip_maps,
+            min_filter: interpolation_from_u8(texture.min_filter),
+            mag_filter: interpolation_from_u8(texture.mag_filter),
+            mip_map_filter: texture.mip_map_filter.map(interpolation_from_u8),
+            wrap_s: wrapping_from_u8(texture.wrap_s),
+            wrap_t: wrapping_from_u8(texture.wrap_t),
+        })
+    }
+}
+
+impl From<&TextureTransform> for ThreeDTextureTransform {
+    fn from(transform: &TextureTransform) -> Self {
+        Self {
+            offset: transform.offset.into(),
+            rotation: transform.rotation,
+            scale: transform.scale.into(),
+            uv_set: transform.uv_set,
+        }
+    }
+}
+
+impl From<ThreeDTextureTransform> for TextureTransform {
+    fn from(transform: ThreeDTextureTransform) -> Self {
+        Self {
+            offset: transform.offset.into(),
+            rotation: transform.rotation,
+            scale: transform.scale.into(),
+            uv_set: transform.uv_set,
+        }
+    }
+}
+
+impl From<&CpuMaterial> for ThreeDMaterial {
+    fn from(material: &CpuMaterial) -> Self {
+        Self {
+            name: material.name.clone(),
+            albedo: color_to_array(&material.albedo),
+            albedo_texture: material.albedo_texture.as_ref().map(|t| t.into()),
+            albedo_texture_transform: (&material.albedo_texture_transform).into(),
+            metallic: material.metallic,
+            roughness: material.roughness,
+            occlusion_metallic_roughness_texture: material
+                .occlusion_metallic_roughness_texture
+                .as_ref()
+                .map(|t| t.into()),
+            metallic_roughness_texture: material
+                .metallic_roughness_texture
+                .as_ref()
+                .map(|t| t.into()),
+            metallic_roughness_texture_transform: (&material.metallic_roughness_texture_transform)
+                .into(),
+            occlusion_strength: material.occlusion_strength,
+            occlusion_texture: material.occlusion_texture.as_ref().map(|t| t.into()),
+            occlusion_texture_transform: (&material.occlusion_texture_transform).into(),
+            normal_scale: material.normal_scale,
+            normal_texture: material.normal_texture.as_ref().map(|t| t.into()),
+            normal_texture_transform: (&material.normal_texture_transform).into(),
+            emissive: color_to_array(&material.emissive),
+            emissive_texture: material.emissive_texture.as_ref().map(|t| t.into()),
+            emissive_texture_transform: (&material.emissive_texture_transform).into(),
+            emissive_strength: material.emissive_strength,
+            clearcoat: material.clearcoat,
+            clearcoat_texture: material.clearcoat_texture.as_ref().map(|t| t.into()),
+            clearcoat_texture_transform: (&material.clearcoat_texture_transform).into(),
+            clearcoat_roughness: material.clearcoat_roughness,
+            clearcoat_roughness_texture: material
+                .clearcoat_roughness_texture
+                .as_ref()
+                .map(|t| t.into()),
+            clearcoat_roughness_texture_transform: (&material
+                .clearcoat_roughness_texture_transform)
+                .into(),
+            clearcoat_normal_scale: material.clearcoat_normal_scale,
+            clearcoat_normal_texture: material.clearcoat_normal_texture.as_ref().map(|t| t.into()),
+            clearcoat_normal_texture_transform: (&material.clearcoat_normal_texture_transform)
+                .into(),
+            sheen_color: color_to_array(&material.sheen_color),
+            sheen_color_texture: material.sheen_color_texture.as_ref().map(|t| t.into()),
+            sheen_color_texture_transform: (&material.sheen_color_texture_transform).into(),
+            sheen_roughness: material.sheen_roughness,
+            sheen_roughness_texture: material.sheen_roughness_texture.as_ref().map(|t| t.into()),
+            sheen_roughness_texture_transform: (&material.sheen_roughness_texture_transform).into(),
+            transmission: material.transmission,
+            transmission_texture: material.transmission_texture.as_ref().map(|t| t.into()),
+            transmission_texture_transform: (&material.transmission_texture_transform).into(),
+            index_of_refraction: material.index_of_refraction,
+            alpha_cutout: material.alpha_cutout,
+            is_transparent: material.is_transparent,
+            cull: match material.cull {
+                Cull::None => 0,
+                Cull::Back => 1,
+                Cull::Front => 2,
+                Cull::FrontAndBack => 3,
+            },
+            lighting_model: match material.lighting_model {
+                LightingModel::Phong => 0,
+                LightingModel::Blinn => 1,
+                LightingModel::Cook(NormalDistributionFunction::Blinn, _) => 2,
+                LightingModel::Cook(NormalDistributionFunction::Beckmann, _) => 3,
+                LightingModel::Cook(NormalDistributionFunction::TrowbridgeReitzGGX, _) => 4,
+            },
+        }
+    }
+}
+
+impl TryFrom<ThreeDMaterial> for CpuMaterial {
+    type Error = IOError;
+    fn try_from(material: ThreeDMaterial) -> Result<Self, IOError> {
+        Ok(Self {
+            name: material.name,
+            albedo: color_from_array(material.albedo),
+            albedo_texture: material.albedo_texture.map(|t| t.try_into()).transpose()?,
+            albedo_texture_transform: material.albedo_texture_transform.into(),
+            metallic: material.metallic,
+            roughness: material.roughness,
+            occlusion_metallic_roughness_texture: material
+                .occlusion_metallic_roughness_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            metallic_roughness_texture: material
+                .metallic_roughness_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            metallic_roughness_texture_transform: material
+                .metallic_roughness_texture_transform
+                .into(),
+            occlusion_strength: material.occlusion_strength,
+            occlusion_texture: material
+                .occlusion_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            occlusion_texture_transform: material.occlusion_texture_transform.into(),
+            normal_scale: material.normal_scale,
+            normal_texture: material.normal_texture.map(|t| t.try_into()).transpose()?,
+            normal_texture_transform: material.normal_texture_transform.into(),
+            emissive: color_from_array(material.emissive),
+            emissive_texture: material
+                .emissive_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            emissive_texture_transform: material.emissive_texture_transform.into(),
+            emissive_strength: material.emissive_strength,
+            clearcoat: material.clearcoat,
+            clearcoat_texture: material
+                .clearcoat_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            clearcoat_texture_transform: material.clearcoat_texture_transform.into(),
+            clearcoat_roughness: material.clearcoat_roughness,
+            clearcoat_roughness_texture: material
+                .clearcoat_roughness_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            clearcoat_roughness_texture_transform: material
+                .clearcoat_roughness_texture_transform
+                .into(),
+            clearcoat_normal_scale: material.clearcoat_normal_scale,
+            clearcoat_normal_texture: material
+                .clearcoat_normal_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            clearcoat_normal_texture_transform: material.clearcoat_normal_texture_transform.into(),
+            sheen_color: color_from_array(material.sheen_color),
+            sheen_color_texture: material
+                .sheen_color_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            sheen_color_texture_transform: material.sheen_color_texture_transform.into(),
+            sheen_roughness: material.sheen_roughness,
+            sheen_roughness_texture: material
+                .sheen_roughness_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            sheen_roughness_texture_transform: material.sheen_roughness_texture_transform.into(),
+            transmission: material.transmission,
+            transmission_texture: material
+                .transmission_texture
+                .map(|t| t.try_into())
+                .transpose()?,
+            transmission_texture_transform: material.transmission_texture_transform.into(),
+            index_of_refraction: material.index_of_refraction,
+            alpha_cutout: material.alpha_cutout,
+            is_transparent: material.is_transparent,
+            cull: match material.cull {
+                1 => Cull::Back,
+                2 => Cull::Front,
+                3 => Cull::FrontAndBack,
+                _ => Cull::None,
+            },
+            lighting_model: match material.lighting_model {
+                0 => LightingModel::Phong,
+                2 => LightingModel::Cook(
+                    NormalDistributionFunction::Blinn,
+                    GeometryFunction::SmithSchlickGGX,
+                ),
+                3 => LightingModel::Cook(
+                    NormalDistributionFunction::Beckmann,
+                    GeometryFunction::SmithSchlickGGX,
+                ),
+                4 => LightingModel::Cook(
+                    NormalDistributionFunction::TrowbridgeReitzGGX,
+                    GeometryFunction::SmithSchlickGGX,
+                ),
+                _ => LightingModel::Blinn,
+            },
+        })
+    }
+}
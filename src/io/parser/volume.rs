@@ -0,0 +1,490 @@
+use crate::core::*;
+use crate::io::*;
+use std::io::Read;
+use std::path::Path;
+
+///
+/// The type of the values stored in a volume file.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeDataType {
+    /// 8-bit unsigned integers.
+    U8,
+    /// 16-bit unsigned integers.
+    U16,
+    /// 32-bit floating point values.
+    F32,
+}
+
+impl VolumeDataType {
+    fn byte_size(&self) -> usize {
+        match self {
+            VolumeDataType::U8 => 1,
+            VolumeDataType::U16 => 2,
+            VolumeDataType::F32 => 4,
+        }
+    }
+}
+
+///
+/// Describes the layout of the data in a raw volume file without a header, see [Loaded::raw_volume].
+/// The values are stored with the channels of each voxel next to each other, then voxel by voxel along the width, then row by row along the height and finally slice by slice along the depth.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct RawVolumeLayout {
+    /// The number of voxels along the width.
+    pub width: u32,
+    /// The number of voxels along the height.
+    pub height: u32,
+    /// The number of voxels along the depth, for example the number of slices.
+    pub depth: u32,
+    /// The number of channels of each voxel, between 1 and 4.
+    pub channel_count: u32,
+    /// The type of the values.
+    pub data_type: VolumeDataType,
+    /// Whether the values are stored with the most significant byte first.
+    pub big_endian: bool,
+    /// The number of bytes to skip at the start of the file or, if `None`, the data is assumed to be at the end of the file.
+    pub header_size: Option<usize>,
+    /// The distance between the centers of two neighbouring voxels along the width, height and depth.
+    pub spacing: Vec3,
+}
+
+impl Default for RawVolumeLayout {
+    fn default() -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            depth: 1,
+            channel_count: 1,
+            data_type: VolumeDataType::U8,
+            big_endian: false,
+            header_size: Some(0),
+            spacing: vec3(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// How the data in a volume file is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Raw,
+    Gzip,
+    Zlib,
+    Ascii,
+}
+
+/// The information parsed from the header of a volume file.
+struct Header {
+    layout: RawVolumeLayout,
+    encoding: Encoding,
+    /// The path to the data file relative to the header or `None` if the data follows the header.
+    data_file: Option<String>,
+    /// The position in the file where the header ends.
+    header_end: usize,
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded volume file at the given path into a [CpuTexture3D] which can be used to construct a [Texture3D].
+    ///
+    /// Supports .nrrd files, .nhdr files with a detached data file, .mha files and .mhd files with a detached data file (MetaImage).
+    /// The data can be stored as raw or compressed (gzip for NRRD and zlib for MetaImage) 8-bit unsigned integers, 16-bit unsigned integers or 32-bit floats with up to 4 channels per voxel,
+    /// NRRD files furthermore support ASCII encoded data.
+    /// A detached data file must also be loaded. The values are converted to the data type `T` without normalization,
+    /// for example a value of 1000 stored as a 16-bit integer is converted to 1000.0 if `T` is `f32`.
+    /// The spacing between voxels stored in the file is returned in [CpuTexture3D::spacing].
+    ///
+    /// # Errors
+    /// Returns an error if the file has an unsupported extension, data type or encoding or if it is not a 3D volume.
+    ///
+    pub fn volume<T: TextureDataType>(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> ThreeDResult<CpuTexture3D<T>> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let header = {
+            let bytes = self.get_bytes(path)?;
+            match extension.as_str() {
+                "nrrd" | "nhdr" => parse_nrrd_header(bytes)?,
+                "mha" | "mhd" => parse_meta_image_header(bytes)?,
+                _ => Err(IOError::Volume(format!(
+                    "the file extension .{} is not supported",
+                    extension
+                )))?,
+            }
+        };
+        let bytes = match header.data_file {
+            Some(ref data_file) => {
+                let data_path = path.parent().unwrap_or(Path::new("")).join(data_file);
+                self.get_bytes(data_path)?
+            }
+            None => &self.get_bytes(path)?[header.header_end..],
+        };
+        decode(bytes, &header.layout, header.encoding)
+    }
+
+    ///
+    /// Deserialize the loaded raw volume file at the given path, which contains the data described by the given layout, into a [CpuTexture3D].
+    /// This can for example be used for a DICOM series which has been exported as a single raw file.
+    /// The values are converted to the data type `T` without normalization, see [Loaded::volume].
+    ///
+    /// # Errors
+    /// Returns an error if the file is too short to contain the data described by the layout.
+    ///
+    pub fn raw_volume<T: TextureDataType>(
+        &mut self,
+        path: impl AsRef<Path>,
+        layout: &RawVolumeLayout,
+    ) -> ThreeDResult<CpuTexture3D<T>> {
+        decode(self.get_bytes(path)?, layout, Encoding::Raw)
+    }
+}
+
+///
+/// Parses the header of a .nrrd or .nhdr file, see <http://teem.sourceforge.net/nrrd/format.html>.
+///
+fn parse_nrrd_header(bytes: &[u8]) -> ThreeDResult<Header> {
+    if !bytes.starts_with(b"NRRD") {
+        Err(IOError::Volume("the file is not a NRRD file".to_string()))?;
+    }
+    let mut layout = RawVolumeLayout::default();
+    let mut encoding = Encoding::Raw;
+    let mut data_file = None;
+    let mut sizes: Vec<u32> = Vec::new();
+    let mut spacings = Vec::new();
+    let mut position = 0;
+    // The header ends with an empty line, or at the end of the file in a detached header
+    while position < bytes.len() {
+        let end = bytes[position..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|i| position + i)
+            .unwrap_or(bytes.len());
+        let line = String::from_utf8_lossy(&bytes[position..end])
+            .trim_end_matches('\r')
+            .to_string();
+        position = end + 1;
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with('#') || line.starts_with("NRRD") {
+            continue;
+        }
+        let (key, value) = match line.split_once(": ") {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            // Key/value pairs are separated by ":=" and not needed
+            None => continue,
+        };
+        match key.as_str() {
+            "type" => {
+                layout.data_type = match value {
+                    "uchar" | "unsigned char" | "uint8" | "uint8_t" => VolumeDataType::U8,
+                    "ushort" | "unsigned short" | "unsigned short int" | "uint16" | "uint16_t" => {
+                        VolumeDataType::U16
+                    }
+                    "float" => VolumeDataType::F32,
+                    _ => Err(IOError::Volume(format!(
+                        "the data type {} is not supported",
+                        value
+                    )))?,
+                }
+            }
+            "sizes" => sizes = parse_numbers(value)?,
+            "spacings" => spacings = parse_numbers(value)?,
+            "space directions" => {
+                spacings = value
+                    .split_whitespace()
+                    .map(|direction| {
+                        if direction == "none" {
+                            Ok(f32::NAN)
+                        } else {
+                            let vector: Vec<f32> =
+                                parse_numbers(direction.trim_matches(|c| c == '(' || c == ')'))?;
+                            Ok(vector.iter().map(|v| v * v).sum::<f32>().sqrt())
+                        }
+                    })
+                    .collect::<ThreeDResult<Vec<_>>>()?
+            }
+            "encoding" => {
+                encoding = match value {
+                    "raw" => Encoding::Raw,
+                    "gzip" | "gz" => Encoding::Gzip,
+                    "ascii" | "text" | "txt" => Encoding::Ascii,
+                    _ => Err(IOError::Volume(format!(
+                        "the encoding {} is not supported",
+                        value
+                    )))?,
+                }
+            }
+            "endian" => layout.big_endian = value == "big",
+            "byte skip" => {
+                layout.header_size = match value.parse::<i64>() {
+                    Ok(-1) => None,
+                    Ok(skip) if usize::try_from(skip).is_ok() => Some(skip as usize),
+                    _ => Err(IOError::Volume(format!("invalid byte skip {}", value)))?,
+                }
+            }
+            "data file" | "datafile" => {
+                if value.starts_with("LIST") || value.contains(' ') {
+                    Err(IOError::Volume(
+                        "data stored in multiple files is not supported".to_string(),
+                    ))?;
+                }
+                data_file = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    // A fourth axis is the channels of each voxel
+    let (channel_count, sizes) = match sizes.as_slice() {
+        [width, height, depth] => (1, [*width, *height, *depth]),
+        [channels, width, height, depth] => (*channels, [*width, *height, *depth]),
+        _ => Err(IOError::Volume(format!(
+            "expected a volume with 3 dimensions but the volume has {} dimensions",
+            sizes.len()
+        )))?,
+    };
+    // NRRD uses "nan" or "none" for the spacing of the channel axis
+    let spacings: Vec<f32> = spacings
+        .into_iter()
+        .filter(|spacing| !spacing.is_nan())
+        .collect();
+    layout.width = sizes[0];
+    layout.height = sizes[1];
+    layout.depth = sizes[2];
+    layout.channel_count = channel_count;
+    if spacings.len() == 3 {
+        layout.spacing = vec3(spacings[0], spacings[1], spacings[2]);
+    }
+    Ok(Header {
+        layout,
+        encoding,
+        data_file,
+        header_end: position.min(bytes.len()),
+    })
+}
+
+///
+/// Parses the header of a .mha or .mhd MetaImage file, see <https://itk.org/Wiki/ITK/MetaIO/Documentation>.
+///
+fn parse_meta_image_header(bytes: &[u8]) -> ThreeDResult<Header> {
+    let mut layout = RawVolumeLayout::default();
+    let mut encoding = Encoding::Raw;
+    let mut data_file = None;
+    let mut dimension_count = 3;
+    let mut sizes = Vec::new();
+    let mut position = 0;
+    // The ElementDataFile field is always the last field of the header
+    while data_file.is_none() {
+        let end = bytes[position..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|i| position + i)
+            .ok_or_else(|| {
+                IOError::Volume("the header has no ElementDataFile field".to_string())
+            })?;
+        let line = String::from_utf8_lossy(&bytes[position..end]).to_string();
+        position = end + 1;
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        match key {
+            "NDims" => dimension_count = value.parse::<usize>().unwrap_or(0),
+            "DimSize" => sizes = parse_numbers(value)?,
+            "ElementSpacing" => {
+                let spacings: Vec<f32> = parse_numbers(value)?;
+                if spacings.len() == 3 {
+                    layout.spacing = vec3(spacings[0], spacings[1], spacings[2]);
+                }
+            }
+            "ElementNumberOfChannels" => {
+                layout.channel_count = value.parse().unwrap_or(1);
+            }
+            "ElementType" => {
+                layout.data_type = match value {
+                    "MET_UCHAR" => VolumeDataType::U8,
+                    "MET_USHORT" => VolumeDataType::U16,
+                    "MET_FLOAT" => VolumeDataType::F32,
+                    _ => Err(IOError::Volume(format!(
+                        "the data type {} is not supported",
+                        value
+                    )))?,
+                }
+            }
+            "BinaryDataByteOrderMSB" | "ElementByteOrderMSB" => {
+                layout.big_endian = value.eq_ignore_ascii_case("true");
+            }
+            "CompressedData" if value.eq_ignore_ascii_case("true") => {
+                encoding = Encoding::Zlib;
+            }
+            "HeaderSize" => {
+                layout.header_size = match value.parse::<i64>() {
+                    Ok(-1) => None,
+                    Ok(size) if usize::try_from(size).is_ok() => Some(size as usize),
+                    _ => Err(IOError::Volume(format!("invalid header size {}", value)))?,
+                }
+            }
+            "ElementDataFile" => {
+                if value.starts_with("LIST") || value.contains(' ') {
+                    Err(IOError::Volume(
+                        "data stored in multiple files is not supported".to_string(),
+                    ))?;
+                }
+                data_file = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+    if dimension_count != 3 || sizes.len() != 3 {
+        Err(IOError::Volume(format!(
+            "expected a volume with 3 dimensions but the volume has {} dimensions",
+            dimension_count
+        )))?;
+    }
+    layout.width = sizes[0];
+    layout.height = sizes[1];
+    layout.depth = sizes[2];
+    Ok(Header {
+        layout,
+        encoding,
+        data_file: data_file.filter(|file| file != "LOCAL"),
+        header_end: position,
+    })
+}
+
+fn parse_numbers<N: std::str::FromStr>(value: &str) -> ThreeDResult<Vec<N>> {
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<N>()
+                .map_err(|_| IOError::Volume(format!("invalid number {}", v)).into())
+        })
+        .collect()
+}
+
+fn decode<T: TextureDataType>(
+    bytes: &[u8],
+    layout: &RawVolumeLayout,
+    encoding: Encoding,
+) -> ThreeDResult<CpuTexture3D<T>> {
+    let format = match layout.channel_count {
+        1 => Format::R,
+        2 => Format::RG,
+        3 => Format::RGB,
+        4 => Format::RGBA,
+        _ => Err(IOError::Volume(format!(
+            "{} channels per voxel is not supported",
+            layout.channel_count
+        )))?,
+    };
+    let value_count = (layout.width as usize)
+        .checked_mul(layout.height as usize)
+        .and_then(|count| count.checked_mul(layout.depth as usize))
+        .and_then(|count| count.checked_mul(layout.channel_count as usize))
+        .ok_or_else(|| {
+            IOError::Volume(format!(
+                "the volume size {}x{}x{} is too large",
+                layout.width, layout.height, layout.depth
+            ))
+        })?;
+    let mut decompressed = Vec::new();
+    let bytes = match encoding {
+        Encoding::Raw => bytes,
+        Encoding::Gzip => {
+            flate2::read::GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| IOError::Volume(format!("invalid gzip data: {}", e)))?;
+            &decompressed
+        }
+        Encoding::Zlib => {
+            flate2::read::ZlibDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| IOError::Volume(format!("invalid zlib data: {}", e)))?;
+            &decompressed
+        }
+        Encoding::Ascii => {
+            let values: Vec<f32> = parse_numbers(&String::from_utf8_lossy(bytes))?;
+            if values.len() < value_count {
+                Err(IOError::Volume(format!(
+                    "expected {} values but the file contains {} values",
+                    value_count,
+                    values.len()
+                )))?;
+            }
+            return Ok(volume(
+                layout,
+                format,
+                values
+                    .into_iter()
+                    .take(value_count)
+                    .map(T::from_f32)
+                    .collect(),
+            ));
+        }
+    };
+
+    let byte_count = value_count
+        .checked_mul(layout.data_type.byte_size())
+        .ok_or_else(|| {
+            IOError::Volume(format!(
+                "the volume size {}x{}x{} is too large",
+                layout.width, layout.height, layout.depth
+            ))
+        })?;
+    let start = match layout.header_size {
+        Some(header_size) => header_size,
+        None => bytes.len().saturating_sub(byte_count),
+    };
+    let data = start
+        .checked_add(byte_count)
+        .and_then(|end| bytes.get(start..end))
+        .ok_or_else(|| {
+            IOError::Volume(format!(
+                "expected {} bytes of data after {} bytes of header but the file contains {} bytes",
+                byte_count,
+                start,
+                bytes.len()
+            ))
+        })?;
+    let big_endian = layout.big_endian;
+    let values = data
+        .chunks_exact(layout.data_type.byte_size())
+        .map(|b| {
+            T::from_f32(match layout.data_type {
+                VolumeDataType::U8 => b[0] as f32,
+                VolumeDataType::U16 if big_endian => u16::from_be_bytes([b[0], b[1]]) as f32,
+                VolumeDataType::U16 => u16::from_le_bytes([b[0], b[1]]) as f32,
+                VolumeDataType::F32 if big_endian => f32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+                VolumeDataType::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            })
+        })
+        .collect();
+    Ok(volume(layout, format, values))
+}
+
+fn volume<T: TextureDataType>(
+    layout: &RawVolumeLayout,
+    format: Format,
+    data: Vec<T>,
+) -> CpuTexture3D<T> {
+    CpuTexture3D {
+        data,
+        width: layout.width,
+        height: layout.height,
+        depth: layout.depth,
+        format,
+        wrap_s: Wrapping::ClampToEdge,
+        wrap_t: Wrapping::ClampToEdge,
+        wrap_r: Wrapping::ClampToEdge,
+        spacing: layout.spacing,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,227 @@
+use crate::core::*;
+use crate::io::*;
+use crate::window::FrameInput;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+///
+/// The type of files written by a [FrameRecorder].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordingFormat {
+    ///
+    /// A sequence of numbered PNG images, `frame_00000.png`, `frame_00001.png` and so on, which are written to the directory given by the path.
+    /// The directory is created if it does not exist.
+    ///
+    #[cfg(feature = "image-io")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image-io")))]
+    PngSequence,
+    ///
+    /// An uncompressed video in the YUV4MPEG2 format written to the .y4m file given by the path.
+    /// The video can be converted to other formats using for example [ffmpeg](https://ffmpeg.org/), ie. `ffmpeg -i video.y4m video.mp4`.
+    ///
+    Y4m,
+}
+
+///
+/// Records a sequence of frames, for example to produce a video of an animation.
+/// The recorder simulates a fixed time step between frames, so that the result does not depend on how long it takes to render and save each frame.
+///
+/// When rendering to a window, use [FrameRecorder::frame_input] to override the time in the input to each frame and then [FrameRecorder::capture_screen] after rendering.
+/// When rendering with a headless context (see [Context::new]), use [FrameRecorder::headless_frame_input] to construct the input to each frame,
+/// render into a [Texture2D] and add it using [FrameRecorder::capture_texture].
+/// Finally, call [FrameRecorder::finish] when all frames are recorded.
+///
+pub struct FrameRecorder {
+    #[cfg_attr(not(feature = "image-io"), allow(dead_code))]
+    path: PathBuf,
+    format: RecordingFormat,
+    frames_per_second: u32,
+    frame_count: u32,
+    frame_size: Option<(u32, u32)>,
+    writer: Option<std::io::BufWriter<std::fs::File>>,
+}
+
+impl FrameRecorder {
+    ///
+    /// Creates a new recorder which writes the frames to the given path in the given format with the given number of frames per second.
+    ///
+    pub fn new(
+        path: impl AsRef<Path>,
+        format: RecordingFormat,
+        frames_per_second: u32,
+    ) -> ThreeDResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let writer = match format {
+            #[cfg(feature = "image-io")]
+            RecordingFormat::PngSequence => {
+                std::fs::create_dir_all(&path)?;
+                None
+            }
+            RecordingFormat::Y4m => Some(std::io::BufWriter::new(std::fs::File::create(&path)?)),
+        };
+        Ok(Self {
+            path,
+            format,
+            frames_per_second: frames_per_second.max(1),
+            frame_count: 0,
+            frame_size: None,
+            writer,
+        })
+    }
+
+    ///
+    /// Returns the number of frames recorded so far.
+    ///
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    ///
+    /// Returns the simulated time in milliseconds between two frames.
+    ///
+    pub fn time_step(&self) -> f64 {
+        1000.0 / self.frames_per_second as f64
+    }
+
+    ///
+    /// Returns the given frame input where the elapsed and accumulated time is replaced by the simulated time of the next frame.
+    /// The first frame has zero elapsed and accumulated time, then the time is increased by the [time step](FrameRecorder::time_step) for each recorded frame.
+    ///
+    pub fn frame_input(&self, frame_input: FrameInput) -> FrameInput {
+        FrameInput {
+            elapsed_time: if self.frame_count == 0 {
+                0.0
+            } else {
+                self.time_step()
+            },
+            accumulated_time: self.frame_count as f64 * self.time_step(),
+            ..frame_input
+        }
+    }
+
+    ///
+    /// Returns the input to the next frame when rendering without a window, ie. without any events and with the simulated time of the next frame.
+    ///
+    pub fn headless_frame_input(&self, viewport: Viewport) -> FrameInput {
+        self.frame_input(FrameInput {
+            events: Vec::new(),
+            elapsed_time: 0.0,
+            accumulated_time: 0.0,
+            viewport,
+            window_width: viewport.width,
+            window_height: viewport.height,
+            device_pixel_ratio: 1.0,
+            first_frame: self.frame_count == 0,
+        })
+    }
+
+    ///
+    /// Reads the pixels inside the given viewport of the screen and adds them as the next frame.
+    /// Should be called after rendering the frame, but before the render loop swaps the buffers.
+    ///
+    pub fn capture_screen(&mut self, context: &Context, viewport: Viewport) -> ThreeDResult<()> {
+        let pixels = Screen::read_color(context, viewport)?;
+        self.add_frame(&pixels, viewport.width, viewport.height)
+    }
+
+    ///
+    /// Reads the pixels of the given RGBA texture and adds them as the next frame.
+    ///
+    pub fn capture_texture(&mut self, texture: &Texture2D<u8>) -> ThreeDResult<()> {
+        let pixels = texture.read(Viewport::new_at_origo(texture.width(), texture.height()))?;
+        self.add_frame(&pixels, texture.width(), texture.height())
+    }
+
+    ///
+    /// Adds the given RGBA pixels as the next frame. The first row of pixels is the bottom row of the frame,
+    /// which is the case for the pixels returned by [Screen::read_color] and [Texture2D::read].
+    ///
+    /// # Errors
+    /// Returns an error if the number of pixels does not match the given size
+    /// or if the size of the frame is different from the size of the first frame.
+    ///
+    pub fn add_frame(&mut self, pixels: &[u8], width: u32, height: u32) -> ThreeDResult<()> {
+        let expected_length = width as usize * height as usize * 4;
+        if pixels.len() != expected_length {
+            Err(IOError::FrameDataSize(
+                pixels.len(),
+                width,
+                height,
+                expected_length,
+            ))?;
+        }
+        if let Some((first_width, first_height)) = self.frame_size {
+            if (first_width, first_height) != (width, height) {
+                Err(IOError::FrameSizeChanged(
+                    width,
+                    height,
+                    first_width,
+                    first_height,
+                ))?;
+            }
+        }
+        match self.format {
+            #[cfg(feature = "image-io")]
+            RecordingFormat::PngSequence => {
+                Saver::save_pixels(
+                    self.path.join(format!("frame_{:05}.png", self.frame_count)),
+                    pixels,
+                    width,
+                    height,
+                )?;
+            }
+            RecordingFormat::Y4m => {
+                let writer = self.writer.as_mut().unwrap();
+                if self.frame_size.is_none() {
+                    writeln!(
+                        writer,
+                        "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444",
+                        width, height, self.frames_per_second
+                    )?;
+                }
+                writer.write_all(b"FRAME\n")?;
+                writer.write_all(&rgba_to_yuv444(pixels, width as usize, height as usize))?;
+            }
+        }
+        self.frame_size = Some((width, height));
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Finishes the recording and makes sure that everything is written to disk.
+    ///
+    pub fn finish(mut self) -> ThreeDResult<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// Converts RGBA pixels, starting with the bottom row, to the three planes of a YUV 4:4:4 image, starting with the top row,
+/// using the BT.601 limited range conversion which is the default for YUV4MPEG2 files.
+///
+fn rgba_to_yuv444(pixels: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let size = width * height;
+    let mut planes = vec![0u8; 3 * size];
+    for row in 0..height {
+        for column in 0..width {
+            let source = 4 * ((height - row - 1) * width + column);
+            let (r, g, b) = (
+                pixels[source] as f32,
+                pixels[source + 1] as f32,
+                pixels[source + 2] as f32,
+            );
+            let index = row * width + column;
+            planes[index] = (16.0 + (65.738 * r + 129.057 * g + 25.064 * b) / 256.0).round() as u8;
+            planes[size + index] =
+                (128.0 + (-37.945 * r - 74.494 * g + 112.439 * b) / 256.0).round() as u8;
+            planes[2 * size + index] =
+                (128.0 + (112.439 * r - 94.154 * g - 18.285 * b) / 256.0).round() as u8;
+        }
+    }
+    planes
+}
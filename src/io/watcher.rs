@@ -0,0 +1,76 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+///
+/// Watches a set of files on disk for changes, which can be used to reload assets, for example textures, meshes or shader source files, while the application runs.
+/// The watcher compares the modification time and size of the files each time [changed_paths](Self::changed_paths) or [has_changed](Self::has_changed) is called, which is typically once per frame.
+///
+/// To reload resources loaded using [Loading], use [Loading::new_watched] instead.
+/// To reload shader source files, read the files again when they have changed and pass the new source to for example [Context::program],
+/// which compiles a new program since the source has changed.
+///
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<(SystemTime, u64)>)>,
+}
+
+impl FileWatcher {
+    ///
+    /// Starts watching the files at the given paths. Changes to the files made before this call are not reported.
+    ///
+    pub fn new(paths: &[impl AsRef<Path>]) -> Self {
+        Self {
+            files: paths
+                .iter()
+                .map(|path| (path.as_ref().to_path_buf(), version(path.as_ref())))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns the paths to the watched files.
+    ///
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    ///
+    /// Returns the paths to the files that have changed since the last call to this method or [has_changed](Self::has_changed).
+    /// A file that is temporarily missing, for example while an editor replaces it, is reported when it exists again.
+    ///
+    pub fn changed_paths(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last_version) in self.files.iter_mut() {
+            if let Some(current_version) = version(path) {
+                if *last_version != Some(current_version) {
+                    *last_version = Some(current_version);
+                    changed.push(path.clone());
+                }
+            }
+        }
+        changed
+    }
+
+    ///
+    /// Returns true if any of the files have changed since the last call to this method or [changed_paths](Self::changed_paths).
+    ///
+    pub fn has_changed(&mut self) -> bool {
+        !self.changed_paths().is_empty()
+    }
+
+    ///
+    /// Reads all of the watched files from disk and returns the [Loaded] resources.
+    ///
+    pub fn load(&self) -> ThreeDResult<Loaded> {
+        Loader::load_blocking(&self.paths().collect::<Vec<_>>())
+    }
+}
+
+///
+/// Returns the modification time and size of the file at the given path or `None` if the file does not exist.
+///
+fn version(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
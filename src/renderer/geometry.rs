@@ -3,6 +3,8 @@
 //! A geometry together with a [material] can be rendered directly, or combined into an [object] (see [Shape]) that can be used in a render call, for example [render_pass].
 //!
 
+pub use crate::core::{CpuJoint, CpuMorphTarget, CpuSkin};
+
 mod mesh;
 #[doc(inline)]
 pub use mesh::*;
@@ -11,6 +13,10 @@ mod instanced_mesh;
 #[doc(inline)]
 pub use instanced_mesh::*;
 
+mod skinned_mesh;
+#[doc(inline)]
+pub use skinned_mesh::*;
+
 mod sprites;
 #[doc(inline)]
 pub use sprites::*;
@@ -130,6 +136,42 @@ impl<T: Geometry> Geometry for std::rc::Rc<std::cell::RefCell<T>> {
     }
 }
 
+///
+/// Represents a geometry or an object that is placed in the 3D world using a local to world transformation,
+/// for example a [Mesh] or a [Model]. This is required for an object to be part of a [SceneNode] hierarchy.
+///
+pub trait Transformable {
+    ///
+    /// Returns the local to world transformation.
+    ///
+    fn transformation(&self) -> Mat4;
+
+    ///
+    /// Set the local to world transformation.
+    ///
+    fn set_transformation(&mut self, transformation: Mat4);
+}
+
+impl<T: Transformable + ?Sized> Transformable for &mut T {
+    fn transformation(&self) -> Mat4 {
+        (**self).transformation()
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        (**self).set_transformation(transformation)
+    }
+}
+
+impl<T: Transformable + ?Sized> Transformable for Box<T> {
+    fn transformation(&self) -> Mat4 {
+        self.as_ref().transformation()
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        self.as_mut().set_transformation(transformation)
+    }
+}
+
 ///
 /// Represents a 2D geometry that is possible to render with a [Material].
 ///
@@ -211,3 +253,173 @@ impl IndexBuffer {
         })
     }
 }
+
+/// The morph targets of a mesh stored in a texture and blended in the vertex shader.
+struct MorphTargets {
+    texture: Texture2D<f32>,
+    vertex_count: u32,
+    position_stream: Option<u32>,
+    normal_stream: Option<u32>,
+    tangent_stream: Option<u32>,
+    stream_count: u32,
+    weights: Vec<f32>,
+    /// The largest absolute position delta in each direction for each morph target
+    max_position_deltas: Vec<Vec3>,
+}
+
+impl MorphTargets {
+    const TEXTURE_WIDTH: u32 = 2048;
+
+    pub fn new(context: &Context, cpu_mesh: &CpuMesh) -> ThreeDResult<Option<Self>> {
+        let morph_targets = &cpu_mesh.morph_targets;
+        if morph_targets.is_empty() {
+            return Ok(None);
+        }
+        let vertex_count = cpu_mesh.positions.len();
+        let first = &morph_targets[0];
+        let mut stream_count = 0;
+        let mut next_stream = |used: bool| {
+            if used {
+                stream_count += 1;
+                Some(stream_count - 1)
+            } else {
+                None
+            }
+        };
+        let position_stream = next_stream(first.position_deltas.is_some());
+        let normal_stream = next_stream(first.normal_deltas.is_some());
+        let tangent_stream = next_stream(first.tangent_deltas.is_some());
+
+        let texel_count = (morph_targets.len() * stream_count as usize * vertex_count).max(1);
+        let width = Self::TEXTURE_WIDTH.min(texel_count as u32);
+        let height = texel_count.div_ceil(width as usize);
+        let max_texture_size = context.max_texture_size();
+        if height > max_texture_size as usize {
+            Err(CoreError::TooManyMorphTargets(
+                morph_targets.len(),
+                vertex_count,
+                height.min(u32::MAX as usize) as u32,
+                max_texture_size,
+            ))?;
+        }
+        let height = height as u32;
+        let mut data = vec![0.0; (width * height * 4) as usize];
+        let mut max_position_deltas = Vec::new();
+        for (target, morph_target) in morph_targets.iter().enumerate() {
+            let mut max_position_delta = vec3(0.0, 0.0, 0.0);
+            for (stream, deltas) in [
+                (position_stream, &morph_target.position_deltas),
+                (normal_stream, &morph_target.normal_deltas),
+                (tangent_stream, &morph_target.tangent_deltas),
+            ] {
+                if let Some(stream) = stream {
+                    let deltas = deltas.as_ref().ok_or(CoreError::MissingMeshBuffer(
+                        "morph target deltas".to_string(),
+                    ))?;
+                    let offset = (target * stream_count as usize + stream as usize) * vertex_count;
+                    for (vertex, delta) in deltas.iter().take(vertex_count).enumerate() {
+                        let i = 4 * (offset + vertex);
+                        data[i] = delta.x;
+                        data[i + 1] = delta.y;
+                        data[i + 2] = delta.z;
+                        if Some(stream) == position_stream {
+                            max_position_delta.x = max_position_delta.x.max(delta.x.abs());
+                            max_position_delta.y = max_position_delta.y.max(delta.y.abs());
+                            max_position_delta.z = max_position_delta.z.max(delta.z.abs());
+                        }
+                    }
+                }
+            }
+            max_position_deltas.push(max_position_delta);
+        }
+        let mut texture = Texture2D::new_empty(
+            context,
+            width,
+            height,
+            Interpolation::Nearest,
+            Interpolation::Nearest,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+            Format::RGBA,
+        )?;
+        texture.fill(&data)?;
+
+        let mut weights = vec![0.0; morph_targets.len()];
+        for (weight, default_weight) in weights.iter_mut().zip(cpu_mesh.morph_weights.iter()) {
+            *weight = *default_weight;
+        }
+        Ok(Some(Self {
+            texture,
+            vertex_count: vertex_count as u32,
+            position_stream,
+            normal_stream,
+            tangent_stream,
+            stream_count,
+            weights,
+            max_position_deltas,
+        }))
+    }
+
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    pub fn set_weights(&mut self, weights: &[f32]) {
+        for (i, weight) in self.weights.iter_mut().enumerate() {
+            *weight = weights.get(i).cloned().unwrap_or(0.0);
+        }
+    }
+
+    /// Returns an upper bound on the distance a vertex is moved by the morph targets with the current weights
+    pub fn max_position_offset(&self) -> f32 {
+        self.weights
+            .iter()
+            .zip(self.max_position_deltas.iter())
+            .map(|(weight, delta)| weight.abs() * delta.magnitude())
+            .sum()
+    }
+
+    /// Expands the given axis aligned bounding box in local space so that it contains the morphed positions
+    pub fn expand_aabb(&self, aabb: &AxisAlignedBoundingBox) -> AxisAlignedBoundingBox {
+        if aabb.is_empty() {
+            return *aabb;
+        }
+        let mut max_delta = vec3(0.0, 0.0, 0.0);
+        for (weight, delta) in self.weights.iter().zip(self.max_position_deltas.iter()) {
+            max_delta += delta * weight.abs();
+        }
+        AxisAlignedBoundingBox::new_with_positions(&[
+            aabb.min() - max_delta,
+            aabb.max() + max_delta,
+        ])
+    }
+
+    pub fn vertex_shader_defines(&self) -> String {
+        let mut defines = format!(
+            "#define MORPH_TARGET_COUNT {}\n#define MORPH_STREAM_COUNT {}\n",
+            self.weights.len(),
+            self.stream_count
+        );
+        for (name, stream) in [
+            ("MORPH_POSITIONS", self.position_stream),
+            ("MORPH_NORMALS", self.normal_stream),
+            ("MORPH_TANGENTS", self.tangent_stream),
+        ] {
+            if let Some(stream) = stream {
+                defines.push_str(&format!("#define {} {}\n", name, stream));
+            }
+        }
+        defines
+    }
+
+    pub fn use_uniforms(&self, program: &Program) -> ThreeDResult<()> {
+        // The deltas are not used if the material does not need the morphed attributes
+        if program.requires_uniform("morphTexture") {
+            program.use_texture("morphTexture", &self.texture)?;
+            program.use_uniform_int("morphVertexCount", &(self.vertex_count as i32))?;
+            program.use_uniform_array("morphWeights", &self.weights)?;
+        }
+        Ok(())
+    }
+}
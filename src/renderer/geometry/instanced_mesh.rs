@@ -11,6 +11,7 @@ pub struct InstancedMesh {
     normal_buffer: Option<VertexBuffer<Vec3>>,
     tangent_buffer: Option<VertexBuffer<Vec4>>,
     uv_buffer: Option<VertexBuffer<Vec2>>,
+    secondary_uv_buffer: Option<VertexBuffer<Vec2>>,
     color_buffer: Option<VertexBuffer<Color>>,
     index_buffer: Option<IndexBuffer>,
     instance_buffer1: InstanceBuffer<Vec4>,
@@ -62,6 +63,11 @@ impl InstancedMesh {
         } else {
             None
         };
+        let secondary_uv_buffer = if let Some(ref uvs) = cpu_mesh.secondary_uvs {
+            Some(VertexBuffer::new_with_data(context, uvs)?)
+        } else {
+            None
+        };
         let color_buffer = if let Some(ref colors) = cpu_mesh.colors {
             Some(VertexBuffer::new_with_data(context, colors)?)
         } else {
@@ -75,6 +81,7 @@ impl InstancedMesh {
             tangent_buffer,
             index_buffer,
             uv_buffer,
+            secondary_uv_buffer,
             color_buffer,
             instance_buffer1: InstanceBuffer::new(context)?,
             instance_buffer2: InstanceBuffer::new(context)?,
@@ -236,6 +243,15 @@ impl InstancedMesh {
                 .ok_or(CoreError::MissingMeshBuffer("uv coordinates".to_string()))?;
             program.use_vertex_attribute("uv_coordinates", uv_buffer)?;
         }
+        if program.requires_attribute("secondary_uv_coordinates") {
+            let secondary_uv_buffer =
+                self.secondary_uv_buffer
+                    .as_ref()
+                    .ok_or(CoreError::MissingMeshBuffer(
+                        "secondary uv coordinates".to_string(),
+                    ))?;
+            program.use_vertex_attribute("secondary_uv_coordinates", secondary_uv_buffer)?;
+        }
         if program.requires_attribute("normal") {
             let normal_buffer = self
                 .normal_buffer
@@ -295,9 +311,10 @@ impl InstancedMesh {
         let use_normals = fragment_shader_source.find("in vec3 nor;").is_some();
         let use_tangents = fragment_shader_source.find("in vec3 tang;").is_some();
         let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
+        let use_secondary_uvs = fragment_shader_source.find("in vec2 uvs2;").is_some();
         let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
         Ok(format!(
-            "#define INSTANCED\n{}{}{}{}{}{}{}",
+            "#define INSTANCED\n{}{}{}{}{}{}{}{}",
             if use_positions {
                 "#define USE_POSITIONS\n"
             } else {
@@ -317,6 +334,11 @@ impl InstancedMesh {
                 ""
             },
             if use_uvs { "#define USE_UVS\n" } else { "" },
+            if use_secondary_uvs {
+                "#define USE_SECONDARY_UVS\n"
+            } else {
+                ""
+            },
             if use_colors {
                 "#define USE_COLORS\n"
             } else {
@@ -328,6 +350,16 @@ impl InstancedMesh {
     }
 }
 
+impl Transformable for InstancedMesh {
+    fn transformation(&self) -> Mat4 {
+        InstancedMesh::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        InstancedMesh::set_transformation(self, transformation)
+    }
+}
+
 impl Geometry for InstancedMesh {
     fn aabb(&self) -> AxisAlignedBoundingBox {
         self.aabb
@@ -1,4 +1,4 @@
-use super::IndexBuffer;
+use super::{IndexBuffer, MorphTargets};
 use crate::core::*;
 use crate::renderer::*;
 
@@ -15,10 +15,13 @@ pub struct Mesh {
     tangent_buffer: Option<VertexBuffer<Vec4>>,
     /// Buffer with the uv coordinate data, ie. `(u, v)` for each vertex.
     uv_buffer: Option<VertexBuffer<Vec2>>,
+    /// Buffer with the secondary uv coordinate data, ie. `(u, v)` for each vertex.
+    secondary_uv_buffer: Option<VertexBuffer<Vec2>>,
     /// Buffer with the color data, ie. `(r, g, b)` for each vertex.
     color_buffer: Option<VertexBuffer<Color>>,
     /// Buffer with the index data, ie. three contiguous integers define the triangle where each integer is and index into the other vertex buffers.
     index_buffer: Option<IndexBuffer>,
+    morph_targets: Option<MorphTargets>,
     context: Context,
     aabb: AxisAlignedBoundingBox,
     aabb_local: AxisAlignedBoundingBox,
@@ -56,25 +59,35 @@ impl Mesh {
         } else {
             None
         };
+        let secondary_uv_buffer = if let Some(ref uvs) = cpu_mesh.secondary_uvs {
+            Some(VertexBuffer::new_with_data(context, uvs)?)
+        } else {
+            None
+        };
         let color_buffer = if let Some(ref colors) = cpu_mesh.colors {
             Some(VertexBuffer::new_with_data(context, colors)?)
         } else {
             None
         };
-        let aabb = cpu_mesh.compute_aabb();
-        Ok(Self {
+        let morph_targets = MorphTargets::new(context, cpu_mesh)?;
+        let aabb_local = cpu_mesh.compute_aabb();
+        let mut mesh = Self {
             context: context.clone(),
             position_buffer,
             normal_buffer,
             tangent_buffer,
             index_buffer,
             uv_buffer,
+            secondary_uv_buffer,
             color_buffer,
-            aabb,
-            aabb_local: aabb.clone(),
+            morph_targets,
+            aabb: aabb_local,
+            aabb_local,
             transformation: Mat4::identity(),
             texture_transform: Mat3::identity(),
-        })
+        };
+        mesh.update_aabb();
+        Ok(mesh)
     }
 
     pub(in crate::renderer) fn set_transformation_2d(&mut self, transformation: Mat3) {
@@ -110,7 +123,38 @@ impl Mesh {
     ///
     pub fn set_transformation(&mut self, transformation: Mat4) {
         self.transformation = transformation;
-        let mut aabb = self.aabb_local.clone();
+        self.update_aabb();
+    }
+
+    ///
+    /// Returns the weight of each of the morph targets of this mesh, see [CpuMesh::morph_targets].
+    /// Returns an empty slice if the mesh has no morph targets.
+    ///
+    pub fn morph_weights(&self) -> &[f32] {
+        self.morph_targets
+            .as_ref()
+            .map(|morph_targets| morph_targets.weights())
+            .unwrap_or(&[])
+    }
+
+    ///
+    /// Set the weight of each of the morph targets of this mesh, see [CpuMesh::morph_targets].
+    /// The morph targets are blended on the GPU, so the weights can be changed every frame, for example using [AnimationClip::morph_weights].
+    /// Missing weights are set to zero and extra weights are ignored.
+    ///
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        if let Some(ref mut morph_targets) = self.morph_targets {
+            morph_targets.set_weights(weights);
+            self.update_aabb();
+        }
+    }
+
+    fn update_aabb(&mut self) {
+        let mut aabb = if let Some(ref morph_targets) = self.morph_targets {
+            morph_targets.expand_aabb(&self.aabb_local)
+        } else {
+            self.aabb_local
+        };
         aabb.transform(&self.transformation);
         self.aabb = aabb;
     }
@@ -134,9 +178,10 @@ impl Mesh {
         let use_normals = fragment_shader_source.find("in vec3 nor;").is_some();
         let use_tangents = fragment_shader_source.find("in vec3 tang;").is_some();
         let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
+        let use_secondary_uvs = fragment_shader_source.find("in vec2 uvs2;").is_some();
         let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
         Ok(format!(
-            "{}{}{}{}{}{}{}",
+            "{}{}{}{}{}{}{}{}",
             if use_positions {
                 "#define USE_POSITIONS\n"
             } else {
@@ -156,6 +201,11 @@ impl Mesh {
                 ""
             },
             if use_uvs { "#define USE_UVS\n" } else { "" },
+            if use_secondary_uvs {
+                "#define USE_SECONDARY_UVS\n"
+            } else {
+                ""
+            },
             if use_colors {
                 "#define USE_COLORS\n"
             } else {
@@ -167,6 +217,16 @@ impl Mesh {
     }
 }
 
+impl Transformable for Mesh {
+    fn transformation(&self) -> Mat4 {
+        Mesh::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        Mesh::set_transformation(self, transformation)
+    }
+}
+
 impl Geometry for Mesh {
     fn aabb(&self) -> AxisAlignedBoundingBox {
         self.aabb
@@ -180,12 +240,24 @@ impl Geometry for Mesh {
     ) -> ThreeDResult<()> {
         let fragment_shader_source =
             material.fragment_shader_source(self.color_buffer.is_some(), lights);
+        let morph_defines = self
+            .morph_targets
+            .as_ref()
+            .map(|morph_targets| morph_targets.vertex_shader_defines())
+            .unwrap_or_default();
         self.context.program(
-            &Self::vertex_shader_source(&fragment_shader_source)?,
+            &format!(
+                "{}{}",
+                morph_defines,
+                Self::vertex_shader_source(&fragment_shader_source)?
+            ),
             &fragment_shader_source,
             |program| {
                 material.use_uniforms(program, camera, lights)?;
                 program.use_uniform_block("Camera", camera.uniform_buffer());
+                if let Some(ref morph_targets) = self.morph_targets {
+                    morph_targets.use_uniforms(program)?;
+                }
                 program.use_uniform("modelMatrix", &self.transformation)?;
 
                 if program.requires_attribute("position") {
@@ -199,6 +271,16 @@ impl Geometry for Mesh {
                         .ok_or(CoreError::MissingMeshBuffer("uv coordinates".to_string()))?;
                     program.use_vertex_attribute("uv_coordinates", uv_buffer)?;
                 }
+                if program.requires_attribute("secondary_uv_coordinates") {
+                    let secondary_uv_buffer =
+                        self.secondary_uv_buffer
+                            .as_ref()
+                            .ok_or(CoreError::MissingMeshBuffer(
+                                "secondary uv coordinates".to_string(),
+                            ))?;
+                    program
+                        .use_vertex_attribute("secondary_uv_coordinates", secondary_uv_buffer)?;
+                }
                 if program.requires_attribute("normal") {
                     let normal_buffer = self
                         .normal_buffer
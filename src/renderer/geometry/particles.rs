@@ -161,6 +161,16 @@ impl Particles {
     }
 }
 
+impl Transformable for Particles {
+    fn transformation(&self) -> Mat4 {
+        Particles::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        Particles::set_transformation(self, transformation)
+    }
+}
+
 impl Geometry for Particles {
     fn aabb(&self) -> AxisAlignedBoundingBox {
         AxisAlignedBoundingBox::INFINITE
@@ -16,6 +16,40 @@ in vec4 row2;
 in vec4 row3;
 #endif
 
+#ifdef SKINNED
+uniform sampler2D jointTexture;
+in vec4 joint_indices;
+in vec4 joint_weights;
+
+mat4 joint_matrix(float index) {
+    int i = 4 * int(index);
+    return mat4(
+        texelFetch(jointTexture, ivec2(i, 0), 0),
+        texelFetch(jointTexture, ivec2(i + 1, 0), 0),
+        texelFetch(jointTexture, ivec2(i + 2, 0), 0),
+        texelFetch(jointTexture, ivec2(i + 3, 0), 0)
+    );
+}
+#endif
+
+#ifdef MORPH_TARGET_COUNT
+uniform sampler2D morphTexture;
+uniform int morphVertexCount;
+uniform float morphWeights[MORPH_TARGET_COUNT];
+
+vec3 morph_delta(int stream) {
+    int width = textureSize(morphTexture, 0).x;
+    vec3 delta = vec3(0.0);
+    for (int i = 0; i < MORPH_TARGET_COUNT; i++) {
+        if (morphWeights[i] != 0.0) {
+            int index = (i * MORPH_STREAM_COUNT + stream) * morphVertexCount + gl_VertexID;
+            delta += morphWeights[i] * texelFetch(morphTexture, ivec2(index % width, index / width), 0).xyz;
+        }
+    }
+    return delta;
+}
+#endif
+
 #ifdef USE_POSITIONS
 out vec3 pos;
 #endif
@@ -42,6 +76,10 @@ in vec3 tex_transform_row2;
 uniform mat3 textureTransform;
 in vec2 uv_coordinates;
 out vec2 uvs;
+#ifdef USE_SECONDARY_UVS
+in vec2 secondary_uv_coordinates;
+out vec2 uvs2;
+#endif
 #endif
 
 #ifdef USE_COLORS 
@@ -60,17 +98,28 @@ void main()
     transform[2] = vec4(row1.z, row2.z, row3.z, 0.0);
     transform[3] = vec4(row1.w, row2.w, row3.w, 1.0);
     local2World *= transform;
+#endif
+
+#ifdef SKINNED
+    local2World *= joint_weights.x * joint_matrix(joint_indices.x)
+        + joint_weights.y * joint_matrix(joint_indices.y)
+        + joint_weights.z * joint_matrix(joint_indices.z)
+        + joint_weights.w * joint_matrix(joint_indices.w);
+#endif
 
 #ifdef USE_NORMALS
+#if defined(INSTANCED) || defined(SKINNED)
     normalMat = mat3(transpose(inverse(local2World)));
-#endif
 #else
-#ifdef USE_NORMALS
     normalMat = mat3(normalMatrix);
 #endif
 #endif
 
-    vec4 worldPosition = local2World * vec4(position, 1.);
+    vec3 localPosition = position;
+#ifdef MORPH_POSITIONS
+    localPosition += morph_delta(MORPH_POSITIONS);
+#endif
+    vec4 worldPosition = local2World * vec4(localPosition, 1.);
     gl_Position = camera.viewProjection * worldPosition;
 
 #ifdef USE_POSITIONS
@@ -78,10 +127,18 @@ void main()
 #endif
 
 #ifdef USE_NORMALS 
-    nor = normalize(normalMat * normal);
+    vec3 localNormal = normal;
+#ifdef MORPH_NORMALS
+    localNormal += morph_delta(MORPH_NORMALS);
+#endif
+    nor = normalize(normalMat * localNormal);
 
 #ifdef USE_TANGENTS 
-    tang = normalize(normalMat * tangent.xyz);
+    vec3 localTangent = tangent.xyz;
+#ifdef MORPH_TANGENTS
+    localTangent += morph_delta(MORPH_TANGENTS);
+#endif
+    tang = normalize(normalMat * localTangent);
     bitang = normalize(cross(nor, tang) * tangent.w);
 #endif
 
@@ -97,6 +154,9 @@ void main()
     texTransform *= instancedTexTransform;
 #endif
     uvs = (texTransform * vec3(uv_coordinates, 1.0)).xy;
+#ifdef USE_SECONDARY_UVS
+    uvs2 = (texTransform * vec3(secondary_uv_coordinates, 1.0)).xy;
+#endif
 #endif
 
 #ifdef USE_COLORS 
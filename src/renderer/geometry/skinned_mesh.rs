@@ -0,0 +1,447 @@
+use super::{IndexBuffer, MorphTargets};
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// Similar to [Mesh], except that the mesh is deformed by the joints of a [CpuSkin] (skeletal animation).
+/// The positions of the mesh are given in the bind pose of the skin and each vertex is transformed by the (up to) four joints specified by
+/// [CpuMesh::joint_indices] and weighted by [CpuMesh::joint_weights]. The skinning is done on the GPU in the vertex shader.
+/// Use [SkinnedMesh::set_joint_transformations] to pose the mesh.
+///
+pub struct SkinnedMesh {
+    context: Context,
+    position_buffer: VertexBuffer<Vec3>,
+    normal_buffer: Option<VertexBuffer<Vec3>>,
+    tangent_buffer: Option<VertexBuffer<Vec4>>,
+    uv_buffer: Option<VertexBuffer<Vec2>>,
+    secondary_uv_buffer: Option<VertexBuffer<Vec2>>,
+    color_buffer: Option<VertexBuffer<Color>>,
+    joint_index_buffer: VertexBuffer<Vector4<u16>>,
+    joint_weight_buffer: VertexBuffer<Vec4>,
+    index_buffer: Option<IndexBuffer>,
+    morph_targets: Option<MorphTargets>,
+    joint_texture: Texture2D<f32>,
+    inverse_bind_matrices: Vec<Mat4>,
+    joint_transformations: Vec<Mat4>,
+    /// The bounding box of the vertices influenced by each joint in the local space of the joint
+    joint_aabbs: Vec<AxisAlignedBoundingBox>,
+    aabb: AxisAlignedBoundingBox,
+    transformation: Mat4,
+    texture_transform: Mat3,
+}
+
+impl SkinnedMesh {
+    ///
+    /// Creates a new skinned mesh from the given [CpuMesh] and [CpuSkin].
+    /// The mesh must have both [CpuMesh::joint_indices] and [CpuMesh::joint_weights] and the joint indices must be indices into the [CpuSkin::joints].
+    /// The mesh is initially in the pose given by the [CpuJoint::transformation] of the joints.
+    ///
+    /// # Errors
+    /// Returns an error if the skin has no joints, if the joint indices and weights do not have the same length as the positions
+    /// or if a joint index is not an index into the joints of the skin.
+    ///
+    pub fn new(context: &Context, cpu_mesh: &CpuMesh, cpu_skin: &CpuSkin) -> ThreeDResult<Self> {
+        #[cfg(debug_assertions)]
+        cpu_mesh.validate()?;
+
+        let joint_indices = cpu_mesh
+            .joint_indices
+            .as_ref()
+            .ok_or(CoreError::MissingMeshBuffer("joint indices".to_string()))?;
+        let joint_weights = cpu_mesh
+            .joint_weights
+            .as_ref()
+            .ok_or(CoreError::MissingMeshBuffer("joint weights".to_string()))?;
+        let joint_count = cpu_skin.joints.len();
+        if joint_count == 0 {
+            Err(CoreError::EmptySkin(cpu_skin.name.clone()))?;
+        }
+        let vertex_count = cpu_mesh.positions.len();
+        if joint_indices.len() != vertex_count {
+            Err(CoreError::InvalidBufferLength(
+                "joint indices".to_string(),
+                vertex_count,
+                joint_indices.len(),
+            ))?;
+        }
+        if joint_weights.len() != vertex_count {
+            Err(CoreError::InvalidBufferLength(
+                "joint weights".to_string(),
+                vertex_count,
+                joint_weights.len(),
+            ))?;
+        }
+        let inverse_bind_matrices = cpu_skin
+            .joints
+            .iter()
+            .map(|joint| joint.inverse_bind_matrix)
+            .collect::<Vec<_>>();
+
+        let positions = cpu_mesh.positions.to_f32();
+        let mut joint_aabbs = vec![AxisAlignedBoundingBox::EMPTY; joint_count];
+        for (vertex, position) in positions.iter().enumerate() {
+            let indices = joint_indices[vertex];
+            let weights = joint_weights[vertex];
+            for (index, weight) in [
+                (indices.x, weights.x),
+                (indices.y, weights.y),
+                (indices.z, weights.z),
+                (indices.w, weights.w),
+            ] {
+                let index = index as usize;
+                if weight > 0.0 {
+                    if index >= joint_count {
+                        Err(CoreError::IndexOutOfRange(index, joint_count - 1))?;
+                    }
+                    joint_aabbs[index].expand(&[(inverse_bind_matrices[index]
+                        * position.extend(1.0))
+                    .truncate()]);
+                }
+            }
+        }
+
+        let position_buffer = VertexBuffer::new_with_data(context, &positions)?;
+        let normal_buffer = if let Some(ref normals) = cpu_mesh.normals {
+            Some(VertexBuffer::new_with_data(context, normals)?)
+        } else {
+            None
+        };
+        let tangent_buffer = if let Some(ref tangents) = cpu_mesh.tangents {
+            Some(VertexBuffer::new_with_data(context, tangents)?)
+        } else {
+            None
+        };
+        let index_buffer = if let Some(ref indices) = cpu_mesh.indices {
+            Some(IndexBuffer::new(context, indices)?)
+        } else {
+            None
+        };
+        let uv_buffer = if let Some(ref uvs) = cpu_mesh.uvs {
+            Some(VertexBuffer::new_with_data(context, uvs)?)
+        } else {
+            None
+        };
+        let secondary_uv_buffer = if let Some(ref uvs) = cpu_mesh.secondary_uvs {
+            Some(VertexBuffer::new_with_data(context, uvs)?)
+        } else {
+            None
+        };
+        let color_buffer = if let Some(ref colors) = cpu_mesh.colors {
+            Some(VertexBuffer::new_with_data(context, colors)?)
+        } else {
+            None
+        };
+        let joint_texture = Texture2D::new_empty(
+            context,
+            4 * joint_count.max(1) as u32,
+            1,
+            Interpolation::Nearest,
+            Interpolation::Nearest,
+            None,
+            Wrapping::ClampToEdge,
+            Wrapping::ClampToEdge,
+            Format::RGBA,
+        )?;
+        let mut mesh = Self {
+            context: context.clone(),
+            position_buffer,
+            normal_buffer,
+            tangent_buffer,
+            uv_buffer,
+            secondary_uv_buffer,
+            color_buffer,
+            joint_index_buffer: VertexBuffer::new_with_data(context, joint_indices)?,
+            joint_weight_buffer: VertexBuffer::new_with_data(context, joint_weights)?,
+            index_buffer,
+            morph_targets: MorphTargets::new(context, cpu_mesh)?,
+            joint_texture,
+            inverse_bind_matrices,
+            joint_transformations: Vec::new(),
+            joint_aabbs,
+            aabb: AxisAlignedBoundingBox::EMPTY,
+            transformation: Mat4::identity(),
+            texture_transform: Mat3::identity(),
+        };
+        mesh.set_joint_transformations(&cpu_skin.joint_transformations()?)?;
+        Ok(mesh)
+    }
+
+    ///
+    /// Returns the current world transformation of each joint.
+    ///
+    pub fn joint_transformations(&self) -> &[Mat4] {
+        &self.joint_transformations
+    }
+
+    ///
+    /// Poses the mesh by setting the world transformation of each joint, for example computed using [CpuSkin::joint_transformations] after
+    /// the [CpuJoint::transformation] of one or more joints has been changed.
+    /// The transformations are uploaded to the GPU as a bone palette, ie. the joint transformation multiplied with the inverse bind matrix of the joint.
+    ///
+    /// # Errors
+    /// Returns an error if the number of transformations is not equal to the number of joints in the skin.
+    ///
+    pub fn set_joint_transformations(
+        &mut self,
+        joint_transformations: &[Mat4],
+    ) -> ThreeDResult<()> {
+        if joint_transformations.len() != self.inverse_bind_matrices.len() {
+            Err(CoreError::InvalidBufferLength(
+                "joint transformations".to_string(),
+                self.inverse_bind_matrices.len(),
+                joint_transformations.len(),
+            ))?;
+        }
+        let mut data = Vec::with_capacity(16 * joint_transformations.len().max(1));
+        for (transformation, inverse_bind_matrix) in joint_transformations
+            .iter()
+            .zip(self.inverse_bind_matrices.iter())
+        {
+            let m = transformation * inverse_bind_matrix;
+            let m: &[f32; 16] = m.as_ref();
+            data.extend_from_slice(m);
+        }
+        data.resize(16 * joint_transformations.len().max(1), 0.0);
+        self.joint_texture.fill(&data)?;
+        self.joint_transformations = joint_transformations.to_vec();
+        self.update_aabb();
+        Ok(())
+    }
+
+    ///
+    /// Returns the weight of each of the morph targets of this mesh, see [CpuMesh::morph_targets].
+    /// Returns an empty slice if the mesh has no morph targets.
+    ///
+    pub fn morph_weights(&self) -> &[f32] {
+        self.morph_targets
+            .as_ref()
+            .map(|morph_targets| morph_targets.weights())
+            .unwrap_or(&[])
+    }
+
+    ///
+    /// Set the weight of each of the morph targets of this mesh, see [CpuMesh::morph_targets].
+    /// Missing weights are set to zero and extra weights are ignored.
+    ///
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        if let Some(ref mut morph_targets) = self.morph_targets {
+            morph_targets.set_weights(weights);
+            self.update_aabb();
+        }
+    }
+
+    ///
+    /// Returns the local to world transformation applied to the skinned mesh after the joint transformations.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to the skinned mesh after the joint transformations.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+        self.update_aabb();
+    }
+
+    ///
+    /// Get the texture transform applied to the uv coordinates of the model.
+    ///
+    pub fn texture_transform(&mut self) -> &Mat3 {
+        &self.texture_transform
+    }
+
+    ///
+    /// Set the texture transform applied to the uv coordinates of the model.
+    ///
+    pub fn set_texture_transform(&mut self, texture_transform: Mat3) {
+        self.texture_transform = texture_transform;
+    }
+
+    fn update_aabb(&mut self) {
+        let offset = self
+            .morph_targets
+            .as_ref()
+            .map(|morph_targets| morph_targets.max_position_offset())
+            .unwrap_or(0.0);
+        let mut aabb = AxisAlignedBoundingBox::EMPTY;
+        for (joint_aabb, joint_transformation) in self
+            .joint_aabbs
+            .iter()
+            .zip(self.joint_transformations.iter())
+        {
+            if !joint_aabb.is_empty() {
+                let mut joint_aabb = AxisAlignedBoundingBox::new_with_positions(&[
+                    joint_aabb.min() - vec3(offset, offset, offset),
+                    joint_aabb.max() + vec3(offset, offset, offset),
+                ]);
+                joint_aabb.transform(&(self.transformation * joint_transformation));
+                aabb.expand_with_aabb(&joint_aabb);
+            }
+        }
+        self.aabb = aabb;
+    }
+
+    fn vertex_shader_source(fragment_shader_source: &str) -> ThreeDResult<String> {
+        let use_positions = fragment_shader_source.find("in vec3 pos;").is_some();
+        let use_normals = fragment_shader_source.find("in vec3 nor;").is_some();
+        let use_tangents = fragment_shader_source.find("in vec3 tang;").is_some();
+        let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
+        let use_secondary_uvs = fragment_shader_source.find("in vec2 uvs2;").is_some();
+        let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
+        Ok(format!(
+            "#define SKINNED\n{}{}{}{}{}{}{}{}",
+            if use_positions {
+                "#define USE_POSITIONS\n"
+            } else {
+                ""
+            },
+            if use_normals {
+                "#define USE_NORMALS\n"
+            } else {
+                ""
+            },
+            if use_tangents {
+                if fragment_shader_source.find("in vec3 bitang;").is_none() {
+                    Err(CoreError::MissingBitangent)?;
+                }
+                "#define USE_TANGENTS\n"
+            } else {
+                ""
+            },
+            if use_uvs { "#define USE_UVS\n" } else { "" },
+            if use_secondary_uvs {
+                "#define USE_SECONDARY_UVS\n"
+            } else {
+                ""
+            },
+            if use_colors {
+                "#define USE_COLORS\n"
+            } else {
+                ""
+            },
+            include_str!("../../core/shared.frag"),
+            include_str!("shaders/mesh.vert"),
+        ))
+    }
+}
+
+impl Transformable for SkinnedMesh {
+    fn transformation(&self) -> Mat4 {
+        SkinnedMesh::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        SkinnedMesh::set_transformation(self, transformation)
+    }
+}
+
+impl Geometry for SkinnedMesh {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        self.aabb
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        let fragment_shader_source =
+            material.fragment_shader_source(self.color_buffer.is_some(), lights);
+        let morph_defines = self
+            .morph_targets
+            .as_ref()
+            .map(|morph_targets| morph_targets.vertex_shader_defines())
+            .unwrap_or_default();
+        self.context.program(
+            &format!(
+                "{}{}",
+                morph_defines,
+                Self::vertex_shader_source(&fragment_shader_source)?
+            ),
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, lights)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer());
+                if let Some(ref morph_targets) = self.morph_targets {
+                    morph_targets.use_uniforms(program)?;
+                }
+                program.use_uniform("modelMatrix", self.transformation)?;
+                program.use_texture("jointTexture", &self.joint_texture)?;
+                program.use_vertex_attribute("joint_indices", &self.joint_index_buffer)?;
+                program.use_vertex_attribute("joint_weights", &self.joint_weight_buffer)?;
+
+                if program.requires_attribute("position") {
+                    program.use_vertex_attribute("position", &self.position_buffer)?;
+                }
+                if program.requires_attribute("uv_coordinates") {
+                    program.use_uniform("textureTransform", self.texture_transform)?;
+                    let uv_buffer = self
+                        .uv_buffer
+                        .as_ref()
+                        .ok_or(CoreError::MissingMeshBuffer("uv coordinates".to_string()))?;
+                    program.use_vertex_attribute("uv_coordinates", uv_buffer)?;
+                }
+                if program.requires_attribute("secondary_uv_coordinates") {
+                    let secondary_uv_buffer =
+                        self.secondary_uv_buffer
+                            .as_ref()
+                            .ok_or(CoreError::MissingMeshBuffer(
+                                "secondary uv coordinates".to_string(),
+                            ))?;
+                    program
+                        .use_vertex_attribute("secondary_uv_coordinates", secondary_uv_buffer)?;
+                }
+                if program.requires_attribute("normal") {
+                    let normal_buffer = self
+                        .normal_buffer
+                        .as_ref()
+                        .ok_or(CoreError::MissingMeshBuffer("normal".to_string()))?;
+                    program.use_vertex_attribute("normal", normal_buffer)?;
+                    if program.requires_attribute("tangent") {
+                        let tangent_buffer = self
+                            .tangent_buffer
+                            .as_ref()
+                            .ok_or(CoreError::MissingMeshBuffer("tangent".to_string()))?;
+                        program.use_vertex_attribute("tangent", tangent_buffer)?;
+                    }
+                }
+                if program.requires_attribute("color") {
+                    let color_buffer = self
+                        .color_buffer
+                        .as_ref()
+                        .ok_or(CoreError::MissingMeshBuffer("color".to_string()))?;
+                    program.use_vertex_attribute("color", color_buffer)?;
+                }
+                if let Some(ref index_buffer) = self.index_buffer {
+                    match index_buffer {
+                        IndexBuffer::U8(ref buffer) => program.draw_elements(
+                            material.render_states(),
+                            camera.viewport(),
+                            buffer,
+                        ),
+                        IndexBuffer::U16(ref buffer) => program.draw_elements(
+                            material.render_states(),
+                            camera.viewport(),
+                            buffer,
+                        ),
+                        IndexBuffer::U32(ref buffer) => program.draw_elements(
+                            material.render_states(),
+                            camera.viewport(),
+                            buffer,
+                        ),
+                    };
+                } else {
+                    program.draw_arrays(
+                        material.render_states(),
+                        camera.viewport(),
+                        self.position_buffer.vertex_count(),
+                    );
+                }
+                Ok(())
+            },
+        )
+    }
+}
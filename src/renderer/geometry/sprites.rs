@@ -61,6 +61,16 @@ impl Sprites {
     }
 }
 
+impl Transformable for Sprites {
+    fn transformation(&self) -> Mat4 {
+        Sprites::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        Sprites::set_transformation(self, transformation)
+    }
+}
+
 impl Geometry for Sprites {
     fn render_with_material(
         &self,
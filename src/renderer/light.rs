@@ -24,6 +24,9 @@ mod environment;
 #[doc(inline)]
 pub use environment::*;
 
+#[cfg(feature = "gltf-io")]
+mod gltf_lights;
+
 use crate::core::*;
 
 ///
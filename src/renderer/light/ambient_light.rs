@@ -57,22 +57,37 @@ impl Light for AmbientLight {
                     float NdV = max(0.001, dot(N, V));
                     
                     // calculate reflectance at normal incidence; if dia-electric (like plastic) use F0 
-                    // of 0.04 and if it's a metal, use the albedo color as F0 (metallic workflow)    
-                    vec3 F0 = mix(vec3(0.04), surface_color, metallic);
+                    // given by the index of refraction and if it's a metal, use the albedo color as F0 (metallic workflow)    
+                    vec3 F0 = mix(dielectric_specular(), surface_color, metallic);
                     vec3 specular_fresnel = fresnel_schlick_roughness(F0, NdV, roughness);
                     vec3 diffuse_fresnel = 1.0 - specular_fresnel;
 
                     // Diffuse
                     vec3 irradiance = texture(irradianceMap, N).rgb;
-                    vec3 diffuse = diffuse_fresnel * mix(surface_color, vec3(0.0), metallic) * irradiance;
+                    vec3 diffuse = diffuse_fresnel * mix(surface_color, vec3(0.0), metallic) * (1.0 - material_transmission) * irradiance;
                     
                     // sample both the pre-filter map and the BRDF lut and combine them together as per the Split-Sum approximation to get the IBL specular part.
                     const float MAX_REFLECTION_LOD = 4.0;
                     vec3 prefilteredColor = textureLod(prefilterMap, R,  roughness * MAX_REFLECTION_LOD).rgb;    
                     vec2 brdf  = texture(brdfLUT, vec2(NdV, roughness)).rg;
                     vec3 specular = prefilteredColor * (specular_fresnel * brdf.x + brdf.y);
+                    vec3 color = diffuse + specular;
+
+                    // sheen layer, approximated by the irradiance scaled by the sheen color
+                    float sheen_strength = max(max(material_sheen_color.r, material_sheen_color.g), material_sheen_color.b);
+                    color = color * (1.0 - 0.157 * sheen_strength) + material_sheen_color * irradiance * (1.0 - 0.5 * material_sheen_roughness) * 0.25;
+
+                    // clear coat layer
+                    if (material_clearcoat > 0.0) {{
+                        vec3 Nc = material_clearcoat_normal;
+                        float clearcoat_NdV = max(0.001, dot(Nc, V));
+                        vec3 clearcoat_fresnel = fresnel_schlick_roughness(vec3(0.04), clearcoat_NdV, material_clearcoat_roughness);
+                        vec3 clearcoat_color = textureLod(prefilterMap, reflect(-V, Nc), material_clearcoat_roughness * MAX_REFLECTION_LOD).rgb;
+                        vec2 clearcoat_brdf = texture(brdfLUT, vec2(clearcoat_NdV, material_clearcoat_roughness)).rg;
+                        color = color * (1.0 - material_clearcoat * clearcoat_fresnel) + material_clearcoat * clearcoat_color * (clearcoat_fresnel * clearcoat_brdf.x + clearcoat_brdf.y);
+                    }}
     
-                    return (diffuse + specular) * occlusion * ambientColor;
+                    return color * occlusion * ambientColor;
                 }}
             
             ", i)
@@ -82,7 +97,7 @@ impl Light for AmbientLight {
                     uniform vec3 ambientColor;
                     vec3 calculate_lighting{}(vec3 surface_color, vec3 position, vec3 normal, vec3 view_direction, float metallic, float roughness, float occlusion)
                     {{
-                        return occlusion * ambientColor * mix(surface_color, vec3(0.0), metallic);
+                        return occlusion * ambientColor * mix(surface_color, vec3(0.0), metallic) * (1.0 - material_transmission);
                     }}
                 
                 ", i)
@@ -0,0 +1,92 @@
+use crate::core::*;
+use crate::io::{GltfLightKind, GltfScene};
+use crate::renderer::*;
+
+impl GltfScene {
+    ///
+    /// Creates a [DirectionalLight] for each directional light in [GltfScene::lights].
+    ///
+    pub fn directional_lights(&self, context: &Context) -> ThreeDResult<Vec<DirectionalLight>> {
+        let mut lights = Vec::new();
+        for light in self.lights.iter() {
+            if let GltfLightKind::Directional { direction } = light.kind {
+                lights.push(DirectionalLight::new(
+                    context,
+                    light.intensity,
+                    light.color,
+                    &direction,
+                )?);
+            }
+        }
+        Ok(lights)
+    }
+
+    ///
+    /// Creates a [PointLight] for each point light in [GltfScene::lights].
+    /// Without a range, the intensity of the light falls off with the inverse square of the distance as specified by glTF,
+    /// with a range, the attenuation is chosen such that the intensity falls off to one percent at the range.
+    ///
+    pub fn point_lights(&self, context: &Context) -> ThreeDResult<Vec<PointLight>> {
+        let mut lights = Vec::new();
+        for light in self.lights.iter() {
+            if let GltfLightKind::Point { position, range } = light.kind {
+                lights.push(PointLight::new(
+                    context,
+                    light.intensity,
+                    light.color,
+                    &position,
+                    attenuation(range),
+                )?);
+            }
+        }
+        Ok(lights)
+    }
+
+    ///
+    /// Creates a [SpotLight] for each spot light in [GltfScene::lights].
+    /// The range of the light is converted to an attenuation in the same way as for [GltfScene::point_lights].
+    ///
+    pub fn spot_lights(&self, context: &Context) -> ThreeDResult<Vec<SpotLight>> {
+        let mut lights = Vec::new();
+        for light in self.lights.iter() {
+            if let GltfLightKind::Spot {
+                position,
+                direction,
+                cutoff,
+                range,
+            } = light.kind
+            {
+                lights.push(SpotLight::new(
+                    context,
+                    light.intensity,
+                    light.color,
+                    &position,
+                    &direction,
+                    cutoff,
+                    attenuation(range),
+                )?);
+            }
+        }
+        Ok(lights)
+    }
+}
+
+///
+/// Returns the attenuation of a glTF light with the given range.
+/// Without a range, the intensity falls off with the inverse square of the distance as specified by glTF,
+/// with a range, the attenuation is chosen such that the intensity falls off to one percent at the range.
+///
+fn attenuation(range: Option<f32>) -> Attenuation {
+    match range {
+        Some(range) if range > 0.0 => Attenuation {
+            constant: 1.0,
+            linear: 4.5 / range,
+            quadratic: 94.5 / (range * range),
+        },
+        _ => Attenuation {
+            constant: 0.0,
+            linear: 0.0,
+            quadratic: 1.0,
+        },
+    }
+}
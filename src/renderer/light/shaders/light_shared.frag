@@ -5,6 +5,23 @@ struct BaseLight
     float intensity;
 };
 
+// Parameters of the additional layers of a physical material which are set by the material before calculating the lighting.
+// The default values disable the layers.
+float material_ior = 1.5;
+float material_transmission = 0.0;
+float material_clearcoat = 0.0;
+float material_clearcoat_roughness = 0.0;
+vec3 material_clearcoat_normal = vec3(0.0, 0.0, 1.0);
+vec3 material_sheen_color = vec3(0.0);
+float material_sheen_roughness = 0.0;
+
+// reflectance at normal incidence of a non-metallic material with the given index of refraction
+vec3 dielectric_specular()
+{
+    float r = (material_ior - 1.0) / (material_ior + 1.0);
+    return vec3(r * r);
+}
+
 // compute fresnel specular factor
 // cosTheta could be NdV or VdH depending on used technique
 vec3 fresnel_schlick(vec3 F0, float cosTheta)
@@ -72,6 +89,47 @@ float G_schlick(in float roughness, in float NdV, in float NdL)
     return NdV * NdL / (V * L);
 }
 
+// Charlie sheen distribution function
+float D_charlie(in float roughness, in float NdH)
+{
+    float alpha = max(roughness * roughness, 0.0001);
+    float inv_alpha = 1.0 / alpha;
+    float sin2h = max(1.0 - NdH * NdH, 0.0078125);
+    return (2.0 + inv_alpha) * pow(sin2h, inv_alpha * 0.5) / (2.0 * PI);
+}
+
+// Neubelt sheen visibility function
+float V_neubelt(in float NdV, in float NdL)
+{
+    return saturate(1.0 / (4.0 * (NdL + NdV - NdL * NdV)));
+}
+
+// Adds the sheen and clear coat layers on top of the reflected light of the base material
+vec3 add_layers(vec3 base, vec3 L, vec3 V, vec3 N)
+{
+    vec3 H = normalize(L + V);
+    float sheen_strength = max(max(material_sheen_color.r, material_sheen_color.g), material_sheen_color.b);
+    if (sheen_strength > 0.0) {
+        float NdL = max(0.001, dot(N, L));
+        float NdV = max(0.001, dot(N, V));
+        float NdH = max(0.001, dot(N, H));
+        vec3 sheen = material_sheen_color * D_charlie(material_sheen_roughness, NdH) * V_neubelt(NdV, NdL) * NdL;
+        base = base * (1.0 - 0.157 * sheen_strength) + sheen;
+    }
+    if (material_clearcoat > 0.0) {
+        vec3 Nc = material_clearcoat_normal;
+        float NdL = max(0.001, dot(Nc, L));
+        float NdV = max(0.001, dot(Nc, V));
+        float NdH = max(0.001, dot(Nc, H));
+        float HdV = max(0.001, dot(H, V));
+        float roughness = max(material_clearcoat_roughness, 0.03);
+        vec3 fresnel = fresnel_schlick(vec3(0.04), HdV);
+        vec3 clearcoat = fresnel * D_GGX(roughness, NdH) * G_schlick(roughness, NdV, NdL) / (4.0 * NdV);
+        base = base * (1.0 - material_clearcoat * fresnel) + material_clearcoat * clearcoat;
+    }
+    return base;
+}
+
 // simple phong specular calculation with normalization
 vec3 phong_specular(in vec3 V, in vec3 L, in vec3 N, in vec3 specular_fresnel, in float roughness)
 {
@@ -106,8 +164,8 @@ vec3 calculate_light(vec3 light_color, vec3 L, vec3 surface_color, vec3 V, vec3
     float NdV = max(0.001, dot(N, V));
 
     // mix between metal and non-metal material, for non-metal
-    // constant base specular factor of 0.04 grey is used
-    vec3 F0 = mix(vec3(0.04), surface_color, metallic);
+    // the base specular factor is given by the index of refraction, which is 0.04 grey by default
+    vec3 F0 = mix(dielectric_specular(), surface_color, metallic);
 
 #ifdef PHONG
     // specular reflectance with PHONG
@@ -132,10 +190,11 @@ vec3 calculate_light(vec3 light_color, vec3 L, vec3 surface_color, vec3 V, vec3
 
     // diffuse is common for any model
     vec3 diffuse_fresnel = 1.0 - specular_fresnel;
-    vec3 diffuse = diffuse_fresnel * mix(surface_color, vec3(0.0), metallic) / PI;
+    // the transmitted part of the light is not diffusely reflected
+    vec3 diffuse = diffuse_fresnel * mix(surface_color, vec3(0.0), metallic) * (1.0 - material_transmission) / PI;
     
     // final result
-    return (diffuse + specular) * light_color * NdL;
+    return add_layers((diffuse + specular) * NdL, L, V, N) * light_color;
 }
 
 vec3 attenuate(vec3 light_color, vec3 attenuation, float distance)
@@ -6,7 +6,7 @@
 use crate::core::*;
 use crate::renderer::*;
 
-pub use crate::core::{Color, CpuMaterial};
+pub use crate::core::{Color, CpuMaterial, TextureTransform};
 
 mod color_material;
 #[doc(inline)]
@@ -7,6 +7,10 @@ use std::rc::Rc;
 /// Must be used together with a [DeferredPipeline].
 /// This material is affected by lights.
 ///
+/// Only the base parameters of a physically-based material are supported. The emissive color, the texture transforms and the
+/// clear coat, sheen, transmission and index of refraction parameters of a [CpuMaterial] or [PhysicalMaterial] are ignored,
+/// so use a [PhysicalMaterial] if a material depends on these.
+///
 pub struct DeferredPhysicalMaterial<A: Texture, ORM: Texture, N: Texture> {
     /// Name. Used for matching geometry and material.
     pub name: String,
@@ -33,6 +37,7 @@ pub struct DeferredPhysicalMaterial<A: Texture, ORM: Texture, N: Texture> {
     /// Render states
     pub render_states: RenderStates,
     /// Alpha cutout value for transparency in deferred rendering pipeline.
+    /// Fragments with an alpha value below the cutout value are discarded.
     pub alpha_cutout: Option<f32>,
 }
 
@@ -41,6 +46,8 @@ impl DeferredPhysicalMaterial<Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D
     /// Constructs a new deferred physical material from a [CpuMaterial].
     /// If the input contains an [CpuMaterial::occlusion_metallic_roughness_texture], this texture is used for both
     /// [DeferredPhysicalMaterial::metallic_roughness_texture] and [DeferredPhysicalMaterial::occlusion_texture] while any [CpuMaterial::metallic_roughness_texture] or [CpuMaterial::occlusion_texture] are ignored.
+    /// Since a deferred material cannot be transparent, a transparent material (see [CpuMaterial::is_transparent]) without an [CpuMaterial::alpha_cutout]
+    /// is rendered with an alpha cutout of 0.5.
     ///
     pub fn new(context: &Context, cpu_material: &CpuMaterial) -> ThreeDResult<Self> {
         let albedo_texture = if let Some(ref cpu_texture) = cpu_material.albedo_texture {
@@ -83,8 +90,15 @@ impl DeferredPhysicalMaterial<Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D
             normal_scale: cpu_material.normal_scale,
             occlusion_texture,
             occlusion_strength: cpu_material.occlusion_strength,
-            render_states: RenderStates::default(),
-            alpha_cutout: cpu_material.alpha_cutout,
+            render_states: RenderStates {
+                cull: cpu_material.cull,
+                ..Default::default()
+            },
+            alpha_cutout: if cpu_material.is_transparent == Some(true) {
+                Some(cpu_material.alpha_cutout.unwrap_or(0.5))
+            } else {
+                cpu_material.alpha_cutout
+            },
         })
     }
 }
@@ -115,9 +129,9 @@ impl<A: Texture + Clone, ORM: Texture + Clone, N: Texture + Clone>
                 ..physical_material.render_states
             },
             alpha_cutout: if physical_material.is_transparent {
-                Some(0.5)
+                Some(physical_material.alpha_cutout.unwrap_or(0.5))
             } else {
-                None
+                physical_material.alpha_cutout
             },
         }
     }
@@ -148,7 +162,7 @@ impl<A: Texture, ORM: Texture, N: Texture> Material for DeferredPhysicalMaterial
             if self.alpha_cutout.is_some() {
                 output.push_str(
                     format!(
-                        "#define ALPHACUT;\nfloat acut = {};",
+                        "#define ALPHACUT;\nfloat acut = {:.6};\n",
                         self.alpha_cutout.unwrap()
                     )
                     .as_str(),
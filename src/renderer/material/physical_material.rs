@@ -13,6 +13,8 @@ pub struct PhysicalMaterial<A: Texture, ORM: Texture, N: Texture, E: Texture> {
     pub albedo: Color,
     /// Texture with albedo base colors, also called diffuse color. Assumed to be in sRGB with or without an alpha channel.
     pub albedo_texture: Option<A>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::albedo_texture] and the set of uv coordinates to use.
+    pub albedo_texture_transform: TextureTransform,
     /// A value in the range `[0..1]` specifying how metallic the material is.
     pub metallic: f32,
     /// A value in the range `[0..1]` specifying how rough the material surface is.
@@ -20,15 +22,21 @@ pub struct PhysicalMaterial<A: Texture, ORM: Texture, N: Texture, E: Texture> {
     /// Texture containing the metallic and roughness parameters which are multiplied with the [Self::metallic] and [Self::roughness] values in the shader.
     /// The metallic values are sampled from the blue channel and the roughness from the green channel.
     pub metallic_roughness_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::metallic_roughness_texture] and the set of uv coordinates to use.
+    pub metallic_roughness_texture_transform: TextureTransform,
     /// A scalar multiplier controlling the amount of occlusion applied from the [Self::occlusion_texture]. A value of 0.0 means no occlusion. A value of 1.0 means full occlusion.
     pub occlusion_strength: f32,
     /// An occlusion map. Higher values indicate areas that should receive full indirect lighting and lower values indicate no indirect lighting.
     /// The occlusion values are sampled from the red channel.
     pub occlusion_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::occlusion_texture] and the set of uv coordinates to use.
+    pub occlusion_texture_transform: TextureTransform,
     /// A scalar multiplier applied to each normal vector of the [Self::normal_texture].
     pub normal_scale: f32,
     /// A tangent space normal map, also known as bump map.
     pub normal_texture: Option<N>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::normal_texture] and the set of uv coordinates to use.
+    pub normal_texture_transform: TextureTransform,
     /// Render states.
     pub render_states: RenderStates,
     /// Whether this material should be treated as a transparent material (An object needs to be rendered differently depending on whether it is transparent or opaque).
@@ -37,8 +45,58 @@ pub struct PhysicalMaterial<A: Texture, ORM: Texture, N: Texture, E: Texture> {
     pub emissive: Color,
     /// Texture with color of light shining from an object.
     pub emissive_texture: Option<E>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::emissive_texture] and the set of uv coordinates to use.
+    pub emissive_texture_transform: TextureTransform,
+    /// A scalar multiplier applied to the [Self::emissive] color, which allows for emissive colors brighter than white.
+    pub emissive_strength: f32,
+    /// A value in the range `[0..1]` specifying the strength of a clear coat layer on top of the material. A value of 0.0 means no clear coat.
+    pub clearcoat: f32,
+    /// Texture containing the clear coat strength which is multiplied with [Self::clearcoat] in the shader.
+    /// The clear coat values are sampled from the red channel.
+    pub clearcoat_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::clearcoat_texture] and the set of uv coordinates to use.
+    pub clearcoat_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how rough the clear coat layer is.
+    pub clearcoat_roughness: f32,
+    /// Texture containing the clear coat roughness which is multiplied with [Self::clearcoat_roughness] in the shader.
+    /// The roughness values are sampled from the green channel.
+    pub clearcoat_roughness_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::clearcoat_roughness_texture] and the set of uv coordinates to use.
+    pub clearcoat_roughness_texture_transform: TextureTransform,
+    /// A scalar multiplier applied to each normal vector of the [Self::clearcoat_normal_texture].
+    pub clearcoat_normal_scale: f32,
+    /// A tangent space normal map for the clear coat layer. If not specified, the clear coat layer is not affected by the [Self::normal_texture].
+    pub clearcoat_normal_texture: Option<N>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::clearcoat_normal_texture] and the set of uv coordinates to use.
+    pub clearcoat_normal_texture_transform: TextureTransform,
+    /// The color of a sheen layer on top of the material. Assumed to be in linear color space. Black means no sheen.
+    pub sheen_color: Color,
+    /// Texture with sheen colors which are multiplied with [Self::sheen_color] in the shader. Assumed to be in sRGB.
+    pub sheen_color_texture: Option<A>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::sheen_color_texture] and the set of uv coordinates to use.
+    pub sheen_color_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how rough the sheen layer is.
+    pub sheen_roughness: f32,
+    /// Texture containing the sheen roughness which is multiplied with [Self::sheen_roughness] in the shader.
+    /// The roughness values are sampled from the alpha channel.
+    pub sheen_roughness_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::sheen_roughness_texture] and the set of uv coordinates to use.
+    pub sheen_roughness_texture_transform: TextureTransform,
+    /// A value in the range `[0..1]` specifying how much of the light that is not reflected is transmitted through the surface.
+    /// The transmission is approximated by blending with the objects behind this material, so the transmitted light is not refracted or tinted by the albedo color.
+    /// Therefore, a transmissive material is always transparent and rendered using premultiplied alpha blending instead of the blending in [Self::render_states].
+    pub transmission: f32,
+    /// Texture containing the transmission which is multiplied with [Self::transmission] in the shader.
+    /// The transmission values are sampled from the red channel.
+    pub transmission_texture: Option<ORM>,
+    /// The transformation applied to the uv coordinates before sampling the [Self::transmission_texture] and the set of uv coordinates to use.
+    pub transmission_texture_transform: TextureTransform,
+    /// The index of refraction, which determines how much light is reflected by a non-metallic surface.
+    pub index_of_refraction: f32,
     /// The lighting model used when rendering this material
     pub lighting_model: LightingModel,
+    /// Alpha cutout value. If specified, fragments with an alpha value below the cutout value are discarded.
+    pub alpha_cutout: Option<f32>,
 }
 
 impl PhysicalMaterial<Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D<u8>>> {
@@ -46,16 +104,23 @@ impl PhysicalMaterial<Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, R
     /// Constructs a new physical material from a [CpuMaterial].
     /// If the input contains an [CpuMaterial::occlusion_metallic_roughness_texture], this texture is used for both
     /// [PhysicalMaterial::metallic_roughness_texture] and [PhysicalMaterial::occlusion_texture] while any [CpuMaterial::metallic_roughness_texture] or [CpuMaterial::occlusion_texture] are ignored.
-    /// Tries to infer whether this material is transparent or opaque from the alpha value of the albedo color and the alpha values in the albedo texture.
-    /// Since this is not always correct, it is preferred to use [PhysicalMaterial::new_opaque] or [PhysicalMaterial::new_transparent].
+    /// A material with a [CpuMaterial::transmission] is always transparent.
+    /// Otherwise, uses [CpuMaterial::is_transparent] to determine whether this material is transparent or opaque if specified.
+    /// Otherwise, the material is opaque if it has an [CpuMaterial::alpha_cutout] and if not, it tries to infer whether this material is transparent or opaque
+    /// from the alpha value of the albedo color and the alpha values in the albedo texture.
+    /// Since this is not always correct, it is preferred to use [PhysicalMaterial::new_opaque] or [PhysicalMaterial::new_transparent] in that case.
     ///
     pub fn new(context: &Context, cpu_material: &CpuMaterial) -> ThreeDResult<Self> {
-        let is_transparent = cpu_material.albedo.a != 255
-            || cpu_material
-                .albedo_texture
-                .as_ref()
-                .map(|t| t.is_transparent())
-                .unwrap_or(false);
+        let is_transparent = cpu_material.transmission > 0.0
+            || cpu_material.is_transparent.unwrap_or_else(|| {
+                cpu_material.alpha_cutout.is_none()
+                    && (cpu_material.albedo.a != 255
+                        || cpu_material
+                            .albedo_texture
+                            .as_ref()
+                            .map(|t| t.is_transparent())
+                            .unwrap_or(false))
+            });
         Self::new_internal(context, cpu_material, is_transparent)
     }
 
@@ -112,30 +177,75 @@ impl PhysicalMaterial<Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, Rc<Texture2D<u8>>, R
         } else {
             None
         };
+        let texture = |cpu_texture: &Option<CpuTexture<u8>>| -> ThreeDResult<_> {
+            Ok(if let Some(ref cpu_texture) = cpu_texture {
+                Some(Rc::new(Texture2D::new(context, cpu_texture)?))
+            } else {
+                None
+            })
+        };
         Ok(Self {
             name: cpu_material.name.clone(),
             albedo: cpu_material.albedo,
             albedo_texture,
+            albedo_texture_transform: cpu_material.albedo_texture_transform,
             metallic: cpu_material.metallic,
             roughness: cpu_material.roughness,
             metallic_roughness_texture,
+            metallic_roughness_texture_transform: cpu_material.metallic_roughness_texture_transform,
             normal_texture,
+            normal_texture_transform: cpu_material.normal_texture_transform,
             normal_scale: cpu_material.normal_scale,
             occlusion_texture,
+            occlusion_texture_transform: if cpu_material
+                .occlusion_metallic_roughness_texture
+                .is_some()
+            {
+                cpu_material.metallic_roughness_texture_transform
+            } else {
+                cpu_material.occlusion_texture_transform
+            },
             occlusion_strength: cpu_material.occlusion_strength,
             render_states: if is_transparent {
                 RenderStates {
                     write_mask: WriteMask::COLOR,
                     blend: Blend::TRANSPARENCY,
+                    cull: cpu_material.cull,
                     ..Default::default()
                 }
             } else {
-                RenderStates::default()
+                RenderStates {
+                    cull: cpu_material.cull,
+                    ..Default::default()
+                }
             },
             is_transparent,
             emissive: cpu_material.emissive,
             emissive_texture,
+            emissive_texture_transform: cpu_material.emissive_texture_transform,
+            emissive_strength: cpu_material.emissive_strength,
+            clearcoat: cpu_material.clearcoat,
+            clearcoat_texture: texture(&cpu_material.clearcoat_texture)?,
+            clearcoat_texture_transform: cpu_material.clearcoat_texture_transform,
+            clearcoat_roughness: cpu_material.clearcoat_roughness,
+            clearcoat_roughness_texture: texture(&cpu_material.clearcoat_roughness_texture)?,
+            clearcoat_roughness_texture_transform: cpu_material
+                .clearcoat_roughness_texture_transform,
+            clearcoat_normal_scale: cpu_material.clearcoat_normal_scale,
+            clearcoat_normal_texture: texture(&cpu_material.clearcoat_normal_texture)?,
+            clearcoat_normal_texture_transform: cpu_material.clearcoat_normal_texture_transform,
+            sheen_color: cpu_material.sheen_color,
+            sheen_color_texture: texture(&cpu_material.sheen_color_texture)?,
+            sheen_color_texture_transform: cpu_material.sheen_color_texture_transform,
+            sheen_roughness: cpu_material.sheen_roughness,
+            sheen_roughness_texture: texture(&cpu_material.sheen_roughness_texture)?,
+            sheen_roughness_texture_transform: cpu_material.sheen_roughness_texture_transform,
+            transmission: cpu_material.transmission,
+            transmission_texture: texture(&cpu_material.transmission_texture)?,
+            transmission_texture_transform: cpu_material.transmission_texture_transform,
+            index_of_refraction: cpu_material.index_of_refraction,
             lighting_model: cpu_material.lighting_model,
+            alpha_cutout: cpu_material.alpha_cutout,
         })
     }
 }
@@ -148,24 +258,160 @@ impl<A: Texture, ORM: Texture, N: Texture, E: Texture> Material for PhysicalMate
             || self.normal_texture.is_some()
             || self.occlusion_texture.is_some()
             || self.emissive_texture.is_some()
+            || self.clearcoat_texture.is_some()
+            || self.clearcoat_roughness_texture.is_some()
+            || self.clearcoat_normal_texture.is_some()
+            || self.sheen_color_texture.is_some()
+            || self.sheen_roughness_texture.is_some()
+            || self.transmission_texture.is_some()
         {
             output.push_str("in vec2 uvs;\n");
+            let transforms = [
+                (
+                    self.albedo_texture.is_some(),
+                    &self.albedo_texture_transform,
+                ),
+                (
+                    self.metallic_roughness_texture.is_some(),
+                    &self.metallic_roughness_texture_transform,
+                ),
+                (
+                    self.occlusion_texture.is_some(),
+                    &self.occlusion_texture_transform,
+                ),
+                (
+                    self.normal_texture.is_some(),
+                    &self.normal_texture_transform,
+                ),
+                (
+                    self.emissive_texture.is_some(),
+                    &self.emissive_texture_transform,
+                ),
+                (
+                    self.clearcoat_texture.is_some(),
+                    &self.clearcoat_texture_transform,
+                ),
+                (
+                    self.clearcoat_roughness_texture.is_some(),
+                    &self.clearcoat_roughness_texture_transform,
+                ),
+                (
+                    self.clearcoat_normal_texture.is_some(),
+                    &self.clearcoat_normal_texture_transform,
+                ),
+                (
+                    self.sheen_color_texture.is_some(),
+                    &self.sheen_color_texture_transform,
+                ),
+                (
+                    self.sheen_roughness_texture.is_some(),
+                    &self.sheen_roughness_texture_transform,
+                ),
+                (
+                    self.transmission_texture.is_some(),
+                    &self.transmission_texture_transform,
+                ),
+            ];
+            if transforms
+                .iter()
+                .any(|(used, transform)| *used && transform.uv_set > 0)
+            {
+                output.push_str("in vec2 uvs2;\n");
+            }
             if self.albedo_texture.is_some() {
                 output.push_str("#define USE_ALBEDO_TEXTURE;\n");
+                output.push_str(&uvs_define("ALBEDO_UVS", &self.albedo_texture_transform));
             }
             if self.metallic_roughness_texture.is_some() {
                 output.push_str("#define USE_METALLIC_ROUGHNESS_TEXTURE;\n");
+                output.push_str(&uvs_define(
+                    "METALLIC_ROUGHNESS_UVS",
+                    &self.metallic_roughness_texture_transform,
+                ));
             }
             if self.occlusion_texture.is_some() {
                 output.push_str("#define USE_OCCLUSION_TEXTURE;\n");
+                output.push_str(&uvs_define(
+                    "OCCLUSION_UVS",
+                    &self.occlusion_texture_transform,
+                ));
             }
             if self.normal_texture.is_some() {
-                output.push_str("#define USE_NORMAL_TEXTURE;\nin vec3 tang;\nin vec3 bitang;\n");
+                output.push_str("#define USE_NORMAL_TEXTURE;\n");
+                output.push_str(&uvs_define("NORMAL_UVS", &self.normal_texture_transform));
             }
             if self.emissive_texture.is_some() {
                 output.push_str("#define USE_EMISSIVE_TEXTURE;\n");
+                output.push_str(&uvs_define(
+                    "EMISSIVE_UVS",
+                    &self.emissive_texture_transform,
+                ));
+            }
+            if self.normal_texture.is_some()
+                || (self.clearcoat > 0.0 && self.clearcoat_normal_texture.is_some())
+            {
+                output.push_str("in vec3 tang;\nin vec3 bitang;\n");
             }
         }
+        if self.index_of_refraction != 1.5 {
+            output.push_str("#define USE_IOR\n");
+        }
+        if self.clearcoat > 0.0 {
+            output.push_str("#define USE_CLEARCOAT\n");
+            if self.clearcoat_texture.is_some() {
+                output.push_str("#define USE_CLEARCOAT_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "CLEARCOAT_UVS",
+                    &self.clearcoat_texture_transform,
+                ));
+            }
+            if self.clearcoat_roughness_texture.is_some() {
+                output.push_str("#define USE_CLEARCOAT_ROUGHNESS_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "CLEARCOAT_ROUGHNESS_UVS",
+                    &self.clearcoat_roughness_texture_transform,
+                ));
+            }
+            if self.clearcoat_normal_texture.is_some() {
+                output.push_str("#define USE_CLEARCOAT_NORMAL_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "CLEARCOAT_NORMAL_UVS",
+                    &self.clearcoat_normal_texture_transform,
+                ));
+            }
+        }
+        if self.sheen_color != Color::BLACK {
+            output.push_str("#define USE_SHEEN\n");
+            if self.sheen_color_texture.is_some() {
+                output.push_str("#define USE_SHEEN_COLOR_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "SHEEN_COLOR_UVS",
+                    &self.sheen_color_texture_transform,
+                ));
+            }
+            if self.sheen_roughness_texture.is_some() {
+                output.push_str("#define USE_SHEEN_ROUGHNESS_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "SHEEN_ROUGHNESS_UVS",
+                    &self.sheen_roughness_texture_transform,
+                ));
+            }
+        }
+        if self.transmission > 0.0 {
+            output.push_str("#define USE_TRANSMISSION\n");
+            if self.transmission_texture.is_some() {
+                output.push_str("#define USE_TRANSMISSION_TEXTURE\n");
+                output.push_str(&uvs_define(
+                    "TRANSMISSION_UVS",
+                    &self.transmission_texture_transform,
+                ));
+            }
+        }
+        if let Some(alpha_cutout) = self.alpha_cutout {
+            output.push_str(
+                format!("#define ALPHACUT;\nfloat acut = {:.6};\n", alpha_cutout).as_str(),
+            );
+        }
         if use_vertex_colors {
             output.push_str("#define USE_VERTEX_COLORS\nin vec4 col;\n");
         }
@@ -186,35 +432,124 @@ impl<A: Texture, ORM: Texture, N: Texture, E: Texture> Material for PhysicalMate
         program.use_uniform("roughness", &self.roughness)?;
         program.use_uniform("albedo", self.albedo.to_vec4())?;
         if program.requires_uniform("emissive") {
-            program.use_uniform("emissive", &self.emissive.to_vec3())?;
+            program.use_uniform("emissive", self.emissive.to_vec3() * self.emissive_strength)?;
         }
         if let Some(ref texture) = self.albedo_texture {
+            program.use_uniform(
+                "albedoTextureTransform",
+                self.albedo_texture_transform.matrix(),
+            )?;
             program.use_texture("albedoTexture", texture)?;
         }
         if let Some(ref texture) = self.metallic_roughness_texture {
+            program.use_uniform(
+                "metallicRoughnessTextureTransform",
+                self.metallic_roughness_texture_transform.matrix(),
+            )?;
             program.use_texture("metallicRoughnessTexture", texture)?;
         }
         if let Some(ref texture) = self.occlusion_texture {
             program.use_uniform("occlusionStrength", &self.occlusion_strength)?;
+            program.use_uniform(
+                "occlusionTextureTransform",
+                self.occlusion_texture_transform.matrix(),
+            )?;
             program.use_texture("occlusionTexture", texture)?;
         }
         if let Some(ref texture) = self.normal_texture {
             program.use_uniform("normalScale", &self.normal_scale)?;
+            program.use_uniform(
+                "normalTextureTransform",
+                self.normal_texture_transform.matrix(),
+            )?;
             program.use_texture("normalTexture", texture)?;
         }
         if program.requires_uniform("emissiveTexture") {
             if let Some(ref texture) = self.emissive_texture {
+                program.use_uniform(
+                    "emissiveTextureTransform",
+                    self.emissive_texture_transform.matrix(),
+                )?;
                 program.use_texture("emissiveTexture", texture)?;
             }
         }
+        if program.requires_uniform("ior") {
+            program.use_uniform("ior", self.index_of_refraction)?;
+        }
+        if program.requires_uniform("clearcoat") {
+            program.use_uniform("clearcoat", self.clearcoat)?;
+            program.use_uniform("clearcoatRoughness", self.clearcoat_roughness)?;
+            if let Some(ref texture) = self.clearcoat_texture {
+                program.use_uniform(
+                    "clearcoatTextureTransform",
+                    self.clearcoat_texture_transform.matrix(),
+                )?;
+                program.use_texture("clearcoatTexture", texture)?;
+            }
+            if let Some(ref texture) = self.clearcoat_roughness_texture {
+                program.use_uniform(
+                    "clearcoatRoughnessTextureTransform",
+                    self.clearcoat_roughness_texture_transform.matrix(),
+                )?;
+                program.use_texture("clearcoatRoughnessTexture", texture)?;
+            }
+            if let Some(ref texture) = self.clearcoat_normal_texture {
+                program.use_uniform("clearcoatNormalScale", self.clearcoat_normal_scale)?;
+                program.use_uniform(
+                    "clearcoatNormalTextureTransform",
+                    self.clearcoat_normal_texture_transform.matrix(),
+                )?;
+                program.use_texture("clearcoatNormalTexture", texture)?;
+            }
+        }
+        if program.requires_uniform("sheenColor") {
+            program.use_uniform("sheenColor", self.sheen_color.to_vec3())?;
+            program.use_uniform("sheenRoughness", self.sheen_roughness)?;
+            if let Some(ref texture) = self.sheen_color_texture {
+                program.use_uniform(
+                    "sheenColorTextureTransform",
+                    self.sheen_color_texture_transform.matrix(),
+                )?;
+                program.use_texture("sheenColorTexture", texture)?;
+            }
+            if let Some(ref texture) = self.sheen_roughness_texture {
+                program.use_uniform(
+                    "sheenRoughnessTextureTransform",
+                    self.sheen_roughness_texture_transform.matrix(),
+                )?;
+                program.use_texture("sheenRoughnessTexture", texture)?;
+            }
+        }
+        if program.requires_uniform("transmission") {
+            program.use_uniform("transmission", self.transmission)?;
+            if let Some(ref texture) = self.transmission_texture {
+                program.use_uniform(
+                    "transmissionTextureTransform",
+                    self.transmission_texture_transform.matrix(),
+                )?;
+                program.use_texture("transmissionTexture", texture)?;
+            }
+        }
         Ok(())
     }
 
     fn render_states(&self) -> RenderStates {
-        self.render_states
+        let mut render_states = self.render_states;
+        if self.transmission > 0.0 {
+            // The shader outputs colors with premultiplied alpha for transmissive materials
+            render_states.blend = Blend::Enabled {
+                source_rgb_multiplier: BlendMultiplierType::One,
+                source_alpha_multiplier: BlendMultiplierType::Zero,
+                destination_rgb_multiplier: BlendMultiplierType::OneMinusSrcAlpha,
+                destination_alpha_multiplier: BlendMultiplierType::One,
+                rgb_equation: BlendEquationType::Add,
+                alpha_equation: BlendEquationType::Add,
+            };
+        }
+        render_states
     }
     fn is_transparent(&self) -> bool {
-        self.is_transparent
+        self.is_transparent || self.transmission > 0.0
     }
 }
 
@@ -226,18 +561,44 @@ impl<A: Texture + Clone, ORM: Texture + Clone, N: Texture + Clone, E: Texture +
             name: self.name.clone(),
             albedo: self.albedo.clone(),
             albedo_texture: self.albedo_texture.clone(),
+            albedo_texture_transform: self.albedo_texture_transform,
             metallic: self.metallic,
             roughness: self.roughness,
             metallic_roughness_texture: self.metallic_roughness_texture.clone(),
+            metallic_roughness_texture_transform: self.metallic_roughness_texture_transform,
             normal_texture: self.normal_texture.clone(),
+            normal_texture_transform: self.normal_texture_transform,
             normal_scale: self.normal_scale,
             occlusion_texture: self.occlusion_texture.clone(),
+            occlusion_texture_transform: self.occlusion_texture_transform,
             occlusion_strength: self.occlusion_strength,
             render_states: self.render_states,
             is_transparent: self.is_transparent,
             emissive: self.emissive,
             emissive_texture: self.emissive_texture.clone(),
+            emissive_texture_transform: self.emissive_texture_transform,
+            emissive_strength: self.emissive_strength,
+            clearcoat: self.clearcoat,
+            clearcoat_texture: self.clearcoat_texture.clone(),
+            clearcoat_texture_transform: self.clearcoat_texture_transform,
+            clearcoat_roughness: self.clearcoat_roughness,
+            clearcoat_roughness_texture: self.clearcoat_roughness_texture.clone(),
+            clearcoat_roughness_texture_transform: self.clearcoat_roughness_texture_transform,
+            clearcoat_normal_scale: self.clearcoat_normal_scale,
+            clearcoat_normal_texture: self.clearcoat_normal_texture.clone(),
+            clearcoat_normal_texture_transform: self.clearcoat_normal_texture_transform,
+            sheen_color: self.sheen_color,
+            sheen_color_texture: self.sheen_color_texture.clone(),
+            sheen_color_texture_transform: self.sheen_color_texture_transform,
+            sheen_roughness: self.sheen_roughness,
+            sheen_roughness_texture: self.sheen_roughness_texture.clone(),
+            sheen_roughness_texture_transform: self.sheen_roughness_texture_transform,
+            transmission: self.transmission,
+            transmission_texture: self.transmission_texture.clone(),
+            transmission_texture_transform: self.transmission_texture_transform,
+            index_of_refraction: self.index_of_refraction,
             lighting_model: self.lighting_model,
+            alpha_cutout: self.alpha_cutout,
         }
     }
 }
@@ -250,18 +611,55 @@ impl Default
             name: "default".to_string(),
             albedo: Color::WHITE,
             albedo_texture: None,
+            albedo_texture_transform: TextureTransform::default(),
             metallic: 0.0,
             roughness: 1.0,
             metallic_roughness_texture: None,
+            metallic_roughness_texture_transform: TextureTransform::default(),
             normal_texture: None,
+            normal_texture_transform: TextureTransform::default(),
             normal_scale: 1.0,
             occlusion_texture: None,
+            occlusion_texture_transform: TextureTransform::default(),
             occlusion_strength: 1.0,
             render_states: RenderStates::default(),
             is_transparent: false,
             emissive: Color::BLACK,
             emissive_texture: None,
+            emissive_texture_transform: TextureTransform::default(),
+            emissive_strength: 1.0,
+            clearcoat: 0.0,
+            clearcoat_texture: None,
+            clearcoat_texture_transform: TextureTransform::default(),
+            clearcoat_roughness: 0.0,
+            clearcoat_roughness_texture: None,
+            clearcoat_roughness_texture_transform: TextureTransform::default(),
+            clearcoat_normal_scale: 1.0,
+            clearcoat_normal_texture: None,
+            clearcoat_normal_texture_transform: TextureTransform::default(),
+            sheen_color: Color::BLACK,
+            sheen_color_texture: None,
+            sheen_color_texture_transform: TextureTransform::default(),
+            sheen_roughness: 0.0,
+            sheen_roughness_texture: None,
+            sheen_roughness_texture_transform: TextureTransform::default(),
+            transmission: 0.0,
+            transmission_texture: None,
+            transmission_texture_transform: TextureTransform::default(),
+            index_of_refraction: 1.5,
             lighting_model: LightingModel::Blinn,
+            alpha_cutout: None,
         }
     }
 }
+
+///
+/// Returns a define which specifies the set of uv coordinates to use when sampling a texture.
+///
+fn uvs_define(name: &str, transform: &TextureTransform) -> String {
+    format!(
+        "#define {} {}\n",
+        name,
+        if transform.uv_set > 0 { "uvs2" } else { "uvs" }
+    )
+}
@@ -37,14 +37,14 @@ void main()
     vec4 surface_color = albedo;
 #ifdef USE_ALBEDO_TEXTURE
     vec4 c = texture(albedoTexture, uvs);
-    #ifdef ALPHACUT
-        if (c.a < acut) discard;
-    #endif
     surface_color *= vec4(rgb_from_srgb(c.rgb), c.a);
 #endif
 #ifdef USE_VERTEX_COLORS
     surface_color *= col;
 #endif
+#ifdef ALPHACUT
+    if (surface_color.a < acut) discard;
+#endif
 
     float metallic_factor = metallic;
     float roughness_factor = roughness;
@@ -5,27 +5,75 @@ uniform float roughness;
 uniform vec4 albedo;
 #ifdef USE_ALBEDO_TEXTURE
 uniform sampler2D albedoTexture;
+uniform mat3 albedoTextureTransform;
 #endif
 
 uniform vec3 emissive;
 #ifdef USE_EMISSIVE_TEXTURE
 uniform sampler2D emissiveTexture;
+uniform mat3 emissiveTextureTransform;
 #endif
 
 #ifdef USE_METALLIC_ROUGHNESS_TEXTURE
 uniform sampler2D metallicRoughnessTexture;
+uniform mat3 metallicRoughnessTextureTransform;
 #endif
 
 #ifdef USE_OCCLUSION_TEXTURE
 uniform sampler2D occlusionTexture;
+uniform mat3 occlusionTextureTransform;
 uniform float occlusionStrength;
 #endif
 
 #ifdef USE_NORMAL_TEXTURE
 uniform sampler2D normalTexture;
+uniform mat3 normalTextureTransform;
 uniform float normalScale;
 #endif
 
+#ifdef USE_IOR
+uniform float ior;
+#endif
+
+#ifdef USE_CLEARCOAT
+uniform float clearcoat;
+uniform float clearcoatRoughness;
+#ifdef USE_CLEARCOAT_TEXTURE
+uniform sampler2D clearcoatTexture;
+uniform mat3 clearcoatTextureTransform;
+#endif
+#ifdef USE_CLEARCOAT_ROUGHNESS_TEXTURE
+uniform sampler2D clearcoatRoughnessTexture;
+uniform mat3 clearcoatRoughnessTextureTransform;
+#endif
+#ifdef USE_CLEARCOAT_NORMAL_TEXTURE
+uniform sampler2D clearcoatNormalTexture;
+uniform mat3 clearcoatNormalTextureTransform;
+uniform float clearcoatNormalScale;
+#endif
+#endif
+
+#ifdef USE_SHEEN
+uniform vec3 sheenColor;
+uniform float sheenRoughness;
+#ifdef USE_SHEEN_COLOR_TEXTURE
+uniform sampler2D sheenColorTexture;
+uniform mat3 sheenColorTextureTransform;
+#endif
+#ifdef USE_SHEEN_ROUGHNESS_TEXTURE
+uniform sampler2D sheenRoughnessTexture;
+uniform mat3 sheenRoughnessTextureTransform;
+#endif
+#endif
+
+#ifdef USE_TRANSMISSION
+uniform float transmission;
+#ifdef USE_TRANSMISSION_TEXTURE
+uniform sampler2D transmissionTexture;
+uniform mat3 transmissionTextureTransform;
+#endif
+#endif
+
 in vec3 pos;
 in vec3 nor;
 
@@ -35,45 +83,90 @@ void main()
 {
     vec4 surface_color = albedo;
 #ifdef USE_ALBEDO_TEXTURE
-    vec4 c = texture(albedoTexture, uvs);
-    #ifdef ALPHACUT
-        if (c.a < acut) discard;
-    #endif
+    vec4 c = texture(albedoTexture, (albedoTextureTransform * vec3(ALBEDO_UVS, 1.0)).xy);
     surface_color *= vec4(rgb_from_srgb(c.rgb), c.a);
 #endif
 #ifdef USE_VERTEX_COLORS
     surface_color *= col;
 #endif
+#ifdef ALPHACUT
+    if (surface_color.a < acut) discard;
+#endif
 
     float metallic_factor = metallic;
     float roughness_factor = roughness;
 #ifdef USE_METALLIC_ROUGHNESS_TEXTURE
-    vec2 t = texture(metallicRoughnessTexture, uvs).gb;
+    vec2 t = texture(metallicRoughnessTexture, (metallicRoughnessTextureTransform * vec3(METALLIC_ROUGHNESS_UVS, 1.0)).xy).gb;
     roughness_factor *= t.x;
     metallic_factor *= t.y;
 #endif
 
     float occlusion = 1.0;
 #ifdef USE_OCCLUSION_TEXTURE
-    occlusion = mix(1.0, texture(occlusionTexture, uvs).r, occlusionStrength);
+    occlusion = mix(1.0, texture(occlusionTexture, (occlusionTextureTransform * vec3(OCCLUSION_UVS, 1.0)).xy).r, occlusionStrength);
 #endif
 
     vec3 normal = normalize(gl_FrontFacing ? nor : -nor);
+
+#ifdef USE_IOR
+    material_ior = ior;
+#endif
+#ifdef USE_CLEARCOAT
+    // The clear coat layer is not affected by the normal texture, only by the clear coat normal texture
+    material_clearcoat_normal = normal;
+#ifdef USE_CLEARCOAT_NORMAL_TEXTURE
+    vec3 clearcoat_tangent = normalize(gl_FrontFacing ? tang : -tang);
+    vec3 clearcoat_bitangent = normalize(gl_FrontFacing ? bitang : -bitang);
+    mat3 clearcoat_tbn = mat3(clearcoat_tangent, clearcoat_bitangent, normal);
+    material_clearcoat_normal = normalize(clearcoat_tbn * ((2.0 * texture(clearcoatNormalTexture, (clearcoatNormalTextureTransform * vec3(CLEARCOAT_NORMAL_UVS, 1.0)).xy).xyz - 1.0) * vec3(clearcoatNormalScale, clearcoatNormalScale, 1.0)));
+#endif
+    material_clearcoat = clearcoat;
+#ifdef USE_CLEARCOAT_TEXTURE
+    material_clearcoat *= texture(clearcoatTexture, (clearcoatTextureTransform * vec3(CLEARCOAT_UVS, 1.0)).xy).r;
+#endif
+    material_clearcoat_roughness = clearcoatRoughness;
+#ifdef USE_CLEARCOAT_ROUGHNESS_TEXTURE
+    material_clearcoat_roughness *= texture(clearcoatRoughnessTexture, (clearcoatRoughnessTextureTransform * vec3(CLEARCOAT_ROUGHNESS_UVS, 1.0)).xy).g;
+#endif
+#endif
+#ifdef USE_SHEEN
+    material_sheen_color = sheenColor;
+#ifdef USE_SHEEN_COLOR_TEXTURE
+    material_sheen_color *= rgb_from_srgb(texture(sheenColorTexture, (sheenColorTextureTransform * vec3(SHEEN_COLOR_UVS, 1.0)).xy).rgb);
+#endif
+    material_sheen_roughness = sheenRoughness;
+#ifdef USE_SHEEN_ROUGHNESS_TEXTURE
+    material_sheen_roughness *= texture(sheenRoughnessTexture, (sheenRoughnessTextureTransform * vec3(SHEEN_ROUGHNESS_UVS, 1.0)).xy).a;
+#endif
+#endif
+#ifdef USE_TRANSMISSION
+    material_transmission = transmission;
+#ifdef USE_TRANSMISSION_TEXTURE
+    material_transmission *= texture(transmissionTexture, (transmissionTextureTransform * vec3(TRANSMISSION_UVS, 1.0)).xy).r;
+#endif
+#endif
+
 #ifdef USE_NORMAL_TEXTURE
     vec3 tangent = normalize(gl_FrontFacing ? tang : -tang);
     vec3 bitangent = normalize(gl_FrontFacing ? bitang : -bitang);
     mat3 tbn = mat3(tangent, bitangent, normal);
-    normal = tbn * ((2.0 * texture(normalTexture, uvs).xyz - 1.0) * vec3(normalScale, normalScale, 1.0));
+    normal = tbn * ((2.0 * texture(normalTexture, (normalTextureTransform * vec3(NORMAL_UVS, 1.0)).xy).xyz - 1.0) * vec3(normalScale, normalScale, 1.0));
 #endif
 
     vec3 total_emissive = emissive;
 #ifdef USE_EMISSIVE_TEXTURE
-    vec4 e = texture(emissiveTexture, uvs);
+    vec4 e = texture(emissiveTexture, (emissiveTextureTransform * vec3(EMISSIVE_UVS, 1.0)).xy);
     total_emissive *= rgb_from_srgb(e.rgb);
 #endif
 
     outColor.rgb = total_emissive + calculate_lighting(surface_color.rgb, pos, normal, metallic_factor, roughness_factor, occlusion);
     outColor.rgb = reinhard_tone_mapping(outColor.rgb);
     outColor.rgb = srgb_from_rgb(outColor.rgb);
+#ifdef USE_TRANSMISSION
+    // The transmitted light is given by the objects behind, which are blended using premultiplied alpha
+    outColor.rgb *= surface_color.a;
+    outColor.a = surface_color.a * (1.0 - material_transmission * (1.0 - metallic_factor));
+#else
     outColor.a = surface_color.a;
+#endif
 }
\ No newline at end of file
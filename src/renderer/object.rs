@@ -1,8 +1,14 @@
 //!
 //! A collection of objects (implementing the [Object] trait) that can be rendered directly or used in a render call, for example [render_pass].
 //! Can be a combination of any [geometry] and [material] by using the [Shape] struct.
+//! Objects can be organized in a hierarchy with hierarchical transformations using [SceneNode].
 //!
 
+pub use crate::core::{
+    AnimationChannel, AnimationClip, AnimationTarget, AnimationTransformation,
+    KeyframeInterpolation, KeyframeTrack, KeyframeValue,
+};
+
 mod shape;
 #[doc(inline)]
 pub use shape::*;
@@ -43,6 +49,10 @@ mod bounding_box;
 #[doc(inline)]
 pub use bounding_box::*;
 
+mod scene_node;
+#[doc(inline)]
+pub use scene_node::*;
+
 use crate::core::*;
 use crate::renderer::*;
 
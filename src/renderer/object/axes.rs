@@ -53,6 +53,16 @@ impl Axes {
     }
 }
 
+impl Transformable for Axes {
+    fn transformation(&self) -> Mat4 {
+        Axes::transformation(self)
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        Axes::set_transformation(self, transformation)
+    }
+}
+
 impl Geometry for Axes {
     fn aabb(&self) -> AxisAlignedBoundingBox {
         self.aabb
@@ -0,0 +1,274 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// An [Object] that can be placed in a [SceneNode], ie. an object with a local to world transformation.
+/// This is automatically implemented for all objects that implement the [Transformable] trait, for example [Model] and [InstancedModel].
+///
+pub trait SceneObject: Object + Transformable {
+    ///
+    /// Returns this scene object as an [Object].
+    ///
+    fn as_object(&self) -> &dyn Object;
+}
+
+impl<T: Object + Transformable> SceneObject for T {
+    fn as_object(&self) -> &dyn Object {
+        self
+    }
+}
+
+///
+/// A node in a scene graph. Each node has a transformation relative to its parent node, an optional [SceneObject] and any number of child nodes.
+/// The root of the hierarchy represents the entire scene.
+///
+/// The world transformation of a node, which is the transformation of the parent node multiplied with the transformation of the node itself,
+/// is applied to the object in the node and propagated to all descendants whenever the hierarchy or one of the transformations change.
+/// Since a scene node implements [Object], the entire hierarchy can be rendered with a single render call, for example [render_pass],
+/// in which case the objects in the hierarchy are culled and sorted individually, and it can be used in [pick] and [ray_intersect].
+///
+pub struct SceneNode {
+    /// Name. Can be used to find a node in the hierarchy, see [SceneNode::find].
+    pub name: String,
+    /// The index of the node in the file the node is loaded from, if any. Used for matching the node with an [AnimationTarget::Node].
+    pub node_index: Option<usize>,
+    transformation: Mat4,
+    parent_transformation: Mat4,
+    object: Option<Box<dyn SceneObject>>,
+    children: Vec<SceneNode>,
+}
+
+impl SceneNode {
+    ///
+    /// Creates a new scene node without an object, which for example can be used as the root of a scene or to group other nodes.
+    ///
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            node_index: None,
+            transformation: Mat4::identity(),
+            parent_transformation: Mat4::identity(),
+            object: None,
+            children: Vec::new(),
+        }
+    }
+
+    ///
+    /// Creates a new scene node containing the given object.
+    /// Any transformation already applied to the object is overwritten by the world transformation of the node.
+    ///
+    pub fn new_with_object(name: impl Into<String>, object: impl SceneObject + 'static) -> Self {
+        let mut node = Self::new(name);
+        node.set_object(object);
+        node
+    }
+
+    ///
+    /// Returns the transformation of this node relative to its parent node.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the transformation of this node relative to its parent node.
+    /// The new world transformation is applied to the object of this node and propagated to all descendants.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+        self.update(self.parent_transformation);
+    }
+
+    ///
+    /// Returns the local to world transformation of this node, ie. the transformations of all ancestors multiplied with the transformation of this node.
+    ///
+    pub fn world_transformation(&self) -> Mat4 {
+        self.parent_transformation * self.transformation
+    }
+
+    ///
+    /// Returns the object in this node, if any.
+    ///
+    pub fn object(&self) -> Option<&dyn SceneObject> {
+        self.object.as_deref()
+    }
+
+    ///
+    /// Returns a mutable reference to the object in this node, if any.
+    /// **Note:** Changes to the transformation of the object are overwritten the next time the world transformation of this node changes,
+    /// use [SceneNode::set_transformation] instead.
+    ///
+    pub fn object_mut(&mut self) -> Option<&mut (dyn SceneObject + 'static)> {
+        self.object.as_deref_mut()
+    }
+
+    ///
+    /// Set the object in this node and applies the world transformation of this node to it.
+    /// Returns the object previously in this node, if any.
+    ///
+    pub fn set_object(
+        &mut self,
+        object: impl SceneObject + 'static,
+    ) -> Option<Box<dyn SceneObject>> {
+        let mut object: Box<dyn SceneObject> = Box::new(object);
+        object.set_transformation(self.world_transformation());
+        self.object.replace(object)
+    }
+
+    ///
+    /// Removes and returns the object in this node, if any.
+    ///
+    pub fn remove_object(&mut self) -> Option<Box<dyn SceneObject>> {
+        self.object.take()
+    }
+
+    ///
+    /// Adds the given node as the last child of this node and returns a mutable reference to it.
+    /// The world transformation of this node is propagated to the new child and its descendants.
+    ///
+    pub fn add_child(&mut self, mut child: SceneNode) -> &mut SceneNode {
+        child.update(self.world_transformation());
+        self.children.push(child);
+        self.children.last_mut().unwrap()
+    }
+
+    ///
+    /// Removes and returns the child at the given index, if it exists.
+    /// The returned node keeps its world transformation until it is added to another node or its transformation is changed.
+    ///
+    pub fn remove_child(&mut self, index: usize) -> Option<SceneNode> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns the children of this node.
+    ///
+    pub fn children(&self) -> &[SceneNode] {
+        &self.children
+    }
+
+    ///
+    /// Returns a mutable reference to the child at the given index, if it exists.
+    ///
+    pub fn child_mut(&mut self, index: usize) -> Option<&mut SceneNode> {
+        self.children.get_mut(index)
+    }
+
+    ///
+    /// Returns the first node in the hierarchy with this node as root (including this node) with the given name, searching depth-first.
+    ///
+    pub fn find(&self, name: &str) -> Option<&SceneNode> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(name))
+    }
+
+    ///
+    /// Returns a mutable reference to the first node in the hierarchy with this node as root (including this node) with the given name, searching depth-first.
+    ///
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut SceneNode> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find_map(|child| child.find_mut(name))
+    }
+
+    ///
+    /// Sets the transformation of each node in the hierarchy with this node as root (including this node) which is animated by the given clip
+    /// to the value at the given time in seconds. The nodes are matched with the targets of the clip by name or node index, see [AnimationTarget].
+    ///
+    pub fn animate(&mut self, clip: &AnimationClip, time: f32) {
+        self.apply_animation(clip, time);
+        self.update(self.parent_transformation);
+    }
+
+    fn apply_animation(&mut self, clip: &AnimationClip, time: f32) {
+        if let Some(transformation) = clip.transformation_of(&self.name, self.node_index, time) {
+            self.transformation = transformation;
+        }
+        for child in self.children.iter_mut() {
+            child.apply_animation(clip, time);
+        }
+    }
+
+    ///
+    /// Returns all objects in the hierarchy with this node as root, in depth-first order.
+    /// Use this if the objects should be combined with other objects in a render call, for example [render_pass].
+    ///
+    pub fn objects(&self) -> Vec<&dyn Object> {
+        let mut objects = Vec::new();
+        self.collect_objects(&mut objects);
+        objects
+    }
+
+    fn collect_objects<'a>(&'a self, objects: &mut Vec<&'a dyn Object>) {
+        if let Some(ref object) = self.object {
+            objects.push(object.as_object());
+        }
+        for child in self.children.iter() {
+            child.collect_objects(objects);
+        }
+    }
+
+    fn update(&mut self, parent_transformation: Mat4) {
+        self.parent_transformation = parent_transformation;
+        let world_transformation = self.world_transformation();
+        if let Some(ref mut object) = self.object {
+            object.set_transformation(world_transformation);
+        }
+        for child in self.children.iter_mut() {
+            child.update(world_transformation);
+        }
+    }
+}
+
+impl Geometry for SceneNode {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        let mut aabb = AxisAlignedBoundingBox::EMPTY;
+        for object in self.objects() {
+            aabb.expand_with_aabb(&object.aabb());
+        }
+        aabb
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        for object in self.objects() {
+            object.render_with_material(material, camera, lights)?;
+        }
+        Ok(())
+    }
+}
+
+impl Object for SceneNode {
+    fn render(&self, camera: &Camera, lights: &[&dyn Light]) -> ThreeDResult<()> {
+        render_pass(camera, &self.objects(), lights)
+    }
+
+    fn is_transparent(&self) -> bool {
+        self.objects().iter().any(|object| object.is_transparent())
+    }
+}
+
+impl std::fmt::Debug for SceneNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneNode")
+            .field("name", &self.name)
+            .field("node_index", &self.node_index)
+            .field("transformation", &self.transformation)
+            .field("object", &self.object.is_some())
+            .field("children", &self.children)
+            .finish()
+    }
+}
@@ -37,6 +37,16 @@ impl<G: Geometry, M: Material> Object for Shape<G, M> {
     }
 }
 
+impl<G: Geometry + Transformable, M: Material> Transformable for Shape<G, M> {
+    fn transformation(&self) -> Mat4 {
+        self.geometry.transformation()
+    }
+
+    fn set_transformation(&mut self, transformation: Mat4) {
+        self.geometry.set_transformation(transformation)
+    }
+}
+
 impl<G: Geometry + Clone, M: Material + Clone> Clone for Shape<G, M> {
     fn clone(&self) -> Self {
         Self {
@@ -267,6 +267,7 @@ pub struct FrameOutput {
     ///
     /// Takes a screenshot if this is set to some path and saves it at the given location.
     /// Only works on desktop and if the `image-io` feature is enabled.
+    /// To record a sequence of frames, use a [FrameRecorder](crate::FrameRecorder) instead.
     ///
     #[cfg_attr(docsrs, doc(cfg(feature = "image-io")))]
     pub screenshot: Option<std::path::PathBuf>,